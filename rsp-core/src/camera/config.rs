@@ -0,0 +1,147 @@
+use super::{CameraModel, FisheyeCamera, PinholeCamera};
+
+/// Camera description that can be turned into a boxed `CameraModel`
+///
+/// Gives a single dispatch point when cameras of different kinds are loaded
+/// from configuration. Zero distortion coefficients give an ideal camera.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CameraConfig {
+    /// Pinhole camera with Brown-Conrady distortion
+    Pinhole {
+        width: usize,
+        height: usize,
+        fx: f64,
+        fy: f64,
+        cx: f64,
+        cy: f64,
+        k1: f64,
+        k2: f64,
+        k3: f64,
+        p1: f64,
+        p2: f64,
+    },
+    /// Fisheye camera with equidistant (Kannala-Brandt) distortion
+    Fisheye {
+        width: usize,
+        height: usize,
+        fx: f64,
+        fy: f64,
+        cx: f64,
+        cy: f64,
+        k1: f64,
+        k2: f64,
+        k3: f64,
+        k4: f64,
+    },
+}
+
+impl CameraConfig {
+    /// Build the camera described by this config
+    pub fn build(&self) -> Box<dyn CameraModel> {
+        match *self {
+            CameraConfig::Pinhole {
+                width,
+                height,
+                fx,
+                fy,
+                cx,
+                cy,
+                k1,
+                k2,
+                k3,
+                p1,
+                p2,
+            } => {
+                if [k1, k2, k3, p1, p2].iter().all(|&k| k == 0.0) {
+                    Box::new(PinholeCamera::new_ideal(width, height, fx, fy, cx, cy))
+                } else {
+                    Box::new(PinholeCamera::new_brown_conrady(
+                        width, height, fx, fy, cx, cy, k1, k2, k3, p1, p2,
+                    ))
+                }
+            }
+            CameraConfig::Fisheye {
+                width,
+                height,
+                fx,
+                fy,
+                cx,
+                cy,
+                k1,
+                k2,
+                k3,
+                k4,
+            } => Box::new(FisheyeCamera::new(
+                width, height, fx, fy, cx, cy, k1, k2, k3, k4,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Vector3;
+
+    #[test]
+    fn test_pinhole_config_matches_direct() {
+        let config = CameraConfig::Pinhole {
+            width: 1920,
+            height: 1080,
+            fx: 1000.0,
+            fy: 1000.0,
+            cx: 960.0,
+            cy: 540.0,
+            k1: -0.1,
+            k2: 0.01,
+            k3: 0.0,
+            p1: 0.001,
+            p2: -0.001,
+        };
+        let direct = PinholeCamera::new_brown_conrady(
+            1920, 1080, 1000.0, 1000.0, 960.0, 540.0, -0.1, 0.01, 0.0, 0.001, -0.001,
+        );
+        let built = config.build();
+
+        let point = Vector3::new(0.4, -0.3, 2.0);
+        assert_eq!(built.project(&point), direct.project(&point));
+        assert_eq!(built.image_size(), direct.image_size());
+    }
+
+    #[test]
+    fn test_heterogeneous_cameras() {
+        let configs = [
+            CameraConfig::Pinhole {
+                width: 640,
+                height: 480,
+                fx: 500.0,
+                fy: 500.0,
+                cx: 320.0,
+                cy: 240.0,
+                k1: 0.0,
+                k2: 0.0,
+                k3: 0.0,
+                p1: 0.0,
+                p2: 0.0,
+            },
+            CameraConfig::Fisheye {
+                width: 640,
+                height: 480,
+                fx: 300.0,
+                fy: 300.0,
+                cx: 320.0,
+                cy: 240.0,
+                k1: 0.01,
+                k2: 0.0,
+                k3: 0.0,
+                k4: 0.0,
+            },
+        ];
+
+        let cameras: Vec<Box<dyn CameraModel>> = configs.iter().map(|c| c.build()).collect();
+        for camera in cameras.iter() {
+            let ray = camera.unproject((320.0, 240.0)).unwrap();
+            assert!((ray - Vector3::z()).norm() < 1e-9);
+        }
+    }
+}
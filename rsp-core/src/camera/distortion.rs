@@ -1,3 +1,24 @@
+use crate::error::DistortionError;
+use crate::math::solve_2x2;
+
+/// Convergence settings for the iterative undistortion solver
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UndistortOptions {
+    /// Maximum absolute residual (normalized coordinates) in x and y
+    pub tol: f64,
+    /// Maximum Newton iterations
+    pub max_iter: usize,
+}
+
+impl Default for UndistortOptions {
+    fn default() -> Self {
+        Self {
+            tol: 1e-8,
+            max_iter: 10,
+        }
+    }
+}
+
 /// Internal distortion models used by camera implementations
 #[derive(Debug, Clone)]
 pub(super) enum DistortionModel {
@@ -61,22 +82,51 @@ impl DistortionModel {
         }
     }
 
-    /// Remove distortion from image coordinates using Newton-Raphson iteration
+    /// Whether two models are the same kind with coefficients within `eps`
+    pub(super) fn approx_eq(&self, other: &Self, eps: f64) -> bool {
+        let close = |pairs: &[(f64, f64)]| pairs.iter().all(|(a, b)| (a - b).abs() <= eps);
+
+        match (self, other) {
+            (DistortionModel::None, DistortionModel::None) => true,
+            (
+                DistortionModel::BrownConrady { k1, k2, k3, p1, p2 },
+                DistortionModel::BrownConrady { k1: o1, k2: o2, k3: o3, p1: q1, p2: q2 },
+            ) => close(&[(*k1, *o1), (*k2, *o2), (*k3, *o3), (*p1, *q1), (*p2, *q2)]),
+            (
+                DistortionModel::Fisheye { k1, k2, k3, k4 },
+                DistortionModel::Fisheye { k1: o1, k2: o2, k3: o3, k4: o4 },
+            ) => close(&[(*k1, *o1), (*k2, *o2), (*k3, *o3), (*k4, *o4)]),
+            _ => false,
+        }
+    }
 
-    pub(super) fn undistort(&self, x_dist: f64, y_dist: f64) -> (f64, f64) {
+    /// Remove distortion from image coordinates using Newton-Raphson iteration
+    ///
+    /// Fails fast with `SingularJacobian` if the residuals or Jacobian become
+    /// non-finite (e.g. `distort` overflowing for extreme inputs).
+    pub(super) fn undistort_with(
+        &self,
+        x_dist: f64,
+        y_dist: f64,
+        options: &UndistortOptions,
+    ) -> Result<(f64, f64), DistortionError> {
         match self {
-            DistortionModel::None => (x_dist, y_dist),
+            DistortionModel::None => Ok((x_dist, y_dist)),
             _ => {
                 let mut x = x_dist;
                 let mut y = y_dist;
 
-                for _ in 0..10 {
+                for _ in 0..options.max_iter {
                     let (fx, fy) = self.distort(x, y);
                     let rx = x_dist - fx;
                     let ry = y_dist - fy;
 
-                    if rx.abs() < 1e-8 && ry.abs() < 1e-10 {
-                        break;
+                    if !rx.is_finite() || !ry.is_finite() {
+                        return Err(DistortionError::SingularJacobian);
+                    }
+
+                    if rx.abs() < options.tol && ry.abs() < options.tol {
+                        return Ok((x, y));
                     }
 
                     // Finite-difference Jacobian
@@ -90,33 +140,29 @@ impl DistortionModel {
                     let j22 = (fy_y - fy) / eps;
 
                     // Solve J * [dx, dy]^T = [rx, ry]^T
-                    let det = j11 * j22 - j12 * j21;
-                    if det.abs() < 1e-18 {
-                        break; // Degenerate Jacobian, bail
-                    }
-
-                    let dx = (j22 * rx - j12 * ry) / det;
-                    let dy = (-j21 * rx + j11 * ry) / det;
+                    let [dx, dy] = solve_2x2([[j11, j12], [j21, j22]], [rx, ry])
+                        .map_err(|_| DistortionError::SingularJacobian)?;
 
                     x += dx;
                     y += dy;
                 }
 
-                (x, y)
+                Err(DistortionError::NonConvergent(options.max_iter))
             }
         }
     }
 }
 #[cfg(test)]
 mod tests {
-    use super::DistortionModel;
+    use super::{DistortionModel, UndistortOptions};
+    use crate::error::DistortionError;
 
     #[test]
     fn none_round_trip() {
         let m = DistortionModel::None;
         let (x, y) = (0.123, -0.456);
         let (xd, yd) = m.distort(x, y);
-        let (xu, yu) = m.undistort(xd, yd);
+        let (xu, yu) = m.undistort_with(xd, yd, &UndistortOptions::default()).unwrap();
         assert!((x - xu).abs() < 1e-12);
         assert!((y - yu).abs() < 1e-12);
     }
@@ -132,7 +178,7 @@ mod tests {
         };
         let (x, y) = (0.2, -0.15);
         let (xd, yd) = m.distort(x, y);
-        let (xu, yu) = m.undistort(xd, yd);
+        let (xu, yu) = m.undistort_with(xd, yd, &UndistortOptions::default()).unwrap();
         assert!((x - xu).abs() < 1e-6);
         assert!((y - yu).abs() < 1e-6);
     }
@@ -147,8 +193,73 @@ mod tests {
         };
         let (x, y) = (0.3, 0.1);
         let (xd, yd) = m.distort(x, y);
-        let (xu, yu) = m.undistort(xd, yd);
+        let (xu, yu) = m.undistort_with(xd, yd, &UndistortOptions::default()).unwrap();
         assert!((x - xu).abs() < 1e-6);
         assert!((y - yu).abs() < 1e-6);
     }
+
+    #[test]
+    fn extreme_input_is_singular() {
+        let m = DistortionModel::BrownConrady {
+            k1: -0.5,
+            k2: 0.2,
+            k3: 0.1,
+            p1: 0.0,
+            p2: 0.0,
+        };
+
+        // r^6 overflows to infinity and poisons the residual
+        let result = m.undistort_with(1e200, -1e200, &UndistortOptions::default());
+        assert!(matches!(result, Err(DistortionError::SingularJacobian)));
+    }
+
+    #[test]
+    fn symmetric_tolerance_converges_near_fold() {
+        // Strong barrel distortion close to where the radial mapping folds,
+        // so Newton needs several iterations
+        let m = DistortionModel::BrownConrady {
+            k1: -0.3,
+            k2: 0.0,
+            k3: 0.0,
+            p1: 0.0,
+            p2: 0.0,
+        };
+        let (x, y) = (0.57, 0.76);
+        let (xd, yd) = m.distort(x, y);
+
+        let options = UndistortOptions::default();
+        let (xu, yu) = m.undistort_with(xd, yd, &options).unwrap();
+        let (xr, yr) = m.distort(xu, yu);
+        assert!((xr - xd).abs() < options.tol);
+        assert!((yr - yd).abs() < options.tol);
+        assert!((x - xu).abs() < 1e-6);
+        assert!((y - yu).abs() < 1e-6);
+    }
+
+    #[test]
+    fn iteration_limit_reported() {
+        let m = DistortionModel::BrownConrady {
+            k1: -0.3,
+            k2: 0.0,
+            k3: 0.0,
+            p1: 0.0,
+            p2: 0.0,
+        };
+        let (xd, yd) = m.distort(0.57, 0.76);
+
+        let options = UndistortOptions { tol: 1e-12, max_iter: 1 };
+        let result = m.undistort_with(xd, yd, &options);
+        assert!(matches!(result, Err(DistortionError::NonConvergent(1))));
+    }
+
+    #[test]
+    fn test_approx_eq_requires_same_model() {
+        let brown = DistortionModel::BrownConrady { k1: 0.1, k2: 0.0, k3: 0.0, p1: 0.0, p2: 0.0 };
+        let fisheye = DistortionModel::Fisheye { k1: 0.1, k2: 0.0, k3: 0.0, k4: 0.0 };
+
+        assert!(DistortionModel::None.approx_eq(&DistortionModel::None, 0.0));
+        assert!(brown.approx_eq(&brown.clone(), 0.0));
+        assert!(!brown.approx_eq(&fisheye, 1.0));
+        assert!(!DistortionModel::None.approx_eq(&brown, 1.0));
+    }
 }
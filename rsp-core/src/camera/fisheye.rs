@@ -1,6 +1,11 @@
-use super::{distortion::DistortionModel, CameraModel};
+use super::{
+    distortion::{DistortionModel, UndistortOptions},
+    intrinsics_approx_eq, CameraModel, Intrinsics,
+};
 use nalgebra::Vector3;
 
+use crate::error::{ProjectionError, Result};
+
 /// Fisheye camera model
 #[derive(Debug, Clone)]
 pub struct FisheyeCamera {
@@ -37,6 +42,48 @@ impl FisheyeCamera {
             distortion: DistortionModel::Fisheye { k1, k2, k3, k4 },
         }
     }
+
+    /// Whether two cameras have the same size, intrinsics and distortion within `eps`
+    pub fn approx_eq(&self, other: &Self, eps: f64) -> bool {
+        intrinsics_approx_eq(self.intrinsics(), other.intrinsics(), eps)
+    }
+
+    fn intrinsics(&self) -> Intrinsics<'_> {
+        ((self.width, self.height), [self.fx, self.fy, self.cx, self.cy], &self.distortion)
+    }
+
+    /// Project a camera-frame point, reporting why it has no pixel
+    ///
+    /// The equidistant model only covers the hemisphere in front of the lens
+    /// (`z > 0`, a 180 degree field of view); anything else is
+    /// `BehindCamera`. Points inside that field of view that land outside
+    /// `[0, width) x [0, height)` are `OutOfBounds`.
+    pub fn project_bounded(
+        &self,
+        point_camera: &Vector3<f64>,
+    ) -> std::result::Result<(f64, f64), ProjectionError> {
+        let (u, v) = self.project(point_camera).ok_or(ProjectionError::BehindCamera)?;
+
+        let (width, height) = (self.width as f64, self.height as f64);
+        if !(0.0..width).contains(&u) || !(0.0..height).contains(&v) {
+            return Err(ProjectionError::OutOfBounds { line: v, sample: u });
+        }
+        Ok((u, v))
+    }
+
+    /// Unproject with explicit undistortion solver settings
+    pub fn unproject_with(
+        &self,
+        pixel: (f64, f64),
+        options: &UndistortOptions,
+    ) -> Result<Vector3<f64>> {
+        let x_dist = (pixel.0 - self.cx) / self.fx;
+        let y_dist = (pixel.1 - self.cy) / self.fy;
+
+        let (x_norm, y_norm) = self.distortion.undistort_with(x_dist, y_dist, options)?;
+
+        Ok(Vector3::new(x_norm, y_norm, 1.0).normalize())
+    }
 }
 
 impl CameraModel for FisheyeCamera {
@@ -56,13 +103,8 @@ impl CameraModel for FisheyeCamera {
         Some((u, v))
     }
 
-    fn unproject(&self, pixel: (f64, f64)) -> Vector3<f64> {
-        let x_dist = (pixel.0 - self.cx) / self.fx;
-        let y_dist = (pixel.1 - self.cy) / self.fy;
-
-        let (x_norm, y_norm) = self.distortion.undistort(x_dist, y_dist);
-
-        Vector3::new(x_norm, y_norm, 1.0).normalize()
+    fn unproject(&self, pixel: (f64, f64)) -> Result<Vector3<f64>> {
+        self.unproject_with(pixel, &UndistortOptions::default())
     }
 
     fn image_size(&self) -> (usize, usize) {
@@ -131,7 +173,7 @@ mod tests {
         );
 
         // Unproject center pixel
-        let ray = camera.unproject((960.0, 540.0));
+        let ray = camera.unproject((960.0, 540.0)).unwrap();
 
         // Ray should be normalized and pointing forward
         assert!((ray.norm() - 1.0).abs() < 1e-6);
@@ -149,7 +191,7 @@ mod tests {
 
         let point = Vector3::new(0.5, 0.3, 2.0);
         let pixel = camera.project(&point).unwrap();
-        let ray = camera.unproject(pixel);
+        let ray = camera.unproject(pixel).unwrap();
 
         // Ray direction should be parallel to original point
         let original_normalized = point.normalize();
@@ -204,4 +246,39 @@ mod tests {
         assert_eq!(w, 2560);
         assert_eq!(h, 1440);
     }
+
+    #[test]
+    fn test_fisheye_approx_eq() {
+        let camera = |fx: f64, k4: f64| {
+            FisheyeCamera::new(1920, 1080, fx, 800.0, 960.0, 540.0, -0.1, 0.05, -0.01, k4)
+        };
+        let a = camera(800.0, 0.001);
+
+        assert!(a.approx_eq(&camera(800.0 + 1e-12, 0.001), 1e-9));
+        assert!(!a.approx_eq(&camera(801.0, 0.001), 1e-9));
+        assert!(!a.approx_eq(&camera(800.0, 0.002), 1e-9));
+    }
+
+    #[test]
+    fn test_fisheye_project_bounded() {
+        let camera = FisheyeCamera::new(640, 480, 300.0, 300.0, 320.0, 240.0, 0.0, 0.0, 0.0, 0.0);
+        let at_angle = |deg: f64| Vector3::new(deg.to_radians().tan(), 0.0, 1.0);
+
+        // Equidistant: 30 degrees off axis lands at 300 * pi / 6 from the center
+        let (u, v) = camera.project_bounded(&at_angle(30.0)).unwrap();
+        assert!((u - (320.0 + 50.0 * std::f64::consts::PI)).abs() < 1e-9);
+        assert!((v - 240.0).abs() < 1e-9);
+
+        // Within the lens field of view but past the sensor edge
+        assert!(matches!(
+            camera.project_bounded(&at_angle(80.0)),
+            Err(ProjectionError::OutOfBounds { line, sample }) if line == 240.0 && sample > 640.0
+        ));
+
+        // Behind the lens
+        assert!(matches!(
+            camera.project_bounded(&Vector3::new(0.1, 0.2, -1.0)),
+            Err(ProjectionError::BehindCamera)
+        ));
+    }
 }
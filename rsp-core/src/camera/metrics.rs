@@ -0,0 +1,94 @@
+use nalgebra::Vector3;
+
+use super::CameraModel;
+use crate::error::{Result, RspError};
+
+/// Angular reprojection error between observed pixels and expected rays
+///
+/// Each pixel is unprojected and compared with the matching expected ray
+/// (camera frame, any non-zero length). Returns the RMS angle and the
+/// per-point angles, all in radians. Unlike pixel error this doesn't grow
+/// towards the edge of wide-angle and fisheye images.
+pub fn angular_error<C: CameraModel + ?Sized>(
+    camera: &C,
+    rays_expected: &[Vector3<f64>],
+    pixels: &[(f64, f64)],
+) -> Result<(f64, Vec<f64>)> {
+    if rays_expected.len() != pixels.len() {
+        return Err(RspError::InvalidInput(format!(
+            "Got {} expected rays for {} pixels",
+            rays_expected.len(),
+            pixels.len()
+        )));
+    }
+    if pixels.is_empty() {
+        return Err(RspError::InvalidInput(
+            "Angular error needs at least one point".to_string(),
+        ));
+    }
+
+    let mut angles = Vec::with_capacity(pixels.len());
+    for (expected, &pixel) in rays_expected.iter().zip(pixels) {
+        if expected.norm() == 0.0 {
+            return Err(RspError::InvalidInput("Expected ray has zero length".to_string()));
+        }
+        let ray = camera.unproject(pixel)?;
+
+        // atan2 stays accurate for small angles, where acos of the dot doesn't
+        angles.push(ray.cross(expected).norm().atan2(ray.dot(expected)));
+    }
+
+    let rms = (angles.iter().map(|a| a * a).sum::<f64>() / angles.len() as f64).sqrt();
+    Ok((rms, angles))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::FisheyeCamera;
+
+    fn fisheye() -> FisheyeCamera {
+        FisheyeCamera::new(1280, 960, 400.0, 400.0, 640.0, 480.0, 0.01, 0.001, 0.0, 0.0)
+    }
+
+    /// Rays spread out to 70 degrees off-axis
+    fn wide_rays() -> Vec<Vector3<f64>> {
+        (0..12)
+            .map(|i| {
+                let theta = (70.0f64 * i as f64 / 11.0).to_radians();
+                let phi = 0.9 * i as f64;
+                Vector3::new(theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos()) * 3.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_angular_error_projected_rays() {
+        let camera = fisheye();
+        let rays = wide_rays();
+        let pixels: Vec<_> = rays.iter().map(|r| camera.project(r).unwrap()).collect();
+
+        let (rms, angles) = angular_error(&camera, &rays, &pixels).unwrap();
+        assert_eq!(angles.len(), rays.len());
+        assert!(rms < 1e-8);
+        assert!(angles.iter().all(|a| *a < 1e-8));
+    }
+
+    #[test]
+    fn test_angular_error_offset_pixel() {
+        let camera = fisheye();
+        let ray = Vector3::new(0.0, 0.0, 1.0);
+
+        // One pixel off the principal point is about 1 / f radians
+        let (rms, angles) = angular_error(&camera, &[ray], &[(641.0, 480.0)]).unwrap();
+        assert!((angles[0] - 1.0 / 400.0).abs() < 1e-6);
+        assert_eq!(rms, angles[0]);
+    }
+
+    #[test]
+    fn test_angular_error_length_mismatch() {
+        let camera = fisheye();
+        let result = angular_error(&camera, &wide_rays(), &[(640.0, 480.0)]);
+        assert!(matches!(result, Err(RspError::InvalidInput(_))));
+    }
+}
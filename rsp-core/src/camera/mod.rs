@@ -1,24 +1,125 @@
 //! Camera models and projections
 
+mod config;
 mod distortion;
 mod fisheye;
+mod metrics;
 mod pinhole;
+mod pose;
 
+pub use config::CameraConfig;
+pub use distortion::UndistortOptions;
 pub use fisheye::FisheyeCamera;
+pub use metrics::angular_error;
 pub use pinhole::PinholeCamera;
+pub use pose::{pixel_ray, CameraPose};
 
+use distortion::DistortionModel;
 use nalgebra::Vector3;
 
+use crate::error::{ProjectionError, Result};
+
+/// Calibration of a frame camera: image size, `[fx, fy, cx, cy]` and distortion
+type Intrinsics<'a> = ((usize, usize), [f64; 4], &'a DistortionModel);
+
+/// Whether two calibrations match within `eps`
+///
+/// Image sizes must be equal; focal lengths, principal points and the
+/// distortion coefficients are compared with an absolute tolerance, and
+/// the distortion models must be of the same kind.
+fn intrinsics_approx_eq(a: Intrinsics, b: Intrinsics, eps: f64) -> bool {
+    let ((size_a, k_a, distortion_a), (size_b, k_b, distortion_b)) = (a, b);
+    size_a == size_b
+        && k_a.iter().zip(&k_b).all(|(x, y)| (x - y).abs() <= eps)
+        && distortion_a.approx_eq(distortion_b, eps)
+}
+
 /// Generic CameraModel
-pub trait CameraModel {
+pub trait CameraModel: CameraModelClone {
     /// Project 3D point in camera frame to image coordinates
     /// Returns None if point is behind camera
 
     fn project(&self, point_camera: &Vector3<f64>) -> Option<(f64, f64)>;
 
+    /// [`project`](CameraModel::project) as a `Result`, for use with `?`
+    ///
+    /// A point with no projection is reported as `BehindCamera`.
+    fn try_project(
+        &self,
+        point_camera: &Vector3<f64>,
+    ) -> std::result::Result<(f64, f64), ProjectionError> {
+        self.project(point_camera).ok_or(ProjectionError::BehindCamera)
+    }
+
     /// Unproject image coordinates to unit ray in camera frame
-    fn unproject(&self, pixel: (f64, f64)) -> Vector3<f64>;
+    /// Fails if lens distortion can't be removed at this pixel
+    fn unproject(&self, pixel: (f64, f64)) -> Result<Vector3<f64>>;
 
     /// Get image dimesnsions this camera is calibrated for
     fn image_size(&self) -> (usize, usize);
 }
+
+/// Cloning support for boxed camera models
+///
+/// Implemented for every `Clone + 'static` camera, so `Box<dyn CameraModel>`
+/// can be cloned without knowing the concrete type.
+pub trait CameraModelClone {
+    /// Clone this camera into a new box
+    fn clone_box(&self) -> Box<dyn CameraModel>;
+}
+
+impl<T> CameraModelClone for T
+where
+    T: CameraModel + Clone + 'static,
+{
+    fn clone_box(&self) -> Box<dyn CameraModel> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn CameraModel> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boxed_camera_clone() {
+        let cameras: Vec<Box<dyn CameraModel>> = vec![
+            Box::new(PinholeCamera::new_brown_conrady(
+                1920, 1080, 1000.0, 1000.0, 960.0, 540.0, -0.1, 0.01, 0.0, 0.001, 0.0,
+            )),
+            Box::new(FisheyeCamera::new(
+                1280, 960, 400.0, 400.0, 640.0, 480.0, 0.01, 0.001, 0.0, 0.0,
+            )),
+        ];
+
+        let clones = cameras.clone();
+        let point = Vector3::new(0.3, -0.2, 2.0);
+        for (camera, clone) in cameras.iter().zip(clones.iter()) {
+            let pixel = clone.project(&point).unwrap();
+            assert_eq!(Some(pixel), camera.project(&point));
+            assert_eq!(clone.image_size(), camera.image_size());
+        }
+    }
+
+    #[test]
+    fn test_try_project_behind_camera() {
+        let cameras: Vec<Box<dyn CameraModel>> = vec![
+            Box::new(PinholeCamera::new_ideal(640, 480, 500.0, 500.0, 320.0, 240.0)),
+            Box::new(FisheyeCamera::new(640, 480, 300.0, 300.0, 320.0, 240.0, 0.0, 0.0, 0.0, 0.0)),
+        ];
+
+        for camera in &cameras {
+            let behind = camera.try_project(&Vector3::new(0.1, -0.1, -2.0));
+            assert!(matches!(behind, Err(ProjectionError::BehindCamera)));
+
+            let point = Vector3::new(0.1, -0.1, 2.0);
+            assert_eq!(camera.try_project(&point).ok(), camera.project(&point));
+        }
+    }
+}
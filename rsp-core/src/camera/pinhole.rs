@@ -1,7 +1,17 @@
-use super::{distortion::DistortionModel, CameraModel};
-use nalgebra::Vector3;
+use super::{
+    distortion::{DistortionModel, UndistortOptions},
+    intrinsics_approx_eq, pixel_ray, CameraModel, CameraPose, Intrinsics,
+};
+use nalgebra::{Isometry3, Matrix3, Vector3};
+use ndarray::Array2;
+
+use crate::error::{Result, RspError};
+use crate::warp::Resampler;
 
 /// Pinhole camera model with optional distortion
+///
+/// Carries a world-to-camera pose for [`PinholeCamera::project_world`];
+/// cameras built from intrinsics alone sit at the world origin.
 #[derive(Debug, Clone)]
 pub struct PinholeCamera {
     width: usize,
@@ -11,6 +21,7 @@ pub struct PinholeCamera {
     cx: f64,
     cy: f64,
     distortion: DistortionModel,
+    pose: CameraPose,
 }
 
 impl PinholeCamera {
@@ -36,6 +47,7 @@ impl PinholeCamera {
             cx,
             cy,
             distortion: DistortionModel::BrownConrady { k1, k2, k3, p1, p2 },
+            pose: CameraPose::identity(),
         }
     }
 
@@ -49,6 +61,57 @@ impl PinholeCamera {
             cx,
             cy,
             distortion: DistortionModel::None,
+            pose: CameraPose::identity(),
+        }
+    }
+
+    /// Check the intrinsics of a constructed camera
+    ///
+    /// Fails with `InvalidInput` unless both focal lengths are positive and
+    /// finite and the principal point is finite, since `unproject` divides by
+    /// the focal lengths. Chain after any constructor, e.g.
+    /// `PinholeCamera::new_ideal(..).validated()?` or
+    /// `PinholeCamera::from_k_and_pose(..).validated()?`. A principal point
+    /// off the image is legal (e.g. for cropped images) and is reported by
+    /// [`PinholeCamera::principal_point_in_image`] instead.
+    pub fn validated(self) -> Result<Self> {
+        if !(self.fx > 0.0 && self.fy > 0.0 && self.fx.is_finite() && self.fy.is_finite()) {
+            return Err(RspError::InvalidInput(format!(
+                "Focal lengths must be positive and finite, got fx={} fy={}",
+                self.fx, self.fy
+            )));
+        }
+        if !(self.cx.is_finite() && self.cy.is_finite()) {
+            return Err(RspError::InvalidInput(format!(
+                "Principal point must be finite, got ({}, {})",
+                self.cx, self.cy
+            )));
+        }
+        Ok(self)
+    }
+
+    /// Whether the principal point lies within `[0, width] x [0, height]`
+    ///
+    /// A point outside usually means swapped or mis-scaled intrinsics.
+    pub fn principal_point_in_image(&self) -> bool {
+        (0.0..=self.width as f64).contains(&self.cx)
+            && (0.0..=self.height as f64).contains(&self.cy)
+    }
+
+    /// Create an undistorted camera from a calibration matrix and a pose
+    ///
+    /// `k` is the usual upper-triangular intrinsic matrix (its skew term is
+    /// ignored) and `pose` maps world points into the camera frame, as
+    /// produced by SfM tools.
+    pub fn from_k_and_pose(
+        width: usize,
+        height: usize,
+        k: &Matrix3<f64>,
+        pose: &Isometry3<f64>,
+    ) -> Self {
+        Self {
+            pose: CameraPose::from_isometry(pose),
+            ..Self::new_ideal(width, height, k[(0, 0)], k[(1, 1)], k[(0, 2)], k[(1, 2)])
         }
     }
 
@@ -61,6 +124,139 @@ impl PinholeCamera {
     pub fn principal_point(&self) -> (f64, f64) {
         (self.cx, self.cy)
     }
+
+    /// World-to-camera pose
+    pub fn pose(&self) -> &CameraPose {
+        &self.pose
+    }
+
+    /// Camera position in world coordinates (`-R^T t`)
+    pub fn camera_center(&self) -> Vector3<f64> {
+        self.pose.center()
+    }
+
+    /// Project a world point through the pose and intrinsics
+    ///
+    /// Returns `None` for points at or behind the camera plane.
+    pub fn project_world(&self, point_world: &Vector3<f64>) -> Option<(f64, f64)> {
+        self.project(&self.pose.world_to_camera(point_world))
+    }
+
+    /// Whether two cameras have the same size, intrinsics and distortion within `eps`
+    ///
+    /// Only the calibration is compared; see
+    /// [`PinholeCamera::approx_eq_with_pose`] to include the pose.
+    pub fn approx_eq(&self, other: &Self, eps: f64) -> bool {
+        intrinsics_approx_eq(self.intrinsics(), other.intrinsics(), eps)
+    }
+
+    fn intrinsics(&self) -> Intrinsics<'_> {
+        ((self.width, self.height), [self.fx, self.fy, self.cx, self.cy], &self.distortion)
+    }
+
+    /// [`approx_eq`](Self::approx_eq) that also compares the embedded poses
+    ///
+    /// Intrinsics use `eps` (pixels), while the poses must agree to
+    /// `rotation_eps` in rotation angle (radians) and `translation_eps` in
+    /// translation (world units).
+    pub fn approx_eq_with_pose(
+        &self,
+        other: &Self,
+        eps: f64,
+        rotation_eps: f64,
+        translation_eps: f64,
+    ) -> bool {
+        self.approx_eq(other, eps)
+            && self.pose.approx_eq(&other.pose, rotation_eps, translation_eps)
+    }
+
+    /// Remove lens distortion from an image taken with this camera
+    ///
+    /// Each output pixel is treated as a pixel of the distortion-free camera
+    /// with the same intrinsics; its position in the distorted input is found
+    /// by applying the distortion model and interpolated with `resampler`.
+    /// The output has the input's size and is NaN where the distorted
+    /// position falls outside the input.
+    pub fn undistort_image(&self, image: &Array2<f32>, resampler: &dyn Resampler) -> Array2<f32> {
+        let view = image.view();
+        Array2::from_shape_fn(image.dim(), |(row, col)| {
+            let x_norm = (col as f64 - self.cx) / self.fx;
+            let y_norm = (row as f64 - self.cy) / self.fy;
+            let (x_dist, y_dist) = self.distortion.distort(x_norm, y_norm);
+            resampler.sample(&view, self.fx * x_dist + self.cx, self.fy * y_dist + self.cy)
+        })
+    }
+
+    /// Largest distort/undistort round-trip error over the image
+    ///
+    /// Lays a `samples` x `samples` grid of pixels over the image, corners
+    /// included, and takes their distortion-free normalized coordinates
+    /// through `distort` and back with the default undistortion settings.
+    /// Returns the largest round-trip error in normalized coordinates; a
+    /// model that folds over within the image can converge to the wrong
+    /// point, which shows up here as a large error.
+    ///
+    /// Fails with `InvalidInput` for fewer than 2 samples, and with the
+    /// solver's error if any point doesn't converge.
+    pub fn check_distortion_invertible(&self, samples: usize) -> Result<f64> {
+        if samples < 2 {
+            return Err(RspError::InvalidInput(format!(
+                "Distortion check needs at least 2 samples per axis, got {}",
+                samples
+            )));
+        }
+
+        let options = UndistortOptions::default();
+        let step = |size: usize, i: usize| (size as f64 - 1.0) * i as f64 / (samples - 1) as f64;
+
+        let mut max_error: f64 = 0.0;
+        for i in 0..samples {
+            for j in 0..samples {
+                let x = (step(self.width, j) - self.cx) / self.fx;
+                let y = (step(self.height, i) - self.cy) / self.fy;
+
+                let (x_dist, y_dist) = self.distortion.distort(x, y);
+                let (x_back, y_back) = self.distortion.undistort_with(x_dist, y_dist, &options)?;
+                max_error = max_error.max((x_back - x).hypot(y_back - y));
+            }
+        }
+
+        Ok(max_error)
+    }
+
+    /// Unproject with explicit undistortion solver settings
+    pub fn unproject_with(
+        &self,
+        pixel: (f64, f64),
+        options: &UndistortOptions,
+    ) -> Result<Vector3<f64>> {
+        // Pixel to distorted normalized coordinates
+        let x_dist = (pixel.0 - self.cx) / self.fx;
+        let y_dist = (pixel.1 - self.cy) / self.fy;
+
+        // Remove distortion
+        let (x_norm, y_norm) = self.distortion.undistort_with(x_dist, y_dist, options)?;
+
+        // Ray in camera frame (unit vector)
+        Ok(Vector3::new(x_norm, y_norm, 1.0).normalize())
+    }
+
+    /// World-space ray through a pixel for a camera placed at `pose`
+    ///
+    /// Removes distortion, unprojects and rotates the ray out of the camera
+    /// frame of `pose` (world-to-camera, as everywhere else). Returns
+    /// `(origin, direction)`: the camera center and a unit direction in world
+    /// coordinates. Fails if undistortion doesn't converge.
+    ///
+    /// The camera's own [`pose`](PinholeCamera::pose) is ignored; pass
+    /// `camera.pose()` to cast the ray from it.
+    pub fn backproject_ray(
+        &self,
+        pose: &CameraPose,
+        pixel: (f64, f64),
+    ) -> Result<(Vector3<f64>, Vector3<f64>)> {
+        pixel_ray(self, pose, pixel)
+    }
 }
 
 impl CameraModel for PinholeCamera {
@@ -83,16 +279,8 @@ impl CameraModel for PinholeCamera {
         Some((u, v))
     }
 
-    fn unproject(&self, pixel: (f64, f64)) -> Vector3<f64> {
-        // Pixel to distorted normalized coordinates
-        let x_dist = (pixel.0 - self.cx) / self.fx;
-        let y_dist = (pixel.1 - self.cy) / self.fy;
-
-        // Remove distortion
-        let (x_norm, y_norm) = self.distortion.undistort(x_dist, y_dist);
-
-        // Ray in camera frame (unit vector)
-        Vector3::new(x_norm, y_norm, 1.0).normalize()
+    fn unproject(&self, pixel: (f64, f64)) -> Result<Vector3<f64>> {
+        self.unproject_with(pixel, &UndistortOptions::default())
     }
 
     fn image_size(&self) -> (usize, usize) {
@@ -103,6 +291,7 @@ impl CameraModel for PinholeCamera {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use nalgebra::Rotation3;
 
     #[test]
     fn test_pinhole_ideal_projection() {
@@ -151,7 +340,7 @@ mod tests {
         let camera = PinholeCamera::new_ideal(1920, 1080, 1000.0, 1000.0, 960.0, 540.0);
 
         // Unproject center pixel
-        let ray = camera.unproject((960.0, 540.0));
+        let ray = camera.unproject((960.0, 540.0)).unwrap();
 
         // Ray should be normalized and pointing forward
         assert!((ray.norm() - 1.0).abs() < 1e-6);
@@ -167,7 +356,7 @@ mod tests {
         // Test roundtrip: project then unproject
         let point = Vector3::new(0.5, 0.3, 2.0);
         let pixel = camera.project(&point).unwrap();
-        let ray = camera.unproject(pixel);
+        let ray = camera.unproject(pixel).unwrap();
 
         // Ray direction should be parallel to original point
         let original_normalized = point.normalize();
@@ -244,4 +433,149 @@ mod tests {
         let (u, _) = pixel.unwrap();
         assert!(u > 2000.0);
     }
+
+    #[test]
+    fn test_pinhole_approx_eq() {
+        let a = PinholeCamera::new_brown_conrady(
+            1920, 1080, 1000.0, 1000.0, 960.0, 540.0, -0.1, 0.01, 0.0, 0.001, -0.002,
+        );
+        let near = PinholeCamera::new_brown_conrady(
+            1920, 1080, 1000.0 + 1e-12, 1000.0, 960.0, 540.0, -0.1, 0.01, 0.0, 0.001, -0.002,
+        );
+        let far = PinholeCamera::new_brown_conrady(
+            1920, 1080, 1001.0, 1000.0, 960.0, 540.0, -0.1, 0.01, 0.0, 0.001, -0.002,
+        );
+
+        assert!(a.approx_eq(&near, 1e-9));
+        assert!(!a.approx_eq(&far, 1e-9));
+
+        // Same intrinsics but no distortion, or a different size
+        let ideal = PinholeCamera::new_ideal(1920, 1080, 1000.0, 1000.0, 960.0, 540.0);
+        assert!(!a.approx_eq(&ideal, 1e-9));
+        let smaller = PinholeCamera::new_ideal(1280, 1080, 1000.0, 1000.0, 960.0, 540.0);
+        assert!(!ideal.approx_eq(&smaller, 1e-9));
+    }
+
+    #[test]
+    fn test_pinhole_validation() {
+        let camera = PinholeCamera::new_ideal(1920, 1080, 1000.0, 1000.0, 960.0, 540.0);
+        assert!(camera.validated().unwrap().principal_point_in_image());
+
+        for (fx, fy) in [(0.0, 1000.0), (1000.0, -5.0), (f64::NAN, 1000.0)] {
+            let result = PinholeCamera::new_ideal(1920, 1080, fx, fy, 960.0, 540.0).validated();
+            assert!(matches!(result, Err(RspError::InvalidInput(_))), "{} {}", fx, fy);
+        }
+        let distorted = PinholeCamera::new_brown_conrady(
+            1920, 1080, 0.0, 1000.0, 960.0, 540.0, -0.1, 0.0, 0.0, 0.0, 0.0,
+        );
+        assert!(matches!(distorted.validated(), Err(RspError::InvalidInput(_))));
+        let k = Matrix3::new(1000.0, 0.0, f64::INFINITY, 0.0, 1000.0, 540.0, 0.0, 0.0, 1.0);
+        let posed = PinholeCamera::from_k_and_pose(1920, 1080, &k, &Isometry3::identity());
+        assert!(matches!(posed.validated(), Err(RspError::InvalidInput(_))));
+
+        // An off-image principal point is valid but flagged
+        let shifted = PinholeCamera::new_ideal(1920, 1080, 1000.0, 1000.0, -40.0, 540.0);
+        assert!(!shifted.validated().unwrap().principal_point_in_image());
+        let below = PinholeCamera::new_ideal(1920, 1080, 1000.0, 1000.0, 960.0, 1200.0);
+        assert!(!below.validated().unwrap().principal_point_in_image());
+    }
+
+    #[test]
+    fn test_backproject_ray_distorted() {
+        let camera = PinholeCamera::new_brown_conrady(
+            1920, 1080, 1000.0, 1010.0, 955.0, 545.0, -0.2, 0.05, 0.0, 0.001, -0.0005,
+        );
+        let rotation = Rotation3::from_euler_angles(0.1, -0.3, 0.7);
+        let pose = CameraPose::new(rotation, Vector3::new(2.0, -1.0, 5.0));
+
+        // The principal point looks straight down the optical axis
+        let (origin, direction) = camera.backproject_ray(&pose, (955.0, 545.0)).unwrap();
+        assert!((origin - pose.center()).norm() < 1e-12);
+        assert!((direction - rotation.inverse() * Vector3::z()).norm() < 1e-12);
+
+        // A point along an off-axis ray reprojects to its pixel through the distortion
+        let pixel = (1700.0, 150.0);
+        let (origin, direction) = camera.backproject_ray(&pose, pixel).unwrap();
+        assert!((direction.norm() - 1.0).abs() < 1e-12);
+        let point = pose.world_to_camera(&(origin + 7.5 * direction));
+        let (u, v) = camera.project(&point).unwrap();
+        assert!((u - pixel.0).abs() < 1e-6 && (v - pixel.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_pinhole_from_k_and_pose() {
+        use nalgebra::{Translation3, UnitQuaternion};
+
+        let k = Matrix3::new(800.0, 0.0, 320.0, 0.0, 810.0, 240.0, 0.0, 0.0, 1.0);
+        let rotation = UnitQuaternion::from_euler_angles(0.1, -0.3, 0.2);
+        let center = Vector3::new(2.0, -1.0, 5.0);
+        let pose = Isometry3::from_parts(Translation3::from(-(rotation * center)), rotation);
+
+        let camera = PinholeCamera::from_k_and_pose(640, 480, &k, &pose);
+        assert_eq!(camera.focal_length(), (800.0, 810.0));
+        assert_eq!(camera.principal_point(), (320.0, 240.0));
+        assert!((camera.camera_center() - center).norm() < 1e-12);
+
+        // The center sits on the camera plane, so it has no projection
+        assert!(camera.project_world(&camera.camera_center()).is_none());
+
+        // A point on the optical axis lands on the principal point
+        let ahead = center + rotation.inverse() * Vector3::new(0.0, 0.0, 3.0);
+        let (u, v) = camera.project_world(&ahead).unwrap();
+        assert!((u - 320.0).abs() < 1e-9 && (v - 240.0).abs() < 1e-9);
+
+        // Same intrinsics at the origin: the same calibration, a different pose
+        let origin = PinholeCamera::new_ideal(640, 480, 800.0, 810.0, 320.0, 240.0);
+        assert!(camera.approx_eq(&origin, 1e-9));
+        assert!(!camera.approx_eq_with_pose(&origin, 1e-9, 1e-9, 1e-9));
+        let unposed = PinholeCamera::from_k_and_pose(640, 480, &k, &Isometry3::identity());
+        assert!(origin.approx_eq_with_pose(&unposed, 1e-9, 1e-9, 1e-9));
+    }
+
+    #[test]
+    fn test_undistort_image_samples_distorted_position() {
+        use crate::warp::{BilinearResampler, NearestResampler};
+
+        // Linear ramp, so bilinear sampling is exact
+        let image = Array2::from_shape_fn((60, 80), |(r, c)| (2.0 * r as f64 + c as f64) as f32);
+
+        let ideal = PinholeCamera::new_ideal(80, 60, 100.0, 100.0, 40.0, 30.0);
+        assert_eq!(ideal.undistort_image(&image, &NearestResampler), image);
+
+        let camera = PinholeCamera::new_brown_conrady(
+            80, 60, 100.0, 100.0, 40.0, 30.0, 0.1, 0.0, 0.0, 0.0, 0.0,
+        );
+        let out = camera.undistort_image(&image, &BilinearResampler);
+        for ((row, col), &value) in out.indexed_iter() {
+            let ray = Vector3::new((col as f64 - 40.0) / 100.0, (row as f64 - 30.0) / 100.0, 1.0);
+            let (u, v) = camera.project(&ray).unwrap();
+            if (0.0..=79.0).contains(&u) && (0.0..=59.0).contains(&v) {
+                assert!((value as f64 - (2.0 * v + u)).abs() < 1e-3);
+            } else {
+                assert!(value.is_nan());
+            }
+        }
+        // Positive k1 pushes the corners' sources outside the frame
+        assert!(out[[0, 0]].is_nan());
+    }
+
+    #[test]
+    fn test_check_distortion_invertible() {
+        let camera = PinholeCamera::new_brown_conrady(
+            1920, 1080, 1000.0, 1000.0, 960.0, 540.0, -0.2, 0.05, 0.0, 0.001, -0.0005,
+        );
+        let error = camera.check_distortion_invertible(15).unwrap();
+        assert!(error < 1e-6, "round-trip error {}", error);
+
+        let ideal = PinholeCamera::new_ideal(640, 480, 500.0, 500.0, 320.0, 240.0);
+        assert_eq!(ideal.check_distortion_invertible(5).unwrap(), 0.0);
+        assert!(matches!(ideal.check_distortion_invertible(1), Err(RspError::InvalidInput(_))));
+
+        // Strong barrel distortion folds over inside this wide field of view
+        let pathological = PinholeCamera::new_brown_conrady(
+            1920, 1080, 500.0, 500.0, 960.0, 540.0, -1.0, 0.0, 0.0, 0.0, 0.0,
+        );
+        let result = pathological.check_distortion_invertible(15);
+        assert!(matches!(result, Err(RspError::Distortion(_))));
+    }
 }
@@ -0,0 +1,118 @@
+use nalgebra::{Isometry3, Rotation3, Vector3};
+
+use super::CameraModel;
+use crate::error::Result;
+
+/// Rigid camera pose
+///
+/// Maps world points into the camera frame as `x_cam = rotation * x_world + translation`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraPose {
+    pub rotation: Rotation3<f64>,
+    pub translation: Vector3<f64>,
+}
+
+impl CameraPose {
+    /// Create a pose from a world-to-camera rotation and translation
+    pub fn new(rotation: Rotation3<f64>, translation: Vector3<f64>) -> Self {
+        Self {
+            rotation,
+            translation,
+        }
+    }
+
+    /// Pose whose camera frame is the world frame
+    pub fn identity() -> Self {
+        Self::new(Rotation3::identity(), Vector3::zeros())
+    }
+
+    /// Pose from a world-to-camera rigid transform
+    pub fn from_isometry(isometry: &Isometry3<f64>) -> Self {
+        Self::new(isometry.rotation.to_rotation_matrix(), isometry.translation.vector)
+    }
+
+    /// Camera center in world coordinates (`-R^T t`)
+    pub fn center(&self) -> Vector3<f64> {
+        -(self.rotation.inverse() * self.translation)
+    }
+
+    /// Transform a world point into the camera frame
+    pub fn world_to_camera(&self, point_world: &Vector3<f64>) -> Vector3<f64> {
+        self.rotation * point_world + self.translation
+    }
+
+    /// Transform a camera-frame point into world coordinates
+    pub fn camera_to_world(&self, point_camera: &Vector3<f64>) -> Vector3<f64> {
+        self.rotation.inverse() * (point_camera - self.translation)
+    }
+
+    /// Whether two poses agree to `rotation_eps` radians and `translation_eps`
+    pub fn approx_eq(&self, other: &Self, rotation_eps: f64, translation_eps: f64) -> bool {
+        (self.rotation.inverse() * other.rotation).angle() <= rotation_eps
+            && (self.translation - other.translation).norm() <= translation_eps
+    }
+}
+
+/// World-space viewing ray through a pixel
+///
+/// Returns `(origin, direction)` where the origin is the camera center and the
+/// direction is the unit unprojected ray rotated into the world frame.
+pub fn pixel_ray<C: CameraModel + ?Sized>(
+    camera: &C,
+    pose: &CameraPose,
+    pixel: (f64, f64),
+) -> Result<(Vector3<f64>, Vector3<f64>)> {
+    let ray_camera = camera.unproject(pixel)?;
+    let direction = pose.rotation.inverse() * ray_camera;
+
+    Ok((pose.center(), direction))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::PinholeCamera;
+
+    #[test]
+    fn test_pose_center_roundtrip() {
+        let rotation = Rotation3::from_euler_angles(0.1, -0.2, 0.3);
+        let pose = CameraPose::new(rotation, Vector3::new(1.0, -2.0, 0.5));
+
+        // The camera center maps to the camera-frame origin
+        let origin = pose.world_to_camera(&pose.center());
+        assert!(origin.norm() < 1e-12);
+
+        let p = Vector3::new(3.0, 4.0, 5.0);
+        let back = pose.camera_to_world(&pose.world_to_camera(&p));
+        assert!((back - p).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_pose_approx_eq_separate_tolerances() {
+        let pose = CameraPose::new(Rotation3::from_euler_angles(0.1, -0.2, 0.3), Vector3::zeros());
+        let turned = CameraPose::new(
+            Rotation3::from_euler_angles(0.1 + 1e-4, -0.2, 0.3),
+            Vector3::new(0.5, 0.0, 0.0),
+        );
+
+        assert!(pose.approx_eq(&pose, 0.0, 0.0));
+        assert!(pose.approx_eq(&turned, 1e-3, 1.0));
+        assert!(!pose.approx_eq(&turned, 1e-5, 1.0));
+        assert!(!pose.approx_eq(&turned, 1e-3, 0.1));
+    }
+
+    #[test]
+    fn test_principal_point_ray_along_optical_axis() {
+        let camera = PinholeCamera::new_ideal(640, 480, 500.0, 500.0, 320.0, 240.0);
+        let rotation = Rotation3::from_euler_angles(0.0, 0.4, 0.1);
+        let center = Vector3::new(10.0, 20.0, 30.0);
+        let pose = CameraPose::new(rotation, -(rotation * center));
+
+        let (origin, direction) = pixel_ray(&camera, &pose, (320.0, 240.0)).unwrap();
+
+        // Optical axis is the camera z-axis expressed in world coordinates
+        let optical_axis = rotation.inverse() * Vector3::z();
+        assert!((origin - center).norm() < 1e-9);
+        assert!((direction - optical_axis).norm() < 1e-9);
+    }
+}
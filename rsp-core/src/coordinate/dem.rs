@@ -0,0 +1,137 @@
+use ndarray::Array2;
+
+use crate::error::{Result, RspError};
+
+/// Gridded digital elevation model
+///
+/// Heights are ellipsoidal meters on a north-up geographic grid described by
+/// a GDAL-style geotransform (x is longitude, y is latitude, degrees). Each
+/// value is taken to lie at its pixel center. NaN marks missing data.
+#[derive(Debug, Clone)]
+pub struct GridDem {
+    geotransform: [f64; 6],
+    heights: Array2<f32>,
+}
+
+impl GridDem {
+    /// Create a DEM from a geotransform and a [rows, cols] height array
+    pub fn new(geotransform: [f64; 6], heights: Array2<f32>) -> Result<Self> {
+        if heights.is_empty() {
+            return Err(RspError::InvalidInput("DEM has zero size".to_string()));
+        }
+        let gt = &geotransform;
+        if gt[1] == 0.0 || gt[5] == 0.0 || gt[2] != 0.0 || gt[4] != 0.0 {
+            return Err(RspError::InvalidInput(
+                "DEM geotransform must be north-up with non-zero resolution".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            geotransform,
+            heights,
+        })
+    }
+
+    /// Get the geotransform
+    pub fn geotransform(&self) -> &[f64; 6] {
+        &self.geotransform
+    }
+
+    /// Get reference to the height array
+    pub fn heights(&self) -> &Array2<f32> {
+        &self.heights
+    }
+
+    /// Bilinearly interpolated height at a latitude/longitude in degrees
+    ///
+    /// Returns `None` outside the span of pixel centers or if any of the four
+    /// neighboring samples is missing.
+    pub fn height_at(&self, lat: f64, lon: f64) -> Option<f64> {
+        let gt = &self.geotransform;
+        let (rows, cols) = self.heights.dim();
+
+        let col = (lon - gt[0]) / gt[1] - 0.5;
+        let row = (lat - gt[3]) / gt[5] - 0.5;
+        if !(col >= 0.0 && row >= 0.0 && col <= (cols - 1) as f64 && row <= (rows - 1) as f64) {
+            return None;
+        }
+
+        let r0 = row.floor() as usize;
+        let c0 = col.floor() as usize;
+        let r1 = (r0 + 1).min(rows - 1);
+        let c1 = (c0 + 1).min(cols - 1);
+        let fr = row - r0 as f64;
+        let fc = col - c0 as f64;
+
+        let at = |r: usize, c: usize| self.heights[[r, c]] as f64;
+        let top = at(r0, c0) * (1.0 - fc) + at(r0, c1) * fc;
+        let bottom = at(r1, c0) * (1.0 - fc) + at(r1, c1) * fc;
+        let height = top * (1.0 - fr) + bottom * fr;
+
+        height.is_finite().then_some(height)
+    }
+}
+
+/// Height of the test plane rising to the north-east from 250 m at 39 N, 77 W
+#[cfg(test)]
+pub(crate) fn tilted_plane(lat: f64, lon: f64) -> f64 {
+    250.0 + 3000.0 * (lat - 39.0) + 2000.0 * (lon + 77.0)
+}
+
+/// 100x100 DEM of [`tilted_plane`] at 0.001 degree pixel centers around 39 N, 77 W
+#[cfg(test)]
+pub(crate) fn tilted_plane_dem() -> GridDem {
+    let gt = [-77.05, 0.001, 0.0, 39.05, 0.0, -0.001];
+    let heights = Array2::from_shape_fn((100, 100), |(r, c)| {
+        let lon = gt[0] + (c as f64 + 0.5) * gt[1];
+        let lat = gt[3] + (r as f64 + 0.5) * gt[5];
+        tilted_plane(lat, lon) as f32
+    });
+    GridDem::new(gt, heights).expect("tilted plane DEM is valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_height_at_bilinear() {
+        // 2x2 DEM with 1 degree pixels; centers at lat 0.5/-0.5, lon 0.5/1.5
+        let heights = Array2::from_shape_vec((2, 2), vec![0.0, 10.0, 20.0, 30.0]).unwrap();
+        let dem = GridDem::new([0.0, 1.0, 0.0, 1.0, 0.0, -1.0], heights).unwrap();
+
+        assert_eq!(dem.height_at(0.5, 0.5), Some(0.0));
+        assert_eq!(dem.height_at(-0.5, 1.5), Some(30.0));
+        assert!((dem.height_at(0.0, 1.0).unwrap() - 15.0).abs() < 1e-12);
+
+        // Outside the pixel-center span
+        assert_eq!(dem.height_at(0.9, 0.5), None);
+        assert_eq!(dem.height_at(0.0, 1.6), None);
+    }
+
+    #[test]
+    fn test_height_at_nodata() {
+        let heights = Array2::from_shape_vec((2, 2), vec![0.0, f32::NAN, 20.0, 30.0]).unwrap();
+        let dem = GridDem::new([0.0, 1.0, 0.0, 1.0, 0.0, -1.0], heights).unwrap();
+
+        assert_eq!(dem.height_at(0.0, 1.0), None);
+        assert_eq!(dem.height_at(-0.5, 0.5), Some(20.0));
+    }
+
+    #[test]
+    fn test_height_at_tilted_plane() {
+        // Bilinear interpolation reproduces a plane up to f32 rounding
+        let dem = tilted_plane_dem();
+        for (lat, lon) in [(39.0, -77.0), (39.0314, -77.0421), (38.9577, -76.9602)] {
+            let height = dem.height_at(lat, lon).unwrap();
+            assert!((height - tilted_plane(lat, lon)).abs() < 1e-3, "{} {}", lat, lon);
+        }
+    }
+
+    #[test]
+    fn test_rotated_dem_rejected() {
+        let heights = Array2::zeros((2, 2));
+        let result = GridDem::new([0.0, 1.0, 0.1, 1.0, 0.0, -1.0], heights);
+        assert!(matches!(result, Err(RspError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,124 @@
+use super::LlaCoord;
+
+/// Intersection-over-union of two ground footprints
+///
+/// Each footprint is a convex quadrilateral of corners in either winding
+/// order (altitudes are ignored). Both are projected onto a local
+/// equirectangular plane centered on their mean latitude, which is accurate
+/// for scene-sized areas away from the poles and the antimeridian. Returns
+/// a value in [0, 1]; disjoint or degenerate footprints give 0.
+pub fn footprint_overlap(a: &[LlaCoord; 4], b: &[LlaCoord; 4]) -> f64 {
+    let lat0 = a.iter().chain(b).map(|c| c.lat).sum::<f64>() / 8.0;
+    let lon0 = a.iter().chain(b).map(|c| c.lon).sum::<f64>() / 8.0;
+    let cos_lat = lat0.to_radians().cos();
+
+    let planar = |corners: &[LlaCoord; 4]| -> Vec<(f64, f64)> {
+        let mut points: Vec<_> = corners
+            .iter()
+            .map(|c| ((c.lon - lon0) * cos_lat, c.lat - lat0))
+            .collect();
+        if signed_area(&points) < 0.0 {
+            points.reverse();
+        }
+        points
+    };
+
+    let (pa, pb) = (planar(a), planar(b));
+    let area_a = signed_area(&pa);
+    let area_b = signed_area(&pb);
+    let inter = signed_area(&clip_convex(&pa, &pb));
+
+    let union = area_a + area_b - inter;
+    if union <= 0.0 {
+        return 0.0;
+    }
+    (inter / union).clamp(0.0, 1.0)
+}
+
+/// Shoelace area, positive for counter-clockwise polygons
+fn signed_area(points: &[(f64, f64)]) -> f64 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            let (x0, y0) = points[i];
+            let (x1, y1) = points[(i + 1) % n];
+            x0 * y1 - x1 * y0
+        })
+        .sum::<f64>()
+        * 0.5
+}
+
+/// Sutherland-Hodgman clip of a polygon by a convex counter-clockwise one
+fn clip_convex(subject: &[(f64, f64)], clip: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut output = subject.to_vec();
+
+    for i in 0..clip.len() {
+        if output.is_empty() {
+            break;
+        }
+        let (ax, ay) = clip[i];
+        let (bx, by) = clip[(i + 1) % clip.len()];
+        // Positive on the inner (left) side of edge a -> b
+        let side = |(x, y): (f64, f64)| (bx - ax) * (y - ay) - (by - ay) * (x - ax);
+
+        let input = std::mem::take(&mut output);
+        for j in 0..input.len() {
+            let current = input[j];
+            let previous = input[(j + input.len() - 1) % input.len()];
+            let (s_cur, s_prev) = (side(current), side(previous));
+
+            if s_cur >= 0.0 {
+                if s_prev < 0.0 {
+                    output.push(intersect(previous, current, s_prev, s_cur));
+                }
+                output.push(current);
+            } else if s_prev >= 0.0 {
+                output.push(intersect(previous, current, s_prev, s_cur));
+            }
+        }
+    }
+
+    output
+}
+
+/// Point where segment p -> q crosses the clip edge, from its side values
+fn intersect(p: (f64, f64), q: (f64, f64), s_p: f64, s_q: f64) -> (f64, f64) {
+    let t = s_p / (s_p - s_q);
+    (p.0 + t * (q.0 - p.0), p.1 + t * (q.1 - p.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(lat: f64, lon: f64, size: f64) -> [LlaCoord; 4] {
+        let at = |dlat: f64, dlon: f64| LlaCoord { lat: lat + dlat, lon: lon + dlon, alt: 0.0 };
+        [at(size, 0.0), at(size, size), at(0.0, size), at(0.0, 0.0)]
+    }
+
+    #[test]
+    fn test_footprint_overlap_identical_and_disjoint() {
+        let a = square(39.0, -77.0, 0.1);
+        assert!((footprint_overlap(&a, &a) - 1.0).abs() < 1e-12);
+
+        let b = square(39.5, -76.5, 0.1);
+        assert_eq!(footprint_overlap(&a, &b), 0.0);
+    }
+
+    #[test]
+    fn test_footprint_overlap_partial() {
+        // Shifted by half a side: intersection 1/2, union 3/2
+        let a = square(39.0, -77.0, 0.1);
+        let b = square(39.0, -76.95, 0.1);
+        assert!((footprint_overlap(&a, &b) - 1.0 / 3.0).abs() < 1e-3);
+
+        // Winding order doesn't matter
+        let mut reversed = b;
+        reversed.reverse();
+        assert!((footprint_overlap(&a, &reversed) - footprint_overlap(&a, &b)).abs() < 1e-12);
+
+        // Contained square: IoU is the area ratio
+        let inner = square(39.025, -76.975, 0.05);
+        assert!((footprint_overlap(&a, &inner) - 0.25).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::error::{Result, RspError};
+
+/// Geoid undulation model (e.g. a resampled EGM2008 grid)
+///
+/// Undulations `N` relate heights as `h_ellipsoid = H_orthometric + N`.
+/// Values are bilinearly interpolated on a regular lat/lon grid and clamped
+/// at the grid edges.
+#[derive(Debug, Clone)]
+pub struct GeoidModel {
+    lat_min: f64,
+    lon_min: f64,
+    lat_step: f64,
+    lon_step: f64,
+    rows: usize,
+    cols: usize,
+    /// Row-major undulations in meters, row 0 at `lat_min`
+    undulations: Vec<f64>,
+}
+
+impl GeoidModel {
+    /// Create a geoid with the same undulation everywhere
+    pub fn constant(undulation: f64) -> Self {
+        Self {
+            lat_min: -90.0,
+            lon_min: -180.0,
+            lat_step: 180.0,
+            lon_step: 360.0,
+            rows: 1,
+            cols: 1,
+            undulations: vec![undulation],
+        }
+    }
+
+    /// Create a geoid from a regular grid of undulations (meters)
+    ///
+    /// `values` is row-major with `rows * cols` entries; row 0 lies at
+    /// `lat_min` and column 0 at `lon_min`.
+    pub fn from_grid(
+        lat_min: f64,
+        lon_min: f64,
+        lat_step: f64,
+        lon_step: f64,
+        rows: usize,
+        cols: usize,
+        values: Vec<f64>,
+    ) -> Result<Self> {
+        if rows == 0 || cols == 0 || values.len() != rows * cols {
+            return Err(RspError::InvalidInput(format!(
+                "Geoid grid expects {} x {} values, got {}",
+                rows,
+                cols,
+                values.len()
+            )));
+        }
+        if lat_step <= 0.0 || lon_step <= 0.0 {
+            return Err(RspError::InvalidInput(
+                "Geoid grid steps must be positive".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            lat_min,
+            lon_min,
+            lat_step,
+            lon_step,
+            rows,
+            cols,
+            undulations: values,
+        })
+    }
+
+    /// Geoid undulation (meters) at a latitude/longitude in degrees
+    pub fn undulation(&self, lat: f64, lon: f64) -> f64 {
+        let row = ((lat - self.lat_min) / self.lat_step).clamp(0.0, (self.rows - 1) as f64);
+        let col = ((lon - self.lon_min) / self.lon_step).clamp(0.0, (self.cols - 1) as f64);
+
+        let r0 = row.floor() as usize;
+        let c0 = col.floor() as usize;
+        let r1 = (r0 + 1).min(self.rows - 1);
+        let c1 = (c0 + 1).min(self.cols - 1);
+        let fr = row - r0 as f64;
+        let fc = col - c0 as f64;
+
+        let at = |r: usize, c: usize| self.undulations[r * self.cols + c];
+        let top = at(r0, c0) * (1.0 - fc) + at(r0, c1) * fc;
+        let bottom = at(r1, c0) * (1.0 - fc) + at(r1, c1) * fc;
+
+        top * (1.0 - fr) + bottom * fr
+    }
+
+    /// Convert an orthometric height to an ellipsoidal height
+    pub fn orthometric_to_ellipsoidal(&self, lat: f64, lon: f64, height: f64) -> f64 {
+        height + self.undulation(lat, lon)
+    }
+
+    /// Convert an ellipsoidal height to an orthometric height
+    pub fn ellipsoidal_to_orthometric(&self, lat: f64, lon: f64, height: f64) -> f64 {
+        height - self.undulation(lat, lon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_geoid() {
+        let geoid = GeoidModel::constant(-33.5);
+        assert_eq!(geoid.undulation(39.0, -77.0), -33.5);
+        assert_eq!(geoid.orthometric_to_ellipsoidal(39.0, -77.0, 100.0), 66.5);
+        assert_eq!(geoid.ellipsoidal_to_orthometric(39.0, -77.0, 66.5), 100.0);
+    }
+
+    #[test]
+    fn test_grid_bilinear() {
+        // 2x2 grid: undulation = 10 * lat_index + lon_index
+        let geoid = GeoidModel::from_grid(0.0, 0.0, 1.0, 1.0, 2, 2, vec![0.0, 1.0, 10.0, 11.0])
+            .unwrap();
+
+        assert!((geoid.undulation(0.0, 0.0) - 0.0).abs() < 1e-12);
+        assert!((geoid.undulation(1.0, 1.0) - 11.0).abs() < 1e-12);
+        assert!((geoid.undulation(0.5, 0.5) - 5.5).abs() < 1e-12);
+
+        // Clamped outside the grid
+        assert!((geoid.undulation(5.0, -5.0) - 10.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_grid_size_mismatch() {
+        let result = GeoidModel::from_grid(0.0, 0.0, 1.0, 1.0, 2, 2, vec![0.0; 3]);
+        assert!(matches!(result, Err(RspError::InvalidInput(_))));
+    }
+}
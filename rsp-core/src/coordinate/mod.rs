@@ -1,8 +1,17 @@
 //! Coordiante system transformations
 
+mod dem;
+mod footprint;
+mod geoid;
 mod transforms;
 
+pub use dem::GridDem;
+#[cfg(test)]
+pub(crate) use dem::{tilted_plane, tilted_plane_dem};
+pub use footprint::footprint_overlap;
+pub use geoid::GeoidModel;
 pub use transforms::{
-    ecef_to_lla, lla_to_ecef,
+    ecef_to_lla, ecef_to_lla_iter, lla_to_ecef, radii_of_curvature,
     EcefCoord, LlaCoord,
 };
+
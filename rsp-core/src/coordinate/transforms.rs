@@ -17,8 +17,20 @@ pub struct LlaCoord {
 const WGS84_A: f64 = 6378137.0;              // semi-major axis (meters)
 const WGS84_E2: f64 = 0.00669437999014;      // first eccentricity squared
 
+// Default latitude tolerance for ecef_to_lla (radians, ~0.006 mm on the ground)
+const ECEF_TO_LLA_TOLERANCE: f64 = 1e-12;
+
 /// Convert ECEF to LLA (Latitude, Longitude, Altitude)
 pub fn ecef_to_lla(ecef: &EcefCoord) -> Result<LlaCoord> {
+    ecef_to_lla_iter(ecef, 10, ECEF_TO_LLA_TOLERANCE)
+}
+
+/// Convert ECEF to LLA with an explicit iteration budget
+///
+/// Runs at most `max_iters` fixed-point iterations, stopping early once the
+/// latitude changes by less than `tol` radians. A few iterations are enough
+/// near the Earth's surface.
+pub fn ecef_to_lla_iter(ecef: &EcefCoord, max_iters: usize, tol: f64) -> Result<LlaCoord> {
     let x = ecef.x;
     let y = ecef.y;
     let z = ecef.z;
@@ -28,16 +40,22 @@ pub fn ecef_to_lla(ecef: &EcefCoord) -> Result<LlaCoord> {
     // Longitude
     let lon = y.atan2(x).to_degrees();
     
-    // Iterative solution for latitude and altitude
-    let mut lat = (z / p).atan();
+    // Iterative solution for latitude and altitude, starting from the
+    // geodetic latitude of a point on the ellipsoid surface
+    let mut lat = (z / (p * (1.0 - WGS84_E2))).atan();
     let mut alt = 0.0;
-    let mut n;
 
-    for _ in 0..10 {
+    for _ in 0..max_iters {
         let sin_lat = lat.sin();
-        n = WGS84_A / (1.0 - WGS84_E2 * sin_lat * sin_lat).sqrt();
+        let n = WGS84_A / (1.0 - WGS84_E2 * sin_lat * sin_lat).sqrt();
         alt = p / lat.cos() - n;
-        lat = (z / p / (1.0 - WGS84_E2 * n / (n + alt))).atan();
+        let next = (z / p / (1.0 - WGS84_E2 * n / (n + alt))).atan();
+        
+        let change = (next - lat).abs();
+        lat = next;
+        if change < tol {
+            break;
+        }
     }
     
     let lat_deg = lat.to_degrees();
@@ -76,6 +94,20 @@ pub fn lla_to_ecef(lla: &LlaCoord) -> Result<EcefCoord> {
     Ok(Vector3::new(x, y, z))
 }
 
+/// WGS84 radii of curvature `(M, N)` in meters at a geodetic latitude
+///
+/// `M` is the meridian radius (north-south) and `N` the prime-vertical
+/// radius (east-west), so a small step of `dlat` radians north covers
+/// `M * dlat` meters and `dlon` radians east covers `N * cos(lat) * dlon`.
+pub fn radii_of_curvature(lat_deg: f64) -> (f64, f64) {
+    let sin_lat = lat_deg.to_radians().sin();
+    let w2 = 1.0 - WGS84_E2 * sin_lat * sin_lat;
+    
+    let n = WGS84_A / w2.sqrt();
+    let m = n * (1.0 - WGS84_E2) / w2;
+    (m, n)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,6 +129,42 @@ mod tests {
         assert!((lla.alt - lla2.alt).abs() < 1e-3);
     }
 
+    #[test]
+    fn test_ecef_to_lla_iter_surface_converges_fast() {
+        let lla = LlaCoord {
+            lat: 38.8977,
+            lon: -77.0365,
+            alt: 25.0,
+        };
+        let ecef = lla_to_ecef(&lla).unwrap();
+
+        let fast = ecef_to_lla_iter(&ecef, 3, 1e-12).unwrap();
+        let full = ecef_to_lla(&ecef).unwrap();
+
+        assert!((fast.lat - lla.lat).abs() < 1e-9);
+        assert!((fast.alt - lla.alt).abs() < 1e-4);
+        assert!((fast.lat - full.lat).abs() < 1e-12);
+        assert!((fast.alt - full.alt).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ecef_to_lla_iter_budget_limits_accuracy() {
+        // High-altitude point needs more than one iteration
+        let lla = LlaCoord {
+            lat: 60.0,
+            lon: 10.0,
+            alt: 500_000.0,
+        };
+        let ecef = lla_to_ecef(&lla).unwrap();
+
+        let one = ecef_to_lla_iter(&ecef, 1, 0.0).unwrap();
+        let many = ecef_to_lla_iter(&ecef, 50, 0.0).unwrap();
+
+        assert!((one.lat - lla.lat).abs() > 1e-6);
+        assert!((many.lat - lla.lat).abs() < 1e-9);
+        assert!((many.alt - lla.alt).abs() < 1e-4);
+    }
+
     #[test]
     fn test_equator_prime_meridian() {
         // Test point on equator at prime meridian
@@ -276,4 +344,29 @@ mod tests {
         assert!((lla.lon - lla2.lon).abs() < 1e-6);
         assert!((lla.alt - lla2.alt).abs() < 1e-3);
     }
+
+    #[test]
+    fn test_radii_of_curvature() {
+        // Equator: N is the semi-major axis, M = a (1 - e^2)
+        let (m, n) = radii_of_curvature(0.0);
+        assert!((n - 6_378_137.0).abs() < 1e-6);
+        assert!((m - 6_335_439.327).abs() < 1e-3);
+        assert!(m < n);
+
+        // Poles: both equal a / sqrt(1 - e^2)
+        let (m, n) = radii_of_curvature(-90.0);
+        assert!((m - 6_399_593.626).abs() < 1e-3);
+        assert!((n - 6_399_593.626).abs() < 1e-3);
+
+        // Mid-latitude: M and N scale small lat/lon steps
+        let lla = LlaCoord { lat: 45.0, lon: 10.0, alt: 0.0 };
+        let (m, n) = radii_of_curvature(lla.lat);
+        let step = 1e-4_f64;
+        let origin = lla_to_ecef(&lla).unwrap();
+        let north = lla_to_ecef(&LlaCoord { lat: lla.lat + step, ..lla }).unwrap();
+        let east = lla_to_ecef(&LlaCoord { lon: lla.lon + step, ..lla }).unwrap();
+        let expected_east = n * 45f64.to_radians().cos() * step.to_radians();
+        assert!(((north - origin).norm() - m * step.to_radians()).abs() < 1e-4);
+        assert!(((east - origin).norm() - expected_east).abs() < 1e-4);
+    }
 }
@@ -9,6 +9,9 @@ pub enum RspError {
     #[error("Coordinate transform error: {0}")]
     CoordinateTransform(#[from] CoordinateError),
 
+    #[error("Distortion error: {0}")]
+    Distortion(#[from] DistortionError),
+
     #[error("I/O error: {0}")]
     Io(String),
 
@@ -24,8 +27,8 @@ pub enum ProjectionError {
     #[error("Point behind camera")]
     BehindCamera,
 
-    #[error("Point outside image bounds")]
-    OutOfBounds,
+    #[error("Point outside image bounds at line {line}, sample {sample}")]
+    OutOfBounds { line: f64, sample: f64 },
 
     #[error("Invalid RPC coefficients")]
     InvalidRpc,
@@ -34,6 +37,24 @@ pub enum ProjectionError {
     NoConvergence(usize),
 }
 
+#[derive(Error, Debug)]
+pub enum DistortionError {
+    #[error("Singular or non-finite Jacobian while removing distortion")]
+    SingularJacobian,
+
+    #[error("Undistortion did not converge after {0} iterations")]
+    NonConvergent(usize),
+}
+
+#[derive(Error, Debug)]
+pub enum NumericalError {
+    #[error("Singular linear system")]
+    Singular,
+
+    #[error("Non-finite value in linear system")]
+    NonFinite,
+}
+
 #[derive(Error, Debug)]
 pub enum CoordinateError {
     #[error("Invalid latitude: {0} (must be -90 to 90)")]
@@ -60,8 +81,8 @@ mod tests {
         let err = ProjectionError::BehindCamera;
         assert_eq!(err.to_string(), "Point behind camera");
 
-        let err = ProjectionError::OutOfBounds;
-        assert_eq!(err.to_string(), "Point outside image bounds");
+        let err = ProjectionError::OutOfBounds { line: -3.5, sample: 1024.25 };
+        assert_eq!(err.to_string(), "Point outside image bounds at line -3.5, sample 1024.25");
 
         let err = ProjectionError::InvalidRpc;
         assert_eq!(err.to_string(), "Invalid RPC coefficients");
@@ -82,6 +103,24 @@ mod tests {
         assert_eq!(err.to_string(), "Invalid height: -1000000");
     }
 
+    #[test]
+    fn test_distortion_error_display() {
+        let err = DistortionError::SingularJacobian;
+        assert_eq!(err.to_string(), "Singular or non-finite Jacobian while removing distortion");
+
+        let err = DistortionError::NonConvergent(10);
+        assert_eq!(err.to_string(), "Undistortion did not converge after 10 iterations");
+
+        let rsp_err: RspError = DistortionError::SingularJacobian.into();
+        assert!(matches!(rsp_err, RspError::Distortion(_)));
+    }
+
+    #[test]
+    fn test_numerical_error_display() {
+        assert_eq!(NumericalError::Singular.to_string(), "Singular linear system");
+        assert_eq!(NumericalError::NonFinite.to_string(), "Non-finite value in linear system");
+    }
+
     #[test]
     fn test_rsp_error_from_projection_error() {
         let proj_err = ProjectionError::BehindCamera;
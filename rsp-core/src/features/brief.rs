@@ -0,0 +1,180 @@
+use ndarray::Array2;
+
+/// 256-bit BRIEF descriptor
+pub type BriefDescriptor = [u64; 4];
+
+/// Intensity comparisons per descriptor
+const BRIEF_BITS: usize = 256;
+
+/// Maximum ratio of best to second-best Hamming distance for a match
+const MATCH_RATIO: f32 = 0.8;
+
+/// Compute BRIEF binary descriptors at keypoints
+///
+/// Keypoints are `(x, y)` pixel positions (rounded to the nearest pixel).
+/// Each bit compares the 3x3 mean intensity at a pair of offsets drawn from
+/// a fixed pseudo-random pattern within a `patch` x `patch` window, so
+/// descriptors from different images are comparable. Samples falling
+/// outside the image are clamped to the border, which keeps one descriptor
+/// per keypoint.
+pub fn compute_brief(
+    image: &Array2<u8>,
+    keypoints: &[(f64, f64)],
+    patch: usize,
+) -> Vec<BriefDescriptor> {
+    let pattern = sampling_pattern(patch);
+    let (rows, cols) = image.dim();
+    if rows == 0 || cols == 0 {
+        return vec![[0; 4]; keypoints.len()];
+    }
+
+    // Sum of the 3x3 neighbourhood with clamped coordinates
+    let smoothed = |x: i64, y: i64| -> u32 {
+        let mut sum = 0;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let r = (y + dy).clamp(0, rows as i64 - 1) as usize;
+                let c = (x + dx).clamp(0, cols as i64 - 1) as usize;
+                sum += image[[r, c]] as u32;
+            }
+        }
+        sum
+    };
+
+    keypoints
+        .iter()
+        .map(|&(x, y)| {
+            let (cx, cy) = (x.round() as i64, y.round() as i64);
+            let mut descriptor = [0u64; 4];
+            for (bit, &(a, b)) in pattern.iter().enumerate() {
+                if smoothed(cx + a.0, cy + a.1) < smoothed(cx + b.0, cy + b.1) {
+                    descriptor[bit / 64] |= 1 << (bit % 64);
+                }
+            }
+            descriptor
+        })
+        .collect()
+}
+
+/// Brute-force Hamming matching of two descriptor sets with a ratio test
+///
+/// For each descriptor in `a` the nearest and second-nearest descriptors in
+/// `b` are found. A match `(index_a, index_b, distance)` is kept when the
+/// nearest distance is at most `max_hamming` and clearly smaller than the
+/// second-nearest (ratio below 0.8). Output follows the order of `a`.
+pub fn match_descriptors(
+    a: &[BriefDescriptor],
+    b: &[BriefDescriptor],
+    max_hamming: u32,
+) -> Vec<(usize, usize, u32)> {
+    let hamming = |x: &BriefDescriptor, y: &BriefDescriptor| -> u32 {
+        x.iter().zip(y).map(|(p, q)| (p ^ q).count_ones()).sum()
+    };
+
+    let mut matches = Vec::new();
+    for (i, da) in a.iter().enumerate() {
+        let mut best: Option<(usize, u32)> = None;
+        let mut second = u32::MAX;
+
+        for (j, db) in b.iter().enumerate() {
+            let d = hamming(da, db);
+            match best {
+                Some((_, best_d)) if d >= best_d => second = second.min(d),
+                Some((_, best_d)) => {
+                    second = best_d;
+                    best = Some((j, d));
+                }
+                None => best = Some((j, d)),
+            }
+        }
+
+        if let Some((j, d)) = best
+            && d <= max_hamming
+            && (second == u32::MAX || (d as f32) < MATCH_RATIO * second as f32)
+        {
+            matches.push((i, j, d));
+        }
+    }
+
+    matches
+}
+
+/// Fixed pseudo-random pairs of offsets within a `patch`-sized window
+fn sampling_pattern(patch: usize) -> Vec<((i64, i64), (i64, i64))> {
+    let half = (patch / 2) as i64;
+    let span = 2 * half as u64 + 1;
+
+    // xorshift64 with a fixed seed, so the pattern never changes
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    let mut offset = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % span) as i64 - half
+    };
+
+    (0..BRIEF_BITS)
+        .map(|_| ((offset(), offset()), (offset(), offset())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::texture;
+
+    #[test]
+    fn test_brief_matches_shifted_copy() {
+        let (dx, dy) = (7i64, -4i64);
+        let left = Array2::from_shape_fn((80, 100), |(r, c)| texture(r as i64, c as i64));
+        let right =
+            Array2::from_shape_fn((80, 100), |(r, c)| texture(r as i64 - dy, c as i64 - dx));
+
+        let keypoints: Vec<(f64, f64)> = (0..30)
+            .map(|i| (20.0 + (i % 6) as f64 * 10.0, 20.0 + (i / 6) as f64 * 9.0))
+            .collect();
+
+        // Same features in the shifted image, listed in reverse order
+        let shifted: Vec<(f64, f64)> = keypoints
+            .iter()
+            .rev()
+            .map(|&(x, y)| (x + dx as f64, y + dy as f64))
+            .collect();
+
+        let desc_left = compute_brief(&left, &keypoints, 15);
+        let desc_right = compute_brief(&right, &shifted, 15);
+        assert_eq!(desc_left.len(), keypoints.len());
+
+        let matches = match_descriptors(&desc_left, &desc_right, 40);
+        assert_eq!(matches.len(), keypoints.len());
+        for (i, j, d) in matches {
+            assert_eq!(j, keypoints.len() - 1 - i);
+            assert_eq!(d, 0);
+        }
+    }
+
+    #[test]
+    fn test_match_descriptors_threshold_and_ratio() {
+        let a = [[0u64; 4]];
+
+        // Nearest is too far
+        let far = [[u64::MAX, 0, 0, 0]];
+        assert!(match_descriptors(&a, &far, 10).is_empty());
+
+        // Two equally close candidates fail the ratio test
+        let ambiguous = [[0b11, 0, 0, 0], [0b101, 0, 0, 0]];
+        assert!(match_descriptors(&a, &ambiguous, 10).is_empty());
+
+        let clear = [[0b1, 0, 0, 0], [0xFFFF, 0, 0, 0]];
+        assert_eq!(match_descriptors(&a, &clear, 10), vec![(0, 0, 1)]);
+    }
+
+    #[test]
+    fn test_brief_pattern_is_fixed() {
+        let image = Array2::from_shape_fn((40, 40), |(r, c)| texture(r as i64, c as i64));
+        let first = compute_brief(&image, &[(20.0, 20.0)], 9);
+        let second = compute_brief(&image, &[(20.0, 20.0)], 9);
+        assert_eq!(first, second);
+        assert_ne!(first[0], [0; 4]);
+    }
+}
@@ -0,0 +1,129 @@
+use nalgebra::{Matrix3, Vector3};
+use ndarray::Array2;
+
+use crate::warp::{sample_at, Interp};
+
+/// Half-size of the square NCC window (7x7)
+const NCC_RADIUS: i32 = 3;
+
+/// Match points into a second image by searching along their epipolar lines
+///
+/// Points are `(x, y)` pixel positions and `f` maps a left point to its line
+/// in the right image (`l = F * [x, y, 1]`). The search starts at the point
+/// on the line closest to the left point and samples `search_len` one-pixel
+/// steps either way, scoring a 7x7 bilinear window by normalized
+/// cross-correlation. Returns the best right position and its score per
+/// point, or `None` for a degenerate line, a textureless left window or no
+/// sample whose window fits inside the right image.
+pub fn match_along_epipolar(
+    left: &Array2<f32>,
+    right: &Array2<f32>,
+    f: &Matrix3<f64>,
+    left_pts: &[(f64, f64)],
+    search_len: usize,
+) -> Vec<Option<((f64, f64), f32)>> {
+    left_pts
+        .iter()
+        .map(|&(x, y)| {
+            let template = window(left, x, y)?;
+            let template = normalize(&template)?;
+
+            let line = f * Vector3::new(x, y, 1.0);
+            let norm_sq = line.x * line.x + line.y * line.y;
+            if norm_sq == 0.0 || !norm_sq.is_finite() {
+                return None;
+            }
+
+            // Foot of the perpendicular from the left point, stepping along the line
+            let dist = (line.x * x + line.y * y + line.z) / norm_sq;
+            let (x0, y0) = (x - dist * line.x, y - dist * line.y);
+            let norm = norm_sq.sqrt();
+            let (dx, dy) = (line.y / norm, -line.x / norm);
+
+            let n = search_len as i64;
+            (-n..=n)
+                .filter_map(|step| {
+                    let pos = (x0 + step as f64 * dx, y0 + step as f64 * dy);
+                    let candidate = normalize(&window(right, pos.0, pos.1)?)?;
+                    let score: f32 = template.iter().zip(&candidate).map(|(a, b)| a * b).sum();
+                    Some((pos, score))
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+        })
+        .collect()
+}
+
+/// Bilinear window around `(x, y)`; `None` if any sample is outside the image
+fn window(image: &Array2<f32>, x: f64, y: f64) -> Option<Vec<f32>> {
+    let mut values = Vec::with_capacity(((2 * NCC_RADIUS + 1) * (2 * NCC_RADIUS + 1)) as usize);
+    for dy in -NCC_RADIUS..=NCC_RADIUS {
+        for dx in -NCC_RADIUS..=NCC_RADIUS {
+            let v = sample_at(image, y + dy as f64, x + dx as f64, Interp::Bilinear);
+            if v.is_nan() {
+                return None;
+            }
+            values.push(v);
+        }
+    }
+    Some(values)
+}
+
+/// Zero-mean, unit-norm copy of a window; `None` if it has no variance
+fn normalize(values: &[f32]) -> Option<Vec<f32>> {
+    let mean = values.iter().sum::<f32>() / values.len() as f32;
+    let centered: Vec<f32> = values.iter().map(|v| v - mean).collect();
+    let norm = centered.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm <= f32::EPSILON {
+        return None;
+    }
+    Some(centered.into_iter().map(|v| v / norm).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texture(x: f64, y: f64) -> f32 {
+        ((0.31 * x).sin() + (0.23 * y).cos() + (0.17 * (x + 2.0 * y)).sin() * 0.7
+            + (0.053 * x * y).sin() * 0.3) as f32
+    }
+
+    #[test]
+    fn test_match_along_epipolar_translation() {
+        // Right image is the left shifted by d = (-6, -8): x' = x + d
+        let (dx, dy) = (-6.0, -8.0);
+        let left = Array2::from_shape_fn((60, 80), |(r, c)| texture(c as f64, r as f64));
+        let right =
+            Array2::from_shape_fn((60, 80), |(r, c)| texture(c as f64 - dx, r as f64 - dy));
+
+        // Pure translation: F = [e']x with the epipole at infinity along d
+        let f = Vector3::new(dx, dy, 0.0).cross_matrix();
+
+        let left_pts = [(30.0, 30.0), (45.5, 28.0), (20.0, 40.25), (60.0, 35.0)];
+        let matches = match_along_epipolar(&left, &right, &f, &left_pts, 15);
+
+        assert_eq!(matches.len(), left_pts.len());
+        for (&(x, y), m) in left_pts.iter().zip(&matches) {
+            let ((mx, my), score) = m.expect("point should match");
+            assert!((mx - (x + dx)).abs() < 1e-6, "x {} vs {}", mx, x + dx);
+            assert!((my - (y + dy)).abs() < 1e-6, "y {} vs {}", my, y + dy);
+            assert!(score > 0.999);
+        }
+    }
+
+    #[test]
+    fn test_match_along_epipolar_unmatched() {
+        let flat = Array2::from_elem((20, 20), 1.0f32);
+        let textured = Array2::from_shape_fn((20, 20), |(r, c)| texture(c as f64, r as f64));
+        let f = Vector3::new(1.0, 0.0, 0.0).cross_matrix();
+
+        // Textureless template, window off the image, degenerate line
+        let matches = match_along_epipolar(&flat, &textured, &f, &[(10.0, 10.0)], 5);
+        assert_eq!(matches, vec![None]);
+        let matches = match_along_epipolar(&textured, &textured, &f, &[(1.0, 10.0)], 5);
+        assert_eq!(matches, vec![None]);
+        let zero = Matrix3::zeros();
+        let matches = match_along_epipolar(&textured, &textured, &zero, &[(10.0, 10.0)], 5);
+        assert_eq!(matches, vec![None]);
+    }
+}
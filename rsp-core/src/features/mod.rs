@@ -0,0 +1,164 @@
+//! Feature detection utilities
+
+mod brief;
+mod epipolar;
+
+pub use brief::{compute_brief, match_descriptors, BriefDescriptor};
+pub use epipolar::match_along_epipolar;
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Heap entry ordered by detector response
+struct Candidate {
+    response: f32,
+    index: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Ties broken by input order so results are deterministic
+        self.response
+            .total_cmp(&other.response)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+/// Radius-based non-maximum suppression for detected features
+///
+/// Points are `(x, y, response)`. The strongest points are kept such that no
+/// two survivors lie within `radius` of each other, optionally capped to the
+/// `max_keep` strongest. Kept points are bucketed in a spatial grid with cell
+/// size `radius`, so each candidate only checks its 3x3 cell neighborhood.
+///
+/// Points with a NaN response are discarded. Output is ordered by
+/// decreasing response.
+pub fn nms_radius(
+    points: &[(f32, f32, f32)],
+    radius: f32,
+    max_keep: Option<usize>,
+) -> Vec<(f32, f32, f32)> {
+    let limit = max_keep.unwrap_or(points.len());
+    let mut heap: BinaryHeap<Candidate> = points
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| !p.2.is_nan())
+        .map(|(index, p)| Candidate {
+            response: p.2,
+            index,
+        })
+        .collect();
+
+    let mut kept = Vec::new();
+    let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    let radius_sq = radius * radius;
+
+    while let Some(candidate) = heap.pop() {
+        if kept.len() >= limit {
+            break;
+        }
+
+        let point = points[candidate.index];
+        if radius <= 0.0 {
+            kept.push(point);
+            continue;
+        }
+
+        let cell = (
+            (point.0 / radius).floor() as i64,
+            (point.1 / radius).floor() as i64,
+        );
+
+        let suppressed = (-1..=1).any(|dy| {
+            (-1..=1).any(|dx| {
+                grid.get(&(cell.0 + dx, cell.1 + dy)).is_some_and(|bucket| {
+                    bucket.iter().any(|&k| {
+                        let other = kept[k];
+                        let ddx = other.0 - point.0;
+                        let ddy = other.1 - point.1;
+                        ddx * ddx + ddy * ddy < radius_sq
+                    })
+                })
+            })
+        });
+
+        if !suppressed {
+            grid.entry(cell).or_default().push(kept.len());
+            kept.push(point);
+        }
+    }
+
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clustered_points() -> Vec<(f32, f32, f32)> {
+        vec![
+            // Cluster around (10, 10), strongest is 0.9
+            (10.0, 10.0, 0.5),
+            (11.0, 10.5, 0.9),
+            (9.5, 11.0, 0.7),
+            // Cluster around (50, 20), strongest is 0.8
+            (50.0, 20.0, 0.8),
+            (51.5, 19.0, 0.3),
+            // Cluster around (30, 60), strongest is 0.6
+            (30.0, 60.0, 0.2),
+            (29.0, 61.0, 0.6),
+            (31.0, 59.5, 0.4),
+        ]
+    }
+
+    #[test]
+    fn test_nms_keeps_strongest_per_cluster() {
+        let kept = nms_radius(&clustered_points(), 5.0, None);
+
+        assert_eq!(kept.len(), 3);
+        assert_eq!(kept[0], (11.0, 10.5, 0.9));
+        assert_eq!(kept[1], (50.0, 20.0, 0.8));
+        assert_eq!(kept[2], (29.0, 61.0, 0.6));
+    }
+
+    #[test]
+    fn test_nms_max_keep() {
+        let kept = nms_radius(&clustered_points(), 5.0, Some(2));
+
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[0].2, 0.9);
+        assert_eq!(kept[1].2, 0.8);
+    }
+
+    #[test]
+    fn test_nms_neighbor_cells() {
+        // Points straddle a grid cell boundary but are within the radius
+        let points = vec![(9.9, 0.0, 1.0), (10.1, 0.0, 0.5), (25.0, 0.0, 0.2)];
+        let kept = nms_radius(&points, 10.0, None);
+
+        assert_eq!(kept, vec![(9.9, 0.0, 1.0), (25.0, 0.0, 0.2)]);
+    }
+
+    #[test]
+    fn test_nms_empty_and_nan() {
+        assert!(nms_radius(&[], 3.0, None).is_empty());
+
+        let points = vec![(0.0, 0.0, f32::NAN), (10.0, 10.0, 1.0)];
+        let kept = nms_radius(&points, 3.0, None);
+        assert_eq!(kept, vec![(10.0, 10.0, 1.0)]);
+    }
+}
@@ -0,0 +1,441 @@
+use nalgebra::{
+    DMatrix, DVector, Matrix2x3, Matrix3, Matrix6, Matrix6x3, Rotation3, SMatrix, Vector2, Vector3,
+    Vector6,
+};
+
+use crate::camera::{CameraModel, CameraPose, PinholeCamera};
+use crate::error::{Result, RspError};
+
+/// Initial Levenberg-Marquardt damping, relative to the normal matrix diagonal
+const INITIAL_DAMPING: f64 = 1e-3;
+
+/// Lower bound on the damping as steps keep succeeding
+const MIN_DAMPING: f64 = 1e-12;
+
+/// Damping at which an iteration gives up on finding a cost decrease
+const MAX_DAMPING: f64 = 1e12;
+
+/// Relative cost decrease below which the adjustment is considered converged
+const COST_TOLERANCE: f64 = 1e-15;
+
+/// Camera for [`bundle_adjust`]: a world-to-camera pose with fixed intrinsics
+///
+/// Projection is distortion-free, so observations should be undistorted
+/// pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraPoseWithIntrinsics {
+    pub pose: CameraPose,
+    pub fx: f64,
+    pub fy: f64,
+    pub cx: f64,
+    pub cy: f64,
+}
+
+impl CameraPoseWithIntrinsics {
+    pub fn new(pose: CameraPose, fx: f64, fy: f64, cx: f64, cy: f64) -> Self {
+        Self { pose, fx, fy, cx, cy }
+    }
+
+    /// Pixel of a world point, or `None` at or behind the camera plane
+    pub fn project(&self, point_world: &Vector3<f64>) -> Option<(f64, f64)> {
+        self.camera().project(&self.pose.world_to_camera(point_world))
+    }
+
+    /// Ideal pinhole with these intrinsics; the image size plays no part in projection
+    fn camera(&self) -> PinholeCamera {
+        PinholeCamera::new_ideal(0, 0, self.fx, self.fy, self.cx, self.cy)
+    }
+}
+
+/// Block of the normal equations for one observation
+struct Linearized {
+    residual: Vector2<f64>,
+    /// Derivative with respect to the camera's rotation and translation increments
+    j_camera: SMatrix<f64, 2, 6>,
+    /// Derivative with respect to the point
+    j_point: Matrix2x3<f64>,
+}
+
+/// Sparse bundle adjustment of camera poses and 3D points
+///
+/// Each observation `(camera, point, pixel)` says that `points[point]` is
+/// seen at `(col, row)` `pixel` in `cameras[camera]`. Levenberg-Marquardt
+/// minimizes the summed squared reprojection error over every camera pose
+/// and point; intrinsics stay fixed. The point blocks are eliminated with
+/// the Schur complement, so each step solves a dense system only over the
+/// camera parameters.
+///
+/// The first camera is held fixed to remove the rotation and translation
+/// gauge freedom. The overall scale remains free and is only anchored by
+/// the starting values, so compare results to ground truth up to scale.
+///
+/// Runs at most `iters` iterations and returns the final RMS reprojection
+/// error in pixels. Fails for an observation indexing past `cameras` or
+/// `points`, for a point or a camera other than the first that has no
+/// observations (nothing would constrain it), or if a point starts at or
+/// behind a camera observing it.
+pub fn bundle_adjust(
+    cameras: &mut [CameraPoseWithIntrinsics],
+    points: &mut [Vector3<f64>],
+    observations: &[(usize, usize, (f64, f64))],
+    iters: usize,
+) -> Result<f64> {
+    if let Some(&(camera, point, _)) = observations
+        .iter()
+        .find(|(camera, point, _)| *camera >= cameras.len() || *point >= points.len())
+    {
+        return Err(RspError::InvalidInput(format!(
+            "Observation of point {} in camera {} is out of range ({} cameras, {} points)",
+            point,
+            camera,
+            cameras.len(),
+            points.len()
+        )));
+    }
+    if observations.is_empty() {
+        return Ok(0.0);
+    }
+
+    // Unobserved points and free cameras have zero normal blocks, so no step is solvable
+    let mut point_seen = vec![false; points.len()];
+    let mut camera_seen = vec![false; cameras.len()];
+    for &(camera, point, _) in observations {
+        camera_seen[camera] = true;
+        point_seen[point] = true;
+    }
+    if let Some(point) = point_seen.iter().position(|seen| !seen) {
+        return Err(RspError::InvalidInput(format!("Point {} has no observations", point)));
+    }
+    if let Some(camera) = camera_seen.iter().skip(1).position(|seen| !seen) {
+        return Err(RspError::InvalidInput(format!("Camera {} has no observations", camera + 1)));
+    }
+
+    let cost_of = |cameras: &[CameraPoseWithIntrinsics], points: &[Vector3<f64>]| {
+        observations.iter().try_fold(0.0, |sum, &(camera, point, pixel)| {
+            let (u, v) = cameras[camera].project(&points[point])?;
+            Some(sum + (u - pixel.0).powi(2) + (v - pixel.1).powi(2))
+        })
+    };
+
+    let mut cost = cost_of(cameras, points).ok_or_else(|| {
+        RspError::Numerical("Point behind a camera at the start of bundle adjustment".to_string())
+    })?;
+    let mut damping = INITIAL_DAMPING;
+
+    // Observations of each point, for the Schur complement
+    let mut by_point = vec![Vec::new(); points.len()];
+    for (k, &(_, point, _)) in observations.iter().enumerate() {
+        by_point[point].push(k);
+    }
+
+    for _ in 0..iters {
+        let normal = NormalEquations::build(cameras, points, observations);
+
+        // Try steps with increasing damping until the cost goes down
+        let mut accepted = None;
+        while damping < MAX_DAMPING {
+            let Some((camera_step, point_steps)) =
+                normal.damped_step(observations, &by_point, damping)
+            else {
+                damping *= 10.0;
+                continue;
+            };
+
+            let mut trial_cameras = cameras.to_vec();
+            for (camera, trial) in trial_cameras.iter_mut().enumerate() {
+                if let Some(offset) = camera_block(camera) {
+                    apply_camera_step(trial, &camera_step.fixed_rows::<6>(offset).into_owned());
+                }
+            }
+            let trial_points: Vec<_> =
+                points.iter().zip(&point_steps).map(|(p, delta)| p + delta).collect();
+
+            match cost_of(&trial_cameras, &trial_points) {
+                Some(trial_cost) if trial_cost < cost => {
+                    accepted = Some((trial_cameras, trial_points, trial_cost));
+                    damping = (damping / 10.0).max(MIN_DAMPING);
+                    break;
+                }
+                _ => damping *= 10.0,
+            }
+        }
+
+        let Some((new_cameras, new_points, new_cost)) = accepted else {
+            break;
+        };
+        cameras.copy_from_slice(&new_cameras);
+        points.copy_from_slice(&new_points);
+        let decrease = cost - new_cost;
+        cost = new_cost;
+        if decrease <= COST_TOLERANCE * cost.max(1.0) {
+            break;
+        }
+    }
+
+    Ok((cost / observations.len() as f64).sqrt())
+}
+
+/// Residual and Jacobians of one observation
+fn linearize(
+    camera: &CameraPoseWithIntrinsics,
+    point: &Vector3<f64>,
+    pixel: (f64, f64),
+) -> Linearized {
+    let rotated = camera.pose.rotation * point;
+    let p = rotated + camera.pose.translation;
+    let (inv_z, x, y) = (1.0 / p.z, p.x, p.y);
+
+    let residual = Vector2::new(
+        camera.fx * x * inv_z + camera.cx - pixel.0,
+        camera.fy * y * inv_z + camera.cy - pixel.1,
+    );
+    let d_projection = Matrix2x3::new(
+        camera.fx * inv_z,
+        0.0,
+        -camera.fx * x * inv_z * inv_z,
+        0.0,
+        camera.fy * inv_z,
+        -camera.fy * y * inv_z * inv_z,
+    );
+
+    // Left-multiplied rotation increment: d(exp(w) R X) / dw = -[R X]x
+    let mut d_pose = SMatrix::<f64, 3, 6>::zeros();
+    d_pose.fixed_view_mut::<3, 3>(0, 0).copy_from(&(-rotated.cross_matrix()));
+    d_pose.fixed_view_mut::<3, 3>(0, 3).copy_from(&Matrix3::identity());
+
+    Linearized {
+        residual,
+        j_camera: d_projection * d_pose,
+        j_point: d_projection * camera.pose.rotation.matrix(),
+    }
+}
+
+/// Camera and point blocks of `J^T J` and `J^T r`
+///
+/// The first camera is fixed, but its blocks are kept so that indices match
+/// `cameras`; [`camera_block`] leaves it out of the solve.
+struct NormalEquations {
+    u: Vec<Matrix6<f64>>,
+    g_camera: Vec<Vector6<f64>>,
+    v: Vec<Matrix3<f64>>,
+    g_point: Vec<Vector3<f64>>,
+    /// Camera-point coupling `J_c^T J_p`, one per observation
+    w: Vec<Matrix6x3<f64>>,
+}
+
+impl NormalEquations {
+    fn build(
+        cameras: &[CameraPoseWithIntrinsics],
+        points: &[Vector3<f64>],
+        observations: &[(usize, usize, (f64, f64))],
+    ) -> Self {
+        let mut normal = Self {
+            u: vec![Matrix6::zeros(); cameras.len()],
+            g_camera: vec![Vector6::zeros(); cameras.len()],
+            v: vec![Matrix3::zeros(); points.len()],
+            g_point: vec![Vector3::zeros(); points.len()],
+            w: Vec::with_capacity(observations.len()),
+        };
+
+        for &(camera, point, pixel) in observations {
+            let block = linearize(&cameras[camera], &points[point], pixel);
+            normal.u[camera] += block.j_camera.transpose() * block.j_camera;
+            normal.g_camera[camera] += block.j_camera.transpose() * block.residual;
+            normal.v[point] += block.j_point.transpose() * block.j_point;
+            normal.g_point[point] += block.j_point.transpose() * block.residual;
+            normal.w.push(block.j_camera.transpose() * block.j_point);
+        }
+        normal
+    }
+
+    /// Damped step (free camera parameters, point increments) via the Schur complement
+    ///
+    /// Returns `None` if a damped point block or the reduced camera system
+    /// is singular.
+    fn damped_step(
+        &self,
+        observations: &[(usize, usize, (f64, f64))],
+        by_point: &[Vec<usize>],
+        damping: f64,
+    ) -> Option<(DVector<f64>, Vec<Vector3<f64>>)> {
+        let damp3 = |m: &Matrix3<f64>| m + Matrix3::from_diagonal(&(m.diagonal() * damping));
+        let v_inv: Vec<Matrix3<f64>> =
+            self.v.iter().map(|m| damp3(m).try_inverse()).collect::<Option<_>>()?;
+
+        // Reduced camera system S dc = b, S = U - W V^-1 W^T and b = -g_c + W V^-1 g_p
+        let free = 6 * (self.u.len() - 1);
+        let mut s = DMatrix::zeros(free, free);
+        let mut b = DVector::zeros(free);
+        for (camera, (block, gradient)) in self.u.iter().zip(&self.g_camera).enumerate() {
+            if let Some(i) = camera_block(camera) {
+                let damped = block + Matrix6::from_diagonal(&(block.diagonal() * damping));
+                let mut diagonal = s.fixed_view_mut::<6, 6>(i, i);
+                diagonal += damped;
+                let mut rhs = b.fixed_rows_mut::<6>(i);
+                rhs -= gradient;
+            }
+        }
+        for (point, indices) in by_point.iter().enumerate() {
+            for &k in indices {
+                let Some(i) = camera_block(observations[k].0) else {
+                    continue;
+                };
+                let wv = self.w[k] * v_inv[point];
+                let mut rhs = b.fixed_rows_mut::<6>(i);
+                rhs += wv * self.g_point[point];
+                for &l in indices {
+                    if let Some(j) = camera_block(observations[l].0) {
+                        let mut off_diagonal = s.fixed_view_mut::<6, 6>(i, j);
+                        off_diagonal -= wv * self.w[l].transpose();
+                    }
+                }
+            }
+        }
+
+        let camera_step = if free == 0 { DVector::zeros(0) } else { s.lu().solve(&b)? };
+
+        // Back-substitute for the points: dp = -V^-1 (g_p + W^T dc)
+        let point_steps = by_point
+            .iter()
+            .enumerate()
+            .map(|(point, indices)| {
+                let mut rhs = self.g_point[point];
+                for &k in indices {
+                    if let Some(i) = camera_block(observations[k].0) {
+                        rhs += self.w[k].transpose() * camera_step.fixed_rows::<6>(i);
+                    }
+                }
+                -(v_inv[point] * rhs)
+            })
+            .collect();
+
+        Some((camera_step, point_steps))
+    }
+}
+
+/// Offset of a camera's 6 parameters in the reduced system; `None` for the fixed first camera
+fn camera_block(camera: usize) -> Option<usize> {
+    (camera > 0).then(|| 6 * (camera - 1))
+}
+
+/// Apply a (rotation, translation) increment to a camera pose
+fn apply_camera_step(camera: &mut CameraPoseWithIntrinsics, step: &Vector6<f64>) {
+    let rotation = Rotation3::from_scaled_axis(step.fixed_rows::<3>(0).into_owned());
+    camera.pose = CameraPose::new(
+        rotation * camera.pose.rotation,
+        camera.pose.translation + step.fixed_rows::<3>(3),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Three cameras looking at a cloud of points around (0, 0, 6)
+    fn scene() -> (Vec<CameraPoseWithIntrinsics>, Vec<Vector3<f64>>) {
+        let camera = |x: f64, pitch: f64| {
+            let rotation = Rotation3::from_euler_angles(0.02, pitch, -0.01);
+            let center = Vector3::new(x, 0.1 * x, 0.0);
+            let pose = CameraPose::new(rotation, -(rotation * center));
+            CameraPoseWithIntrinsics::new(pose, 800.0, 810.0, 320.0, 240.0)
+        };
+        let cameras = vec![camera(0.0, 0.0), camera(1.0, 0.12), camera(-1.2, -0.15)];
+
+        let points = (0..12)
+            .map(|i| {
+                let (a, b, c) = (i % 3, (i / 3) % 2, i / 6);
+                Vector3::new(a as f64 - 1.0, b as f64 * 1.2 - 0.6, 5.5 + c as f64 + 0.1 * a as f64)
+            })
+            .collect();
+
+        (cameras, points)
+    }
+
+    fn observe(
+        cameras: &[CameraPoseWithIntrinsics],
+        points: &[Vector3<f64>],
+    ) -> Vec<(usize, usize, (f64, f64))> {
+        let mut observations = Vec::new();
+        for (i, camera) in cameras.iter().enumerate() {
+            for (j, point) in points.iter().enumerate() {
+                observations.push((i, j, camera.project(point).unwrap()));
+            }
+        }
+        observations
+    }
+
+    #[test]
+    fn test_bundle_adjust_recovers_truth() {
+        let (truth_cameras, truth_points) = scene();
+        let observations = observe(&truth_cameras, &truth_points);
+
+        // Perturb every free pose and every point
+        let mut cameras = truth_cameras.clone();
+        for (i, camera) in cameras.iter_mut().enumerate().skip(1) {
+            let k = i as f64;
+            let step = Vector6::new(0.01 * k, -0.008, 0.005, 0.05, -0.03 * k, 0.02);
+            apply_camera_step(camera, &step);
+        }
+        let mut points: Vec<_> = truth_points
+            .iter()
+            .enumerate()
+            .map(|(j, p)| p + Vector3::new(0.05, -0.04, 0.08) * ((j % 5) as f64 - 2.0))
+            .collect();
+
+        let rms = bundle_adjust(&mut cameras, &mut points, &observations, 100).unwrap();
+        assert!(rms < 1e-6, "RMS {}", rms);
+
+        // The first camera is fixed, so the solution matches up to scale
+        let scale = cameras[1].pose.translation.norm() / truth_cameras[1].pose.translation.norm();
+        assert!((scale - 1.0).abs() < 0.1);
+        for (estimate, truth) in points.iter().zip(&truth_points) {
+            assert!((estimate / scale - truth).norm() < 1e-6);
+        }
+        for (estimate, truth) in cameras.iter().zip(&truth_cameras) {
+            // Matrix difference, as the angle from acos is only good to ~1e-8 near zero
+            let rotation = estimate.pose.rotation.matrix() - truth.pose.rotation.matrix();
+            assert!(rotation.norm() < 1e-8, "{}", rotation.norm());
+            let t = estimate.pose.translation / scale - truth.pose.translation;
+            assert!(t.norm() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_bundle_adjust_invalid_input() {
+        let (mut cameras, mut points) = scene();
+        let observations = [(3, 0, (0.0, 0.0))];
+        let result = bundle_adjust(&mut cameras, &mut points, &observations, 10);
+        assert!(matches!(result, Err(RspError::InvalidInput(_))));
+
+        // A point behind the cameras
+        let observations = observe(&cameras, &points);
+        points[0].z = -5.0;
+        let result = bundle_adjust(&mut cameras, &mut points, &observations, 10);
+        assert!(matches!(result, Err(RspError::Numerical(_))));
+    }
+
+    #[test]
+    fn test_bundle_adjust_rejects_unobserved() {
+        let (truth_cameras, truth_points) = scene();
+        let observations = observe(&truth_cameras, &truth_points);
+
+        // An extra point nobody sees
+        let (mut cameras, mut points) = (truth_cameras.clone(), truth_points.clone());
+        points.push(Vector3::new(0.0, 0.0, 7.0));
+        let result = bundle_adjust(&mut cameras, &mut points, &observations, 10);
+        assert!(matches!(result, Err(RspError::InvalidInput(_))));
+
+        // A free camera that sees nothing
+        let (mut cameras, mut points) = (truth_cameras.clone(), truth_points.clone());
+        let without_camera_2: Vec<_> =
+            observations.iter().copied().filter(|&(camera, _, _)| camera != 2).collect();
+        let result = bundle_adjust(&mut cameras, &mut points, &without_camera_2, 10);
+        assert!(matches!(result, Err(RspError::InvalidInput(_))));
+
+        // The fixed first camera may go unobserved
+        let without_camera_0: Vec<_> =
+            observations.iter().copied().filter(|&(camera, _, _)| camera != 0).collect();
+        let rms = bundle_adjust(&mut cameras, &mut points, &without_camera_0, 10).unwrap();
+        assert!(rms < 1e-6, "RMS {}", rms);
+    }
+}
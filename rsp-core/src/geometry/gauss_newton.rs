@@ -0,0 +1,116 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::error::{Result, RspError};
+
+/// Parameter step below which the refinement is considered converged
+const STEP_TOLERANCE: f64 = 1e-12;
+
+/// Central finite-difference step for the Jacobian
+const FD_STEP: f64 = 1e-7;
+
+/// Step halvings tried before an iteration gives up on lowering the cost
+const MAX_HALVINGS: usize = 10;
+
+/// Per-residual weights computed from the current residuals
+pub(super) type ResidualWeights<'a> = &'a dyn Fn(&DVector<f64>) -> DVector<f64>;
+
+/// Gauss-Newton with step halving over a state moved by local increments
+///
+/// `residuals` evaluates the stacked residuals of a state and `apply` moves
+/// a state by a `params`-dimensional increment (e.g. a rotation and a
+/// translation update), so states can live on a manifold. The Jacobian is
+/// taken by central differences around a zero increment. With `weights`,
+/// every iteration reweights the residuals from their current values (IRLS)
+/// and minimizes the weighted sum of squares.
+///
+/// Each step is halved until the cost decreases. Stops after
+/// `max_iterations`, when no halving helps, or when the step becomes
+/// negligible. Errors from `residuals` at the current state or while
+/// differentiating are returned; at a trial state they only reject the step.
+pub(super) fn gauss_newton<S>(
+    initial: S,
+    params: usize,
+    max_iterations: usize,
+    residuals: impl Fn(&S) -> Result<DVector<f64>>,
+    apply: impl Fn(&S, &[f64]) -> S,
+    weights: Option<ResidualWeights>,
+) -> Result<S> {
+    let mut state = initial;
+
+    for _ in 0..max_iterations {
+        let current = residuals(&state)?;
+        let w = match weights {
+            Some(weights) => weights(&current),
+            None => DVector::from_element(current.len(), 1.0),
+        };
+        let cost_of = |r: &DVector<f64>| r.component_mul(r).dot(&w);
+        let cost = cost_of(&current);
+
+        let mut jacobian = DMatrix::zeros(current.len(), params);
+        let mut delta = vec![0.0; params];
+        for k in 0..params {
+            delta[k] = FD_STEP;
+            let plus = residuals(&apply(&state, &delta))?;
+            delta[k] = -FD_STEP;
+            let minus = residuals(&apply(&state, &delta))?;
+            delta[k] = 0.0;
+            jacobian.set_column(k, &((plus - minus) / (2.0 * FD_STEP)));
+        }
+
+        let jtw = jacobian.transpose() * DMatrix::from_diagonal(&w);
+        let step = (&jtw * &jacobian)
+            .lu()
+            .solve(&(-(&jtw * current)))
+            .ok_or_else(|| {
+                RspError::Numerical("Singular normal equations in Gauss-Newton".to_string())
+            })?;
+
+        // Halve the step until the cost decreases
+        let mut factor = 1.0;
+        let mut improved = false;
+        for _ in 0..MAX_HALVINGS {
+            let trial_state = apply(&state, (&step * factor).as_slice());
+            if let Ok(trial) = residuals(&trial_state)
+                && cost_of(&trial) < cost
+            {
+                state = trial_state;
+                improved = true;
+                break;
+            }
+            factor *= 0.5;
+        }
+
+        if !improved || step.norm() * factor < STEP_TOLERANCE {
+            break;
+        }
+    }
+
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Vector2;
+
+    #[test]
+    fn test_gauss_newton_fits_exponential() {
+        // y = 2 exp(-0.5 x), sampled exactly
+        let xs: Vec<f64> = (0..10).map(|i| i as f64 * 0.4).collect();
+        let residuals = |p: &Vector2<f64>| {
+            Ok(DVector::from_iterator(
+                xs.len(),
+                xs.iter().map(|x| p.x * (p.y * x).exp() - 2.0 * (-0.5 * x).exp()),
+            ))
+        };
+        let apply = |p: &Vector2<f64>, delta: &[f64]| p + Vector2::new(delta[0], delta[1]);
+
+        let fit = gauss_newton(Vector2::new(1.0, -0.1), 2, 50, residuals, apply, None).unwrap();
+        assert!((fit - Vector2::new(2.0, -0.5)).norm() < 1e-8, "{}", fit);
+
+        // Zero weights leave nothing to solve for
+        let zero = |r: &DVector<f64>| DVector::zeros(r.len());
+        let singular = gauss_newton(Vector2::new(1.0, -0.1), 2, 50, residuals, apply, Some(&zero));
+        assert!(matches!(singular, Err(RspError::Numerical(_))));
+    }
+}
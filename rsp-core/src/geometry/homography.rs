@@ -0,0 +1,126 @@
+use nalgebra::{Matrix3, Vector3};
+
+use crate::camera::{CameraPose, PinholeCamera};
+use crate::error::Result;
+
+/// Homography induced by a world plane between two frame cameras
+///
+/// The plane is `n . X + d = 0` in world coordinates. With the relative pose
+/// `x2 = R * x1 + t` and the plane moved into camera 1's frame, this returns
+/// `H = K2 (R - t n^T / d) K1^-1`, mapping homogeneous pixels of camera 1 to
+/// camera 2. Lens distortion is ignored, so undistort pixels first. The
+/// result is not normalized and is infinite when the plane passes through
+/// camera 1's center.
+///
+/// Fails with `InvalidInput` if either camera fails
+/// [`PinholeCamera::validated`], e.g. for a zero focal length.
+pub fn plane_homography(
+    cam1: &PinholeCamera,
+    pose1: &CameraPose,
+    cam2: &PinholeCamera,
+    pose2: &CameraPose,
+    plane_normal: &Vector3<f64>,
+    plane_d: f64,
+) -> Result<Matrix3<f64>> {
+    let (cam1, cam2) = (cam1.clone().validated()?, cam2.clone().validated()?);
+
+    let r = pose2.rotation * pose1.rotation.inverse();
+    let t = pose2.translation - r * pose1.translation;
+
+    // Plane in camera 1: n_c = R1 n, d_c = d - n_c . t1
+    let n = pose1.rotation * plane_normal;
+    let d = plane_d - n.dot(&pose1.translation);
+
+    let plane_induced = r.matrix() - t * n.transpose() / d;
+    Ok(intrinsic_matrix(&cam2) * plane_induced * inverse_intrinsic_matrix(&cam1))
+}
+
+/// Calibration matrix `K` of a pinhole camera
+fn intrinsic_matrix(camera: &PinholeCamera) -> Matrix3<f64> {
+    let (fx, fy) = camera.focal_length();
+    let (cx, cy) = camera.principal_point();
+    Matrix3::new(fx, 0.0, cx, 0.0, fy, cy, 0.0, 0.0, 1.0)
+}
+
+/// `K^-1` of a pinhole camera with non-zero focal lengths
+fn inverse_intrinsic_matrix(camera: &PinholeCamera) -> Matrix3<f64> {
+    let (fx, fy) = camera.focal_length();
+    let (cx, cy) = camera.principal_point();
+    Matrix3::new(1.0 / fx, 0.0, -cx / fx, 0.0, 1.0 / fy, -cy / fy, 0.0, 0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::CameraModel;
+    use crate::error::RspError;
+    use nalgebra::Rotation3;
+
+    fn apply(h: &Matrix3<f64>, pixel: (f64, f64)) -> (f64, f64) {
+        let p = h * Vector3::new(pixel.0, pixel.1, 1.0);
+        (p.x / p.z, p.y / p.z)
+    }
+
+    /// Two cameras looking down at the ground plane z = 0 from 100 m
+    fn down_looking_pair() -> (PinholeCamera, CameraPose, PinholeCamera, CameraPose) {
+        let down = Rotation3::from_axis_angle(&Vector3::x_axis(), std::f64::consts::PI);
+        let cam1 = PinholeCamera::new_ideal(1000, 800, 1200.0, 1200.0, 500.0, 400.0);
+        let cam2 = PinholeCamera::new_ideal(1200, 900, 1500.0, 1480.0, 610.0, 455.0);
+
+        let r1 = down;
+        let r2 = Rotation3::new(Vector3::new(0.03, -0.05, 0.1)) * down;
+        let pose1 = CameraPose::new(r1, -(r1 * Vector3::new(0.0, 0.0, 100.0)));
+        let pose2 = CameraPose::new(r2, -(r2 * Vector3::new(25.0, -10.0, 105.0)));
+        (cam1, pose1, cam2, pose2)
+    }
+
+    #[test]
+    fn test_plane_homography_identity() {
+        let (cam, pose, _, _) = down_looking_pair();
+        let h = plane_homography(&cam, &pose, &cam, &pose, &Vector3::z(), 0.0).unwrap();
+
+        assert!((h - Matrix3::identity()).abs().max() < 1e-12);
+        let (u, v) = apply(&h, (123.4, 567.8));
+        assert!((u - 123.4).abs() < 1e-9 && (v - 567.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_plane_homography_round_trip() {
+        let (cam1, pose1, cam2, pose2) = down_looking_pair();
+        let forward = plane_homography(&cam1, &pose1, &cam2, &pose2, &Vector3::z(), 0.0).unwrap();
+        let backward = plane_homography(&cam2, &pose2, &cam1, &pose1, &Vector3::z(), 0.0).unwrap();
+
+        for pixel in [(10.0, 20.0), (500.0, 400.0), (930.5, 777.25)] {
+            let (u, v) = apply(&backward, apply(&forward, pixel));
+            assert!((u - pixel.0).abs() < 1e-8);
+            assert!((v - pixel.1).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_plane_homography_matches_projection() {
+        let (cam1, pose1, cam2, pose2) = down_looking_pair();
+
+        // Plane z = 5 written as n . X + d = 0
+        let h = plane_homography(&cam1, &pose1, &cam2, &pose2, &Vector3::z(), -5.0).unwrap();
+
+        for ground in [Vector3::new(3.0, 4.0, 5.0), Vector3::new(-20.0, 15.0, 5.0)] {
+            let p1 = cam1.project(&pose1.world_to_camera(&ground)).unwrap();
+            let p2 = cam2.project(&pose2.world_to_camera(&ground)).unwrap();
+            let (u, v) = apply(&h, p1);
+            assert!((u - p2.0).abs() < 1e-8);
+            assert!((v - p2.1).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_plane_homography_invalid_intrinsics() {
+        let (cam1, pose1, cam2, pose2) = down_looking_pair();
+        let flat = PinholeCamera::new_ideal(1000, 800, 0.0, 1200.0, 500.0, 400.0);
+
+        for (a, b) in [(&flat, &cam2), (&cam1, &flat)] {
+            let h = plane_homography(a, &pose1, b, &pose2, &Vector3::z(), 0.0);
+            assert!(matches!(h, Err(RspError::InvalidInput(_))));
+        }
+    }
+}
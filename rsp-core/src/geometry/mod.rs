@@ -0,0 +1,18 @@
+//! Multi-view geometry (relative orientation, triangulation)
+
+mod bundle;
+mod gauss_newton;
+mod homography;
+mod orientation;
+mod pnp;
+mod ransac;
+mod relative_pose;
+mod two_view;
+
+pub use bundle::{bundle_adjust, CameraPoseWithIntrinsics};
+pub use homography::plane_homography;
+pub use orientation::slerp_orientation;
+pub use pnp::refine_pose;
+pub use ransac::{ransac, RansacOptions, RansacResult};
+pub use relative_pose::{refine_relative_pose, PixelCorrespondence};
+pub use two_view::{decompose_essential, recover_pose, triangulate_normalized};
@@ -0,0 +1,78 @@
+use nalgebra::{Quaternion, UnitQuaternion};
+
+/// Quaternion dot product above which SLERP falls back to normalized lerp
+const NEAR_IDENTICAL_DOT: f64 = 1.0 - 1e-9;
+
+/// Spherical interpolation between two orientations
+///
+/// `t` is clamped to [0, 1], with 0 giving `a` and 1 giving `b`. The
+/// interpolation always takes the shorter arc, whichever sign `b`'s
+/// quaternion has, and rotates at a constant rate. Nearly identical
+/// orientations are blended linearly and renormalized, which avoids dividing
+/// by a vanishing `sin(theta)`.
+pub fn slerp_orientation(
+    a: &UnitQuaternion<f64>,
+    b: &UnitQuaternion<f64>,
+    t: f64,
+) -> UnitQuaternion<f64> {
+    let t = t.clamp(0.0, 1.0);
+    let qa = a.quaternion();
+    let mut qb = *b.quaternion();
+
+    // q and -q are the same rotation; pick the one on a's side
+    let mut dot = qa.dot(&qb);
+    if dot < 0.0 {
+        qb = -qb;
+        dot = -dot;
+    }
+
+    let blended: Quaternion<f64> = if dot > NEAR_IDENTICAL_DOT {
+        qa * (1.0 - t) + qb * t
+    } else {
+        let theta = dot.min(1.0).acos();
+        let sin_theta = theta.sin();
+        qa * (((1.0 - t) * theta).sin() / sin_theta) + qb * ((t * theta).sin() / sin_theta)
+    };
+
+    UnitQuaternion::new_normalize(blended)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Vector3;
+
+    #[test]
+    fn test_slerp_orientation_endpoints_and_midpoint() {
+        let a = UnitQuaternion::from_euler_angles(0.1, -0.2, 0.3);
+        let b = UnitQuaternion::from_euler_angles(-0.4, 0.5, 1.2);
+        let total = a.angle_to(&b);
+
+        assert!(slerp_orientation(&a, &b, 0.0).angle_to(&a) < 1e-12);
+        assert!(slerp_orientation(&a, &b, 1.0).angle_to(&b) < 1e-12);
+
+        let mid = slerp_orientation(&a, &b, 0.5);
+        assert!((mid.angle_to(&a) - 0.5 * total).abs() < 1e-12);
+        assert!((mid.angle_to(&b) - 0.5 * total).abs() < 1e-12);
+
+        // Constant rate and clamping
+        let quarter = slerp_orientation(&a, &b, 0.25);
+        assert!((quarter.angle_to(&a) - 0.25 * total).abs() < 1e-12);
+        assert!(slerp_orientation(&a, &b, 1.5).angle_to(&b) < 1e-12);
+    }
+
+    #[test]
+    fn test_slerp_orientation_antipodal_and_identical() {
+        let a = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 0.2);
+        let b = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 0.6);
+
+        // The negated quaternion is the same rotation and gives the same path
+        let b_flipped = UnitQuaternion::new_unchecked(-*b.quaternion());
+        let mid = slerp_orientation(&a, &b_flipped, 0.5);
+        let expected = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 0.4);
+        assert!(mid.angle_to(&expected) < 1e-12);
+
+        let same = slerp_orientation(&a, &a, 0.3);
+        assert!(same.angle_to(&a) < 1e-12);
+    }
+}
@@ -0,0 +1,165 @@
+use nalgebra::{DVector, Isometry3, Translation3, UnitQuaternion, Vector3};
+
+use super::gauss_newton::gauss_newton;
+use crate::camera::{CameraModel, PinholeCamera};
+use crate::error::{Result, RspError};
+use crate::math::MEstimator;
+
+/// Minimum observations to constrain the 6 pose parameters
+const MIN_OBSERVATIONS: usize = 3;
+
+/// Maximum Gauss-Newton iterations
+const MAX_ITERATIONS: usize = 100;
+
+/// Refine an absolute camera pose by minimizing reprojection error
+///
+/// `initial` maps world points into the camera frame (`x_cam = R * x_world +
+/// t`, as in [`CameraPose`](crate::camera::CameraPose)) and each world point
+/// is observed at the matching `(col, row)` pixel. Gauss-Newton (with step
+/// halving) runs over a rotation increment and a translation increment.
+///
+/// With a robust `m_estimator`, every iteration reweights each observation
+/// by its current reprojection error (IRLS), so gross outliers stop pulling
+/// the pose. Redescending estimators like Tukey need an `initial` pose whose
+/// inlier residuals are already inside the cutoff.
+///
+/// Needs at least 3 observations; fails if a point falls behind the camera
+/// or every observation is weighted out.
+pub fn refine_pose(
+    camera: &PinholeCamera,
+    points_world: &[Vector3<f64>],
+    pixels: &[(f64, f64)],
+    initial: Isometry3<f64>,
+    m_estimator: MEstimator,
+) -> Result<Isometry3<f64>> {
+    if points_world.len() != pixels.len() {
+        return Err(RspError::InvalidInput(format!(
+            "Got {} world points but {} pixels",
+            points_world.len(),
+            pixels.len()
+        )));
+    }
+    if points_world.len() < MIN_OBSERVATIONS {
+        return Err(RspError::InvalidInput(format!(
+            "Pose refinement needs at least {} observations, got {}",
+            MIN_OBSERVATIONS,
+            points_world.len()
+        )));
+    }
+
+    let residuals = |pose: &Isometry3<f64>| {
+        reprojection_residuals(camera, points_world, pixels, pose).ok_or_else(|| {
+            RspError::Numerical("Point behind the camera during pose refinement".to_string())
+        })
+    };
+    let apply = |pose: &Isometry3<f64>, delta: &[f64]| {
+        let rotation = UnitQuaternion::from_scaled_axis(Vector3::new(delta[0], delta[1], delta[2]))
+            * pose.rotation;
+        let translation = pose.translation.vector + Vector3::new(delta[3], delta[4], delta[5]);
+        Isometry3::from_parts(Translation3::from(translation), rotation)
+    };
+    // One weight per observation, shared by its two residuals
+    let weights = |residuals: &DVector<f64>| {
+        DVector::from_iterator(
+            residuals.len(),
+            residuals.as_slice().chunks(2).flat_map(|r| {
+                let w = m_estimator.weight(r[0].hypot(r[1]));
+                [w, w]
+            }),
+        )
+    };
+
+    gauss_newton(initial, 6, MAX_ITERATIONS, residuals, apply, Some(&weights))
+}
+
+/// Stacked `(du, dv)` reprojection errors, or `None` if a point can't be projected
+fn reprojection_residuals(
+    camera: &PinholeCamera,
+    points_world: &[Vector3<f64>],
+    pixels: &[(f64, f64)],
+    pose: &Isometry3<f64>,
+) -> Option<DVector<f64>> {
+    let mut residuals = DVector::zeros(2 * pixels.len());
+    for (i, (point, pixel)) in points_world.iter().zip(pixels).enumerate() {
+        let (u, v) = camera.project(&(pose.rotation * point + pose.translation.vector))?;
+        residuals[2 * i] = u - pixel.0;
+        residuals[2 * i + 1] = v - pixel.1;
+    }
+    Some(residuals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Camera, true pose, world points and their exact pixels
+    type Scene = (PinholeCamera, Isometry3<f64>, Vec<Vector3<f64>>, Vec<(f64, f64)>);
+
+    fn scene() -> Scene {
+        let camera = PinholeCamera::new_ideal(640, 480, 500.0, 500.0, 320.0, 240.0);
+        let truth = Isometry3::from_parts(
+            Translation3::new(0.3, -0.2, 4.0),
+            UnitQuaternion::from_euler_angles(0.05, -0.1, 0.2),
+        );
+
+        let points: Vec<_> = (0..24)
+            .map(|i| {
+                let (a, b, c) = (i % 4, (i / 4) % 3, i / 12);
+                Vector3::new(a as f64 - 1.5, b as f64 - 1.0, c as f64 * 0.8 - 0.4)
+            })
+            .collect();
+        let pixels = points
+            .iter()
+            .map(|p| camera.project(&(truth.rotation * p + truth.translation.vector)).unwrap())
+            .collect();
+
+        (camera, truth, points, pixels)
+    }
+
+    fn pose_error(a: &Isometry3<f64>, b: &Isometry3<f64>) -> f64 {
+        (a.translation.vector - b.translation.vector).norm() + a.rotation.angle_to(&b.rotation)
+    }
+
+    #[test]
+    fn test_refine_pose_recovers_truth() {
+        let (camera, truth, points, pixels) = scene();
+        let initial = Isometry3::from_parts(
+            Translation3::new(0.25, -0.1, 4.2),
+            UnitQuaternion::from_euler_angles(0.08, -0.12, 0.17),
+        );
+
+        let refined = refine_pose(&camera, &points, &pixels, initial, MEstimator::None).unwrap();
+        assert!(pose_error(&refined, &truth) < 1e-8);
+    }
+
+    #[test]
+    fn test_refine_pose_tukey_rejects_outliers() {
+        let (camera, truth, points, mut pixels) = scene();
+        for (i, offset) in [(3, (60.0, -25.0)), (10, (-40.0, 70.0)), (17, (90.0, 35.0))] {
+            pixels[i].0 += offset.0;
+            pixels[i].1 += offset.1;
+        }
+        let initial = Isometry3::from_parts(
+            Translation3::new(0.31, -0.19, 4.02),
+            UnitQuaternion::from_euler_angles(0.052, -0.098, 0.201),
+        );
+
+        let plain = refine_pose(&camera, &points, &pixels, initial, MEstimator::None).unwrap();
+        let tukey =
+            refine_pose(&camera, &points, &pixels, initial, MEstimator::Tukey(10.0)).unwrap();
+
+        // The inlier-only solution is the true pose
+        assert!(pose_error(&plain, &truth) > 1e-2);
+        assert!(pose_error(&tukey, &truth) < 1e-8);
+    }
+
+    #[test]
+    fn test_refine_pose_invalid_input() {
+        let (camera, truth, points, pixels) = scene();
+        let few = refine_pose(&camera, &points[..2], &pixels[..2], truth, MEstimator::None);
+        assert!(matches!(few, Err(RspError::InvalidInput(_))));
+
+        let mismatched = refine_pose(&camera, &points, &pixels[..5], truth, MEstimator::None);
+        assert!(matches!(mismatched, Err(RspError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+
+/// RANSAC settings shared by the robust estimators
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RansacOptions {
+    /// Upper bound on the number of hypotheses
+    pub max_iters: usize,
+    /// Residual at or below which a sample counts as an inlier
+    pub threshold: f64,
+    /// Probability of drawing at least one all-inlier sample, in (0, 1)
+    pub confidence: f64,
+    /// RNG seed for reproducible runs; `None` seeds from the OS
+    pub seed: Option<u64>,
+}
+
+impl Default for RansacOptions {
+    fn default() -> Self {
+        Self {
+            max_iters: 1000,
+            threshold: 1.0,
+            confidence: 0.99,
+            seed: None,
+        }
+    }
+}
+
+impl RansacOptions {
+    /// Hypotheses needed to reach `confidence` at an inlier ratio
+    ///
+    /// The standard `log(1 - confidence) / log(1 - ratio^sample_size)`,
+    /// capped at `max_iters`.
+    pub fn required_iterations(&self, inlier_ratio: f64, sample_size: usize) -> usize {
+        let all_inliers = inlier_ratio.clamp(0.0, 1.0).powi(sample_size as i32);
+        if all_inliers >= 1.0 {
+            return 1;
+        }
+        if all_inliers <= 0.0 {
+            return self.max_iters;
+        }
+
+        let confidence = self.confidence.clamp(0.0, 1.0 - f64::EPSILON);
+        let needed = ((1.0 - confidence).ln() / (1.0 - all_inliers).ln()).ceil();
+        if needed.is_finite() {
+            (needed.max(1.0) as usize).min(self.max_iters)
+        } else {
+            self.max_iters
+        }
+    }
+}
+
+/// Best model found by [`ransac`]
+#[derive(Debug, Clone)]
+pub struct RansacResult<M> {
+    pub model: M,
+    /// Inlier flag per data point
+    pub inliers: Vec<bool>,
+    /// Hypotheses drawn before stopping
+    pub iterations: usize,
+}
+
+/// Generic RANSAC over `count` data points
+///
+/// Each iteration draws `sample_size` distinct indices, builds a hypothesis
+/// with `fit` (which may reject degenerate samples with `None`) and counts
+/// the points whose `residual` is within the threshold. The iteration limit
+/// shrinks as better hypotheses are found, following
+/// [`RansacOptions::required_iterations`]. The model with the most inliers
+/// wins; ties keep the earlier one. Returns `None` if there are fewer points
+/// than `sample_size` or no sample produced a model.
+pub fn ransac<M, F, R>(
+    count: usize,
+    sample_size: usize,
+    options: &RansacOptions,
+    mut fit: F,
+    residual: R,
+) -> Option<RansacResult<M>>
+where
+    F: FnMut(&[usize]) -> Option<M>,
+    R: Fn(&M, usize) -> f64,
+{
+    if sample_size == 0 || count < sample_size {
+        return None;
+    }
+
+    let seed = options
+        .seed
+        .unwrap_or_else(|| RandomState::new().hash_one(0x5EED_u64));
+    let mut rng = SplitMix64(seed);
+
+    let mut best: Option<RansacResult<M>> = None;
+    let mut best_count = 0;
+    let mut limit = options.max_iters;
+    let mut sample = Vec::with_capacity(sample_size);
+    let mut iterations = 0;
+
+    while iterations < limit {
+        iterations += 1;
+
+        sample.clear();
+        while sample.len() < sample_size {
+            let index = rng.below(count);
+            if !sample.contains(&index) {
+                sample.push(index);
+            }
+        }
+
+        let Some(model) = fit(&sample) else {
+            continue;
+        };
+
+        let inliers: Vec<bool> = (0..count)
+            .map(|i| residual(&model, i) <= options.threshold)
+            .collect();
+        let inlier_count = inliers.iter().filter(|&&inlier| inlier).count();
+
+        if best.is_none() || inlier_count > best_count {
+            best_count = inlier_count;
+            best = Some(RansacResult { model, inliers, iterations });
+            let ratio = inlier_count as f64 / count as f64;
+            limit = limit.min(options.required_iterations(ratio, sample_size));
+        }
+    }
+
+    best.map(|result| RansacResult { iterations, ..result })
+}
+
+/// SplitMix64 generator: small, fast and fully determined by its seed
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform integer in `0..n` (n > 0)
+    fn below(&mut self, n: usize) -> usize {
+        ((self.next() as u128 * n as u128) >> 64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Points on y = 2x + 1 with every fourth point replaced by an outlier
+    fn line_points() -> Vec<(f64, f64)> {
+        (0..60)
+            .map(|i| {
+                let x = i as f64 * 0.5;
+                if i % 4 == 3 {
+                    (x, 40.0 - 3.0 * x + (i % 7) as f64)
+                } else {
+                    (x, 2.0 * x + 1.0 + 0.01 * ((i * 13 % 5) as f64 - 2.0))
+                }
+            })
+            .collect()
+    }
+
+    fn fit_line(points: &[(f64, f64)], sample: &[usize]) -> Option<(f64, f64)> {
+        let (x0, y0) = points[sample[0]];
+        let (x1, y1) = points[sample[1]];
+        if (x1 - x0).abs() < 1e-12 {
+            return None;
+        }
+        let slope = (y1 - y0) / (x1 - x0);
+        Some((slope, y0 - slope * x0))
+    }
+
+    fn run(points: &[(f64, f64)], options: &RansacOptions) -> RansacResult<(f64, f64)> {
+        ransac(
+            points.len(),
+            2,
+            options,
+            |sample| fit_line(points, sample),
+            |&(slope, intercept), i| (points[i].1 - (slope * points[i].0 + intercept)).abs(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_ransac_seeded_is_reproducible() {
+        let points = line_points();
+        let options = RansacOptions { threshold: 0.05, seed: Some(42), ..Default::default() };
+
+        let first = run(&points, &options);
+        let second = run(&points, &options);
+        assert_eq!(first.inliers, second.inliers);
+        assert_eq!(first.iterations, second.iterations);
+        assert_eq!(first.model, second.model);
+
+        // The outliers are rejected and the line recovered
+        for (i, &inlier) in first.inliers.iter().enumerate() {
+            assert_eq!(inlier, i % 4 != 3);
+        }
+        assert!((first.model.0 - 2.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_ransac_adaptive_iterations() {
+        let points = line_points();
+        let options = RansacOptions { threshold: 0.05, seed: Some(7), ..Default::default() };
+        let result = run(&points, &options);
+
+        // 75% inliers with 2-point samples needs about 6 draws at 99%
+        assert!(result.iterations < 50, "{} iterations", result.iterations);
+        assert_eq!(options.required_iterations(0.75, 2), 6);
+        assert_eq!(options.required_iterations(1.0, 4), 1);
+        assert_eq!(options.required_iterations(0.0, 4), options.max_iters);
+        assert_eq!(options.required_iterations(0.01, 8), options.max_iters);
+    }
+
+    #[test]
+    fn test_ransac_too_few_points() {
+        let options = RansacOptions::default();
+        let result = ransac(1, 2, &options, |_| Some(()), |_, _| 0.0);
+        assert!(result.is_none());
+    }
+}
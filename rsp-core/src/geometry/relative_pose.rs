@@ -0,0 +1,182 @@
+use nalgebra::{DVector, Isometry3, Matrix3, Translation3, UnitQuaternion, Vector3};
+
+use super::gauss_newton::gauss_newton;
+use crate::camera::{CameraModel, PinholeCamera};
+use crate::error::{Result, RspError};
+
+/// Minimum correspondences to constrain the 5 relative-pose parameters
+const MIN_CORRESPONDENCES: usize = 5;
+
+/// Maximum Gauss-Newton iterations
+const MAX_ITERATIONS: usize = 50;
+
+/// A matched pixel pair `(pixel_a, pixel_b)` as `(col, row)` tuples
+pub type PixelCorrespondence = ((f64, f64), (f64, f64));
+
+/// Refine a relative pose by minimizing the Sampson error
+///
+/// `initial` maps camera A coordinates into camera B (`x_b = R * x_a + t`,
+/// as in [`decompose_essential`](super::decompose_essential)) and each
+/// correspondence is `(pixel_a, pixel_b)`. Pixels are undistorted and
+/// normalized with the cameras, then Gauss-Newton (with step halving) runs
+/// over a rotation increment and the direction of `t` on the unit sphere.
+/// The translation is unobservable in scale, so the result keeps the length
+/// of the initial translation.
+///
+/// Needs at least 5 correspondences and a non-zero initial translation.
+pub fn refine_relative_pose(
+    cam_a: &PinholeCamera,
+    cam_b: &PinholeCamera,
+    correspondences: &[PixelCorrespondence],
+    initial: Isometry3<f64>,
+) -> Result<Isometry3<f64>> {
+    if correspondences.len() < MIN_CORRESPONDENCES {
+        return Err(RspError::InvalidInput(format!(
+            "Relative pose refinement needs at least {} correspondences, got {}",
+            MIN_CORRESPONDENCES,
+            correspondences.len()
+        )));
+    }
+
+    let scale = initial.translation.vector.norm();
+    if scale == 0.0 || !scale.is_finite() {
+        return Err(RspError::InvalidInput(
+            "Initial translation must be non-zero".to_string(),
+        ));
+    }
+
+    // Normalized image coordinates on the z = 1 plane
+    let rays = correspondences
+        .iter()
+        .map(|&(pa, pb)| {
+            let a = cam_a.unproject(pa)?;
+            let b = cam_b.unproject(pb)?;
+            Ok((a / a.z, b / b.z))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // State is the rotation and the unit translation direction
+    let residuals = |(rotation, direction): &(UnitQuaternion<f64>, Vector3<f64>)| {
+        Ok(sampson_residuals(&rays, rotation, direction))
+    };
+    let apply = |(rotation, direction): &(UnitQuaternion<f64>, Vector3<f64>), delta: &[f64]| {
+        let (b1, b2) = tangent_basis(direction);
+        let r = UnitQuaternion::from_scaled_axis(Vector3::new(delta[0], delta[1], delta[2]))
+            * rotation;
+        let t = (direction + b1 * delta[3] + b2 * delta[4]).normalize();
+        (r, t)
+    };
+
+    let initial_state = (initial.rotation, initial.translation.vector / scale);
+    let (rotation, direction) =
+        gauss_newton(initial_state, 5, MAX_ITERATIONS, residuals, apply, None)?;
+
+    Ok(Isometry3::from_parts(
+        Translation3::from(direction * scale),
+        rotation,
+    ))
+}
+
+/// Signed Sampson distance of each correspondence to `E = [t]x R`
+fn sampson_residuals(
+    rays: &[(Vector3<f64>, Vector3<f64>)],
+    rotation: &UnitQuaternion<f64>,
+    translation: &Vector3<f64>,
+) -> DVector<f64> {
+    let e = translation.cross_matrix() * rotation.to_rotation_matrix().matrix();
+    let et: Matrix3<f64> = e.transpose();
+
+    DVector::from_iterator(
+        rays.len(),
+        rays.iter().map(|(xa, xb)| {
+            let ea = e * xa;
+            let eb = et * xb;
+            let denom = (ea.x * ea.x + ea.y * ea.y + eb.x * eb.x + eb.y * eb.y).sqrt();
+            if denom > 0.0 { xb.dot(&ea) / denom } else { 0.0 }
+        }),
+    )
+}
+
+/// Two unit vectors spanning the plane orthogonal to a unit vector
+fn tangent_basis(v: &Vector3<f64>) -> (Vector3<f64>, Vector3<f64>) {
+    let axis = if v.x.abs() < 0.9 { Vector3::x() } else { Vector3::y() };
+    let b1 = v.cross(&axis).normalize();
+    let b2 = v.cross(&b1);
+    (b1, b2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene_points() -> Vec<Vector3<f64>> {
+        let mut points = Vec::new();
+        for i in 0..6 {
+            for j in 0..5 {
+                let x = -1.2 + 0.5 * i as f64;
+                let y = -1.0 + 0.5 * j as f64;
+                let z = 5.0 + 0.4 * ((i * 3 + j) % 5) as f64;
+                points.push(Vector3::new(x, y, z));
+            }
+        }
+        points
+    }
+
+    fn synthetic_pair() -> (
+        PinholeCamera,
+        PinholeCamera,
+        Isometry3<f64>,
+        Vec<PixelCorrespondence>,
+    ) {
+        let cam_a = PinholeCamera::new_ideal(640, 480, 800.0, 800.0, 320.0, 240.0);
+        let cam_b = PinholeCamera::new_brown_conrady(
+            640, 480, 780.0, 790.0, 310.0, 245.0, -0.05, 0.01, 0.0, 0.0005, -0.0003,
+        );
+        let truth = Isometry3::new(Vector3::new(-1.0, 0.1, 0.05), Vector3::new(0.02, 0.15, -0.03));
+
+        let correspondences = scene_points()
+            .iter()
+            .map(|p| {
+                let pa = cam_a.project(p).unwrap();
+                let pb = cam_b.project(&(truth * nalgebra::Point3::from(*p)).coords).unwrap();
+                (pa, pb)
+            })
+            .collect();
+
+        (cam_a, cam_b, truth, correspondences)
+    }
+
+    #[test]
+    fn test_refine_relative_pose_recovers_truth() {
+        let (cam_a, cam_b, truth, correspondences) = synthetic_pair();
+
+        // Perturb rotation by ~1.5 degrees and tilt the baseline direction
+        let perturbed = Isometry3::from_parts(
+            Translation3::from(truth.translation.vector + Vector3::new(0.0, 0.08, -0.1)),
+            UnitQuaternion::from_scaled_axis(Vector3::new(0.01, -0.02, 0.015)) * truth.rotation,
+        );
+
+        let refined = refine_relative_pose(&cam_a, &cam_b, &correspondences, perturbed).unwrap();
+
+        assert!(refined.rotation.angle_to(&truth.rotation) < 1e-6);
+        let t_refined = refined.translation.vector.normalize();
+        let t_true = truth.translation.vector.normalize();
+        assert!(t_refined.dot(&t_true) > 1.0 - 1e-10);
+
+        // Scale of the initial translation is kept
+        let initial_norm = perturbed.translation.vector.norm();
+        assert!((refined.translation.vector.norm() - initial_norm).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_refine_relative_pose_invalid_input() {
+        let (cam_a, cam_b, truth, correspondences) = synthetic_pair();
+
+        let result = refine_relative_pose(&cam_a, &cam_b, &correspondences[..4], truth);
+        assert!(matches!(result, Err(RspError::InvalidInput(_))));
+
+        let no_baseline = Isometry3::from_parts(Translation3::identity(), truth.rotation);
+        let result = refine_relative_pose(&cam_a, &cam_b, &correspondences, no_baseline);
+        assert!(matches!(result, Err(RspError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,239 @@
+use nalgebra::{Matrix3, Matrix4, Rotation3, RowVector4, Vector3};
+
+/// Maximum number of correspondences triangulated per pose candidate
+const CHEIRALITY_SAMPLES: usize = 50;
+
+/// Decompose an essential matrix into its four (R, t) candidates
+///
+/// Convention: a point in camera 1 maps to camera 2 as `x2 = R * x1 + t`,
+/// with `E = [t]x R`. The translation is only known up to scale and is
+/// returned as a unit vector.
+pub fn decompose_essential(e: &Matrix3<f64>) -> [(Rotation3<f64>, Vector3<f64>); 4] {
+    let svd = e.svd(true, true);
+    let mut u = svd.u.expect("SVD requested U");
+    let mut v_t = svd.v_t.expect("SVD requested V^T");
+
+    // Force proper rotations
+    if u.determinant() < 0.0 {
+        u = -u;
+    }
+    if v_t.determinant() < 0.0 {
+        v_t = -v_t;
+    }
+
+    let w = Matrix3::new(
+        0.0, -1.0, 0.0,
+        1.0, 0.0, 0.0,
+        0.0, 0.0, 1.0,
+    );
+
+    let r1 = Rotation3::from_matrix_unchecked(u * w * v_t);
+    let r2 = Rotation3::from_matrix_unchecked(u * w.transpose() * v_t);
+    let t = u.column(2).into_owned();
+
+    [(r1, t), (r1, -t), (r2, t), (r2, -t)]
+}
+
+/// Triangulate a point from two normalized image observations (linear DLT)
+///
+/// Camera 1 is `[I | 0]` and camera 2 is `[R | t]`. Returns the point in
+/// camera 1 coordinates, or `None` if the solution lies at infinity.
+pub fn triangulate_normalized(
+    rotation: &Rotation3<f64>,
+    translation: &Vector3<f64>,
+    x1: (f64, f64),
+    x2: (f64, f64),
+) -> Option<Vector3<f64>> {
+    let r = rotation.matrix();
+    let t = translation;
+
+    let mut a = Matrix4::zeros();
+    // Camera 1 rows: x * P3 - P1, y * P3 - P2 with P = [I | 0]
+    a.set_row(0, &RowVector4::new(-1.0, 0.0, x1.0, 0.0));
+    a.set_row(1, &RowVector4::new(0.0, -1.0, x1.1, 0.0));
+
+    // Camera 2 rows with P = [R | t]
+    for (row, (coord, axis)) in [(x2.0, 0), (x2.1, 1)].into_iter().enumerate() {
+        a.set_row(
+            row + 2,
+            &RowVector4::new(
+                coord * r[(2, 0)] - r[(axis, 0)],
+                coord * r[(2, 1)] - r[(axis, 1)],
+                coord * r[(2, 2)] - r[(axis, 2)],
+                coord * t.z - t[axis],
+            ),
+        );
+    }
+
+    let svd = a.svd(false, true);
+    let v_t = svd.v_t?;
+
+    // Right singular vector for the smallest singular value
+    let (min_idx, _) = svd
+        .singular_values
+        .iter()
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(b.1))?;
+    let h = v_t.row(min_idx);
+
+    if h[3].abs() < 1e-12 {
+        return None;
+    }
+
+    Some(Vector3::new(h[0] / h[3], h[1] / h[3], h[2] / h[3]))
+}
+
+/// Recover the physically valid relative pose from an essential matrix
+///
+/// Each of the four decompositions is tested by triangulating a sample of the
+/// correspondences; the candidate with the most points in front of both
+/// cameras (cheirality) wins. Pixel coordinates are normalized with the
+/// intrinsic matrices `k1` and `k2`.
+///
+/// Returns `None` if the inputs are empty or mismatched, an intrinsic matrix
+/// is singular, or no candidate places any point in front of both cameras.
+pub fn recover_pose(
+    e: &Matrix3<f64>,
+    pts1: &[(f64, f64)],
+    pts2: &[(f64, f64)],
+    k1: &Matrix3<f64>,
+    k2: &Matrix3<f64>,
+) -> Option<(Rotation3<f64>, Vector3<f64>)> {
+    if pts1.is_empty() || pts1.len() != pts2.len() {
+        return None;
+    }
+
+    let k1_inv = k1.try_inverse()?;
+    let k2_inv = k2.try_inverse()?;
+
+    let normalize = |k_inv: &Matrix3<f64>, p: (f64, f64)| {
+        let h = k_inv * Vector3::new(p.0, p.1, 1.0);
+        (h.x / h.z, h.y / h.z)
+    };
+
+    // Evenly spaced sample of the correspondences
+    let step = pts1.len().div_ceil(CHEIRALITY_SAMPLES);
+    let samples: Vec<((f64, f64), (f64, f64))> = (0..pts1.len())
+        .step_by(step)
+        .map(|i| (normalize(&k1_inv, pts1[i]), normalize(&k2_inv, pts2[i])))
+        .collect();
+
+    let mut best: Option<(usize, Rotation3<f64>, Vector3<f64>)> = None;
+
+    for (rotation, translation) in decompose_essential(e) {
+        let in_front = samples
+            .iter()
+            .filter_map(|&(x1, x2)| triangulate_normalized(&rotation, &translation, x1, x2))
+            .filter(|p1| {
+                let p2 = rotation * p1 + translation;
+                p1.z > 0.0 && p2.z > 0.0
+            })
+            .count();
+
+        if in_front > 0 && best.as_ref().is_none_or(|(count, _, _)| in_front > *count) {
+            best = Some((in_front, rotation, translation));
+        }
+    }
+
+    best.map(|(_, rotation, translation)| (rotation, translation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skew(t: &Vector3<f64>) -> Matrix3<f64> {
+        Matrix3::new(
+            0.0, -t.z, t.y,
+            t.z, 0.0, -t.x,
+            -t.y, t.x, 0.0,
+        )
+    }
+
+    fn intrinsics() -> Matrix3<f64> {
+        Matrix3::new(
+            800.0, 0.0, 320.0,
+            0.0, 800.0, 240.0,
+            0.0, 0.0, 1.0,
+        )
+    }
+
+    fn project(k: &Matrix3<f64>, p: &Vector3<f64>) -> (f64, f64) {
+        let h = k * p;
+        (h.x / h.z, h.y / h.z)
+    }
+
+    /// Grid of points in front of camera 1
+    fn scene_points() -> Vec<Vector3<f64>> {
+        let mut points = Vec::new();
+        for i in 0..5 {
+            for j in 0..5 {
+                let x = -1.0 + 0.5 * i as f64;
+                let y = -1.0 + 0.5 * j as f64;
+                let z = 5.0 + 0.3 * ((i * 3 + j) % 4) as f64;
+                points.push(Vector3::new(x, y, z));
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn test_decompose_essential_candidates() {
+        let rotation = Rotation3::from_euler_angles(0.05, -0.1, 0.02);
+        let translation = Vector3::new(1.0, 0.2, 0.1).normalize();
+        let e = skew(&translation) * rotation.matrix();
+
+        let candidates = decompose_essential(&e);
+        let found = candidates.iter().any(|(r, t)| {
+            r.angle_to(&rotation) < 1e-9 && (t - translation).norm() < 1e-9
+        });
+        assert!(found);
+
+        for (r, t) in candidates.iter() {
+            assert!((r.matrix().determinant() - 1.0).abs() < 1e-9);
+            assert!((t.norm() - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_triangulate_normalized() {
+        let rotation = Rotation3::from_euler_angles(0.0, 0.1, 0.0);
+        let translation = Vector3::new(-1.0, 0.0, 0.0);
+        let point = Vector3::new(0.3, -0.2, 4.0);
+
+        let p2 = rotation * point + translation;
+        let x1 = (point.x / point.z, point.y / point.z);
+        let x2 = (p2.x / p2.z, p2.y / p2.z);
+
+        let recovered = triangulate_normalized(&rotation, &translation, x1, x2).unwrap();
+        assert!((recovered - point).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_recover_pose_synthetic() {
+        let k = intrinsics();
+        let rotation = Rotation3::from_euler_angles(0.02, 0.15, -0.03);
+        let translation = Vector3::new(-1.0, 0.1, 0.05);
+
+        let points = scene_points();
+        let pts1: Vec<(f64, f64)> = points.iter().map(|p| project(&k, p)).collect();
+        let pts2: Vec<(f64, f64)> = points
+            .iter()
+            .map(|p| project(&k, &(rotation * p + translation)))
+            .collect();
+
+        let e = skew(&translation) * rotation.matrix();
+        let (r, t) = recover_pose(&e, &pts1, &pts2, &k, &k).unwrap();
+
+        assert!(r.angle_to(&rotation) < 1e-6);
+        assert!(t.dot(&translation.normalize()) > 1.0 - 1e-9);
+    }
+
+    #[test]
+    fn test_recover_pose_rejects_mismatched_input() {
+        let k = intrinsics();
+        let e = Matrix3::identity();
+        assert!(recover_pose(&e, &[(0.0, 0.0)], &[], &k, &k).is_none());
+        assert!(recover_pose(&e, &[], &[], &k, &k).is_none());
+    }
+}
@@ -1,8 +1,23 @@
 pub mod camera;
 pub mod coordinate;
 pub mod error;
+pub mod features;
+pub mod geometry;
+pub mod math;
+pub mod ortho;
 pub mod sensor;
+pub mod stereo;
+pub mod warp;
 
-pub use camera::{CameraModel, FisheyeCamera, PinholeCamera};
-pub use error::{CoordinateError, ProjectionError, Result, RspError};
+#[cfg(test)]
+mod test_util;
+
+pub use camera::{
+    CameraConfig, CameraModel, CameraModelClone, CameraPose, FisheyeCamera, PinholeCamera,
+    UndistortOptions,
+};
+pub use error::{
+    CoordinateError, DistortionError, NumericalError, ProjectionError, Result, RspError,
+};
 pub use sensor::rpc::{RpcCoefficients, RpcModel};
+pub use sensor::SensorModel;
@@ -0,0 +1,186 @@
+//! Small numerical helpers shared across models
+
+use ndarray::{ArrayBase, Data, Dimension};
+
+use crate::error::NumericalError;
+
+/// Relative determinant threshold below which a 2x2 system counts as singular
+const SINGULAR_TOLERANCE: f64 = 1e-12;
+
+/// Solve the 2x2 linear system `j * x = r` by Cramer's rule
+///
+/// The system is singular when `|det| <= 1e-12 * (|j00 j11| + |j01 j10|)`,
+/// a test that doesn't depend on the units of `j`. Non-finite inputs or
+/// results give `NonFinite`.
+pub fn solve_2x2(j: [[f64; 2]; 2], r: [f64; 2]) -> Result<[f64; 2], NumericalError> {
+    let [[a, b], [c, d]] = j;
+    let det = a * d - b * c;
+    if !det.is_finite() || !r[0].is_finite() || !r[1].is_finite() {
+        return Err(NumericalError::NonFinite);
+    }
+
+    let scale = (a * d).abs() + (b * c).abs();
+    if det.abs() <= SINGULAR_TOLERANCE * scale {
+        return Err(NumericalError::Singular);
+    }
+
+    let x = [(d * r[0] - b * r[1]) / det, (a * r[1] - c * r[0]) / det];
+    if !x[0].is_finite() || !x[1].is_finite() {
+        return Err(NumericalError::NonFinite);
+    }
+
+    Ok(x)
+}
+
+/// Robust loss for iteratively reweighted least squares
+///
+/// Each Gauss-Newton iteration reweights every observation by its current
+/// residual `r` (pixels, for the solvers in this crate), so bad measurements
+/// lose influence.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RobustLoss {
+    /// Plain least squares, every observation weighted 1
+    #[default]
+    None,
+    /// Weight `min(1, k / r)`: quadratic up to `k` pixels, linear beyond
+    Huber(f64),
+    /// Weight `(1 - (r / c)^2)^2` up to `c` pixels, 0 beyond
+    Tukey(f64),
+}
+
+/// M-estimator name for [`RobustLoss`], as used by pose refinement
+pub type MEstimator = RobustLoss;
+
+impl RobustLoss {
+    /// Iteratively reweighted least-squares weight for a residual magnitude
+    pub fn weight(&self, residual: f64) -> f64 {
+        let r = residual.abs();
+        match *self {
+            RobustLoss::None => 1.0,
+            RobustLoss::Huber(k) => {
+                if r <= k { 1.0 } else { k / r }
+            }
+            RobustLoss::Tukey(c) => {
+                if r >= c {
+                    0.0
+                } else {
+                    let u = r / c;
+                    (1.0 - u * u).powi(2)
+                }
+            }
+        }
+    }
+}
+
+/// The `p`-th percentile (0-100) of a slice, ignoring NaN
+///
+/// Interpolates linearly between the two nearest ranks, so the 0th and
+/// 100th percentiles are the minimum and maximum. Uses quickselect rather
+/// than a full sort. `p` is clamped to [0, 100]; returns `None` for NaN `p`
+/// or when no non-NaN values remain.
+pub fn percentile(values: &[f32], p: f64) -> Option<f32> {
+    let mut valid: Vec<f32> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+    select_percentile(&mut valid, p)
+}
+
+/// [`percentile`] over the elements of an array of any dimension
+pub fn percentile_array<S, D>(values: &ArrayBase<S, D>, p: f64) -> Option<f32>
+where
+    S: Data<Elem = f32>,
+    D: Dimension,
+{
+    let mut valid: Vec<f32> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+    select_percentile(&mut valid, p)
+}
+
+/// Percentile of NaN-free values, reordering them in place
+fn select_percentile(values: &mut [f32], p: f64) -> Option<f32> {
+    if values.is_empty() || p.is_nan() {
+        return None;
+    }
+
+    let rank = p.clamp(0.0, 100.0) / 100.0 * (values.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let frac = rank - lo as f64;
+
+    let (_, lower, upper) = values.select_nth_unstable_by(lo, f32::total_cmp);
+    let lower = *lower;
+    if frac == 0.0 {
+        return Some(lower);
+    }
+
+    // The next rank is the smallest value above the selected one
+    let next = upper.iter().copied().min_by(f32::total_cmp)?;
+    Some((lower as f64 + frac * (next as f64 - lower as f64)) as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_2x2_well_conditioned() {
+        let x = solve_2x2([[3.0, 1.0], [-2.0, 4.0]], [5.0, 6.0]).unwrap();
+        assert!((3.0 * x[0] + x[1] - 5.0).abs() < 1e-12);
+        assert!((-2.0 * x[0] + 4.0 * x[1] - 6.0).abs() < 1e-12);
+
+        // Scale doesn't affect the singularity test
+        let x = solve_2x2([[3e-9, 1e-9], [-2e-9, 4e-9]], [5e-9, 6e-9]).unwrap();
+        assert!((x[0] - 1.0).abs() < 1e-9);
+        assert!((x[1] - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_solve_2x2_singular() {
+        let result = solve_2x2([[1.0, 2.0], [2.0, 4.0]], [1.0, 1.0]);
+        assert!(matches!(result, Err(NumericalError::Singular)));
+
+        let result = solve_2x2([[0.0, 0.0], [0.0, 0.0]], [1.0, 1.0]);
+        assert!(matches!(result, Err(NumericalError::Singular)));
+    }
+
+    #[test]
+    fn test_solve_2x2_near_singular() {
+        let result = solve_2x2([[1.0, 1.0], [1.0, 1.0 + 1e-14]], [1.0, 2.0]);
+        assert!(matches!(result, Err(NumericalError::Singular)));
+
+        // Ill-conditioned but above the threshold still solves
+        let x = solve_2x2([[1.0, 1.0], [1.0, 1.0 + 1e-8]], [2.0, 2.0 + 1e-8]).unwrap();
+        assert!((x[0] - 1.0).abs() < 1e-6);
+        assert!((x[1] - 1.0).abs() < 1e-6);
+
+        let result = solve_2x2([[f64::NAN, 0.0], [0.0, 1.0]], [1.0, 1.0]);
+        assert!(matches!(result, Err(NumericalError::NonFinite)));
+    }
+
+    #[test]
+    fn test_percentile_median() {
+        let values = [7.0, 1.0, 3.0, 9.0, 5.0];
+        assert_eq!(percentile(&values, 50.0), Some(5.0));
+
+        // Even count interpolates between the middle pair
+        let values = [4.0, 1.0, 3.0, 2.0];
+        assert_eq!(percentile(&values, 50.0), Some(2.5));
+        assert_eq!(percentile(&values, 25.0), Some(1.75));
+    }
+
+    #[test]
+    fn test_percentile_extremes_and_nan() {
+        let values = [3.5, f32::NAN, -2.0, 8.25, 0.0, f32::NAN];
+        assert_eq!(percentile(&values, 0.0), Some(-2.0));
+        assert_eq!(percentile(&values, 100.0), Some(8.25));
+        assert_eq!(percentile(&values, 150.0), Some(8.25));
+        assert_eq!(percentile(&values, f64::NAN), None);
+
+        assert_eq!(percentile(&[], 50.0), None);
+        assert_eq!(percentile(&[f32::NAN, f32::NAN], 50.0), None);
+    }
+
+    #[test]
+    fn test_percentile_array() {
+        let grid = ndarray::Array2::from_shape_fn((10, 10), |(r, c)| (r * 10 + c) as f32);
+        assert_eq!(percentile_array(&grid, 0.0), Some(0.0));
+        assert_eq!(percentile_array(&grid, 100.0), Some(99.0));
+        assert_eq!(percentile_array(&grid.view(), 50.0), Some(49.5));
+    }
+}
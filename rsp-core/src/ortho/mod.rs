@@ -0,0 +1,7 @@
+//! Orthorectification of sensor images onto map grids
+
+mod rectify;
+
+#[cfg(feature = "rayon")]
+pub use rectify::orthorectify_parallel;
+pub use rectify::{orthorectify, orthorectify_with_progress};
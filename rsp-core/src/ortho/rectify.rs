@@ -0,0 +1,255 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ndarray::Array2;
+
+use crate::coordinate::{GridDem, LlaCoord};
+use crate::error::Result;
+use crate::sensor::RpcModel;
+use crate::stereo::DsmGrid;
+use crate::warp::Resampler;
+
+/// Output rows per tile handed to a worker thread
+const TILE_ROWS: usize = 64;
+
+/// Orthorectify a sensor image onto a north-up geographic grid
+///
+/// Each output cell center is given a height from the DEM, projected into the
+/// image with the RPC model and interpolated with `resampler`. Cells with no DEM height,
+/// a failed projection or a footprint outside the image are NaN.
+pub fn orthorectify(
+    image: &Array2<f32>,
+    rpc: &RpcModel,
+    dem: &GridDem,
+    grid: DsmGrid,
+    resampler: &dyn Resampler,
+) -> Result<Array2<f32>> {
+    grid.validate()?;
+
+    Ok(Array2::from_shape_fn((grid.height, grid.width), |(row, col)| {
+        ortho_cell(image, rpc, dem, &grid, resampler, row, col)
+    }))
+}
+
+/// Parallel version of [`orthorectify`]
+///
+/// The output grid is split into bands of rows that are filled on the rayon
+/// thread pool, each worker writing only to its own band. Every cell is
+/// computed exactly as in the serial path, so the result is bit-identical.
+#[cfg(feature = "rayon")]
+pub fn orthorectify_parallel(
+    image: &Array2<f32>,
+    rpc: &RpcModel,
+    dem: &GridDem,
+    grid: DsmGrid,
+    resampler: &dyn Resampler,
+) -> Result<Array2<f32>> {
+    orthorectify_with_progress(image, rpc, dem, grid, resampler, |_| {})
+}
+
+/// [`orthorectify`] with progress reporting
+///
+/// The output is processed in bands of `TILE_ROWS` rows, across the rayon
+/// thread pool when the `rayon` feature is enabled. `on_progress` receives
+/// the completed fraction once per band, reaching exactly 1.0 with the last
+/// one. It may be called from worker threads, and with parallel bands
+/// finishing out of order the calls aren't serialized.
+pub fn orthorectify_with_progress<F>(
+    image: &Array2<f32>,
+    rpc: &RpcModel,
+    dem: &GridDem,
+    grid: DsmGrid,
+    resampler: &dyn Resampler,
+    on_progress: F,
+) -> Result<Array2<f32>>
+where
+    F: Fn(f32) + Send + Sync,
+{
+    grid.validate()?;
+
+    let tiles = grid.height.div_ceil(TILE_ROWS);
+    let done = AtomicUsize::new(0);
+    let fill = |(tile, band): (usize, &mut [f32])| {
+        let first_row = tile * TILE_ROWS;
+        for (i, value) in band.iter_mut().enumerate() {
+            let row = first_row + i / grid.width;
+            let col = i % grid.width;
+            *value = ortho_cell(image, rpc, dem, &grid, resampler, row, col);
+        }
+
+        let finished = done.fetch_add(1, Ordering::Relaxed) + 1;
+        on_progress(finished as f32 / tiles as f32);
+    };
+
+    let mut out = vec![0.0f32; grid.height * grid.width];
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        out.par_chunks_mut(TILE_ROWS * grid.width).enumerate().for_each(fill);
+    }
+    #[cfg(not(feature = "rayon"))]
+    out.chunks_mut(TILE_ROWS * grid.width).enumerate().for_each(fill);
+
+    Ok(Array2::from_shape_vec((grid.height, grid.width), out)
+        .expect("buffer length matches grid size"))
+}
+
+/// Orthorectified value of one output cell
+fn ortho_cell(
+    image: &Array2<f32>,
+    rpc: &RpcModel,
+    dem: &GridDem,
+    grid: &DsmGrid,
+    resampler: &dyn Resampler,
+    row: usize,
+    col: usize,
+) -> f32 {
+    let gt = &grid.geotransform;
+    let lon = gt[0] + (col as f64 + 0.5) * gt[1];
+    let lat = gt[3] + (row as f64 + 0.5) * gt[5];
+
+    let Some(alt) = dem.height_at(lat, lon) else {
+        return f32::NAN;
+    };
+    let Ok((line, sample)) = rpc.lla_to_image(&LlaCoord { lat, lon, alt }) else {
+        return f32::NAN;
+    };
+
+    resampler.sample(&image.view(), sample, line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::RspError;
+    use crate::sensor::linear_rpc;
+    use crate::warp::{BilinearResampler, NearestResampler};
+
+    /// Linear RPC: line southward, sample eastward plus height parallax
+    fn synthetic_rpc() -> RpcModel {
+        RpcModel::new(linear_rpc(0.01, 100.0, 100.0, 0.1))
+    }
+
+    /// Sloped DEM covering the scene
+    fn synthetic_dem() -> GridDem {
+        let heights =
+            Array2::from_shape_fn((60, 60), |(r, c)| (50.0 + 3.0 * r as f64 + c as f64) as f32);
+        GridDem::new([-77.012, 0.0004, 0.0, 39.012, 0.0, -0.0004], heights).unwrap()
+    }
+
+    /// Image whose value is linear in (line, sample), so bilinear sampling is exact
+    fn ramp_image() -> Array2<f32> {
+        Array2::from_shape_fn((200, 200), |(r, c)| (0.5 * r as f64 + 0.25 * c as f64) as f32)
+    }
+
+    fn grid() -> DsmGrid {
+        DsmGrid::new([-77.01, 0.0001, 0.0, 39.01, 0.0, -0.0001], 150, 170)
+    }
+
+    #[test]
+    fn test_orthorectify_samples_projection() {
+        let rpc = synthetic_rpc();
+        let dem = synthetic_dem();
+        let grid = grid();
+
+        let ortho = orthorectify(&ramp_image(), &rpc, &dem, grid, &BilinearResampler).unwrap();
+
+        let mut checked = 0;
+        for ((row, col), &value) in ortho.indexed_iter() {
+            if !value.is_finite() {
+                continue;
+            }
+            let lon = grid.geotransform[0] + (col as f64 + 0.5) * grid.geotransform[1];
+            let lat = grid.geotransform[3] + (row as f64 + 0.5) * grid.geotransform[5];
+            let alt = dem.height_at(lat, lon).unwrap();
+            let (line, sample) = rpc.lla_to_image(&LlaCoord { lat, lon, alt }).unwrap();
+
+            assert!((value as f64 - (0.5 * line + 0.25 * sample)).abs() < 1e-3);
+            checked += 1;
+        }
+        assert!(checked > 10_000);
+    }
+
+    #[test]
+    fn test_orthorectify_nearest_resampler() {
+        let rpc = synthetic_rpc();
+        let dem = synthetic_dem();
+        let grid = DsmGrid::new([-77.005, 0.0001, 0.0, 39.005, 0.0, -0.0001], 20, 20);
+        let image = Array2::from_shape_fn((200, 200), |(r, c)| ((r * 31 + c * 17) % 97) as f32);
+
+        let ortho = orthorectify(&image, &rpc, &dem, grid, &NearestResampler).unwrap();
+        for ((row, col), &value) in ortho.indexed_iter() {
+            let lon = grid.geotransform[0] + (col as f64 + 0.5) * grid.geotransform[1];
+            let lat = grid.geotransform[3] + (row as f64 + 0.5) * grid.geotransform[5];
+            let alt = dem.height_at(lat, lon).unwrap();
+            let (line, sample) = rpc.lla_to_image(&LlaCoord { lat, lon, alt }).unwrap();
+            assert_eq!(value, image[[line.round() as usize, sample.round() as usize]]);
+        }
+    }
+
+    #[test]
+    fn test_orthorectify_outside_dem_is_nan() {
+        let rpc = synthetic_rpc();
+        let dem = synthetic_dem();
+        let grid = DsmGrid::new([-76.9, 0.0001, 0.0, 39.01, 0.0, -0.0001], 10, 10);
+
+        let ortho = orthorectify(&ramp_image(), &rpc, &dem, grid, &BilinearResampler).unwrap();
+        assert!(ortho.iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_orthorectify_rotated_grid_rejected() {
+        let grid = DsmGrid::new([0.0, 1.0, 0.5, 0.0, 0.0, -1.0], 4, 4);
+        let (rpc, dem) = (synthetic_rpc(), synthetic_dem());
+        let result = orthorectify(&ramp_image(), &rpc, &dem, grid, &BilinearResampler);
+        assert!(matches!(result, Err(RspError::InvalidInput(_))));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_matches_serial() {
+        let rpc = synthetic_rpc();
+        let dem = synthetic_dem();
+        let image = Array2::from_shape_fn((200, 200), |(r, c)| ((r * 31 + c * 17) % 97) as f32);
+
+        // Height not a multiple of the tile size, so the last band is partial
+        let grid = grid();
+        let serial = orthorectify(&image, &rpc, &dem, grid, &BilinearResampler).unwrap();
+        let parallel =
+            orthorectify_parallel(&image, &rpc, &dem, grid, &BilinearResampler).unwrap();
+
+        assert_eq!(serial.dim(), parallel.dim());
+        for (a, b) in serial.iter().zip(parallel.iter()) {
+            assert_eq!(a.to_bits(), b.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_orthorectify_progress() {
+        let rpc = synthetic_rpc();
+        let dem = synthetic_dem();
+        let image = ramp_image();
+        let grid = grid();
+
+        let calls = AtomicUsize::new(0);
+        let last = std::sync::Mutex::new(0.0f32);
+        let on_progress = |fraction: f32| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            let mut last = last.lock().unwrap();
+            assert!(fraction > 0.0 && fraction <= 1.0);
+            *last = last.max(fraction);
+        };
+        let ortho =
+            orthorectify_with_progress(&image, &rpc, &dem, grid, &BilinearResampler, on_progress)
+                .unwrap();
+
+        // One call per band of rows: 170 rows in bands of 64
+        assert_eq!(calls.into_inner(), 3);
+        assert_eq!(last.into_inner().unwrap(), 1.0);
+
+        let serial = orthorectify(&image, &rpc, &dem, grid, &BilinearResampler).unwrap();
+        for (a, b) in serial.iter().zip(ortho.iter()) {
+            assert_eq!(a.to_bits(), b.to_bits());
+        }
+    }
+}
@@ -0,0 +1,347 @@
+//! Multi-image RPC space intersection
+
+use nalgebra::{Matrix2x3, Matrix3, Vector2, Vector3};
+
+use crate::coordinate::{ecef_to_lla, lla_to_ecef, EcefCoord, LlaCoord};
+use crate::error::{ProjectionError, Result, RspError};
+use crate::math::RobustLoss;
+use crate::sensor::RpcModel;
+
+/// Maximum Gauss-Newton iterations for space intersection
+const MAX_ITERATIONS: usize = 20;
+
+/// Reweighting converges linearly, so robust refinement gets a longer budget
+const ROBUST_MAX_ITERATIONS: usize = 100;
+
+/// ECEF step (meters) for the numeric pixel-to-ground Jacobian
+const COVARIANCE_STEP: f64 = 0.1;
+
+/// Height span (meters) used to trace each viewing ray's direction
+const RAY_HEIGHT_SPAN: f64 = 1000.0;
+
+/// Find the ground point whose projection matches a pixel in each of two images
+///
+/// Pixels are `(line, sample)`. Minimizes the combined reprojection residual
+/// over (lat, lon, height) with Gauss-Newton, starting from the first image's
+/// ray at `h_guess`.
+pub fn rpc_space_intersection(
+    rpc_a: &RpcModel,
+    pixel_a: (f64, f64),
+    rpc_b: &RpcModel,
+    pixel_b: (f64, f64),
+    h_guess: f64,
+) -> Result<LlaCoord> {
+    rpc_multi_intersection(&[(rpc_a, pixel_a), (rpc_b, pixel_b)], h_guess, RobustLoss::None)
+}
+
+/// Stereo intersection with the covariance of the solved point
+///
+/// Intersects the two rays with [`rpc_space_intersection`] and returns the
+/// ECEF point with its 3x3 ECEF covariance (m²). `var_a` and `var_b` are the
+/// pixel measurement variances (pixels², applied to both line and sample) of
+/// each image. The covariance is `(Jᵀ W J)⁻¹`, where `J` is the 4x3 design
+/// matrix of pixel coordinates with respect to ECEF position (central
+/// differences) and `W = diag(1 / var)`.
+pub fn rpc_stereo_intersect_with_cov(
+    rpc_a: &RpcModel,
+    pixel_a: (f64, f64),
+    var_a: f64,
+    rpc_b: &RpcModel,
+    pixel_b: (f64, f64),
+    var_b: f64,
+    h_guess: f64,
+) -> Result<(EcefCoord, Matrix3<f64>)> {
+    if !(var_a > 0.0 && var_b > 0.0 && var_a.is_finite() && var_b.is_finite()) {
+        return Err(RspError::InvalidInput(format!(
+            "Pixel variances must be positive and finite, got {} and {}",
+            var_a, var_b
+        )));
+    }
+
+    let lla = rpc_space_intersection(rpc_a, pixel_a, rpc_b, pixel_b, h_guess)?;
+    let point = lla_to_ecef(&lla)?;
+
+    let project = |rpc: &RpcModel, ecef: EcefCoord| -> Result<Vector2<f64>> {
+        let (line, sample) = rpc.lla_to_image(&ecef_to_lla(&ecef)?)?;
+        Ok(Vector2::new(line, sample))
+    };
+
+    let mut normal = Matrix3::zeros();
+    for (rpc, var) in [(rpc_a, var_a), (rpc_b, var_b)] {
+        let mut jacobian = Matrix2x3::zeros();
+        for k in 0..3 {
+            let mut offset = Vector3::zeros();
+            offset[k] = COVARIANCE_STEP;
+            let forward = project(rpc, point + offset)?;
+            let backward = project(rpc, point - offset)?;
+            jacobian.set_column(k, &((forward - backward) / (2.0 * COVARIANCE_STEP)));
+        }
+        normal += jacobian.transpose() * jacobian / var;
+    }
+
+    let Some(covariance) = normal.try_inverse() else {
+        return Err(RspError::Numerical(
+            "Intersection geometry is degenerate, covariance is undefined".to_string(),
+        ));
+    };
+
+    // Symmetrize away round-off from the inversion
+    Ok((point, 0.5 * (covariance + covariance.transpose())))
+}
+
+/// Space intersection of one ground point seen in any number of images
+///
+/// Each observation is an RPC and the `(line, sample)` pixel where the point
+/// appears. Gauss-Newton over (lat, lon, height) starts from the first
+/// observation's ray at `h_guess` and converges to the least-squares point.
+/// With a robust `loss`, iteratively reweighted Gauss-Newton then continues
+/// from there, weighting each observation by `loss` applied to its current
+/// pixel residual. Needs at least two observations.
+pub fn rpc_multi_intersection(
+    observations: &[(&RpcModel, (f64, f64))],
+    h_guess: f64,
+    loss: RobustLoss,
+) -> Result<LlaCoord> {
+    if observations.len() < 2 {
+        return Err(RspError::InvalidInput(format!(
+            "Space intersection needs at least 2 observations, got {}",
+            observations.len()
+        )));
+    }
+
+    let (first_rpc, first_pixel) = observations[0];
+    let start = first_rpc.image_to_lla(first_pixel.0, first_pixel.1, h_guess)?;
+    let x = Vector3::new(start.lat, start.lon, start.alt);
+
+    let mut x = gauss_newton(observations, x, RobustLoss::None, MAX_ITERATIONS)?;
+    if loss != RobustLoss::None {
+        x = gauss_newton(observations, x, loss, ROBUST_MAX_ITERATIONS)?;
+    }
+
+    Ok(LlaCoord { lat: x[0], lon: x[1], alt: x[2] })
+}
+
+/// Weighted Gauss-Newton on (lat, lon, height) until the step is negligible
+fn gauss_newton(
+    observations: &[(&RpcModel, (f64, f64))],
+    mut x: Vector3<f64>,
+    loss: RobustLoss,
+    max_iterations: usize,
+) -> Result<Vector3<f64>> {
+    // Finite-difference steps for (lat, lon, height)
+    let steps = [1e-7, 1e-7, 1e-3];
+
+    for _ in 0..max_iterations {
+        let mut jtj = Matrix3::zeros();
+        let mut jtr = Vector3::zeros();
+
+        for (rpc, pixel) in observations.iter() {
+            let lla = LlaCoord { lat: x[0], lon: x[1], alt: x[2] };
+            let (line, samp) = rpc.lla_to_image(&lla)?;
+            let residual = [pixel.0 - line, pixel.1 - samp];
+            let weight = loss.weight(residual[0].hypot(residual[1]));
+
+            let mut jacobian = [[0.0; 3]; 2];
+            for (k, step) in steps.iter().enumerate() {
+                let mut perturbed = x;
+                perturbed[k] += step;
+                let lla = LlaCoord {
+                    lat: perturbed[0],
+                    lon: perturbed[1],
+                    alt: perturbed[2],
+                };
+                let (line_p, samp_p) = rpc.lla_to_image(&lla)?;
+                jacobian[0][k] = (line_p - line) / step;
+                jacobian[1][k] = (samp_p - samp) / step;
+            }
+
+            for (row, r) in jacobian.iter().zip(residual) {
+                let j = Vector3::new(row[0], row[1], row[2]);
+                jtj += weight * j * j.transpose();
+                jtr += weight * j * r;
+            }
+        }
+
+        let Some(jtj_inv) = jtj.try_inverse() else {
+            return Err(ProjectionError::NoConvergence(max_iterations).into());
+        };
+        let dx = jtj_inv * jtr;
+        x += dx;
+
+        if dx[0].abs() < 1e-10 && dx[1].abs() < 1e-10 && dx[2].abs() < 1e-4 {
+            return Ok(x);
+        }
+    }
+
+    Err(ProjectionError::NoConvergence(max_iterations).into())
+}
+
+/// Convergence angle (degrees) between two RPC viewing rays
+///
+/// The rays through `pixel_a` and `pixel_b` (`(line, sample)`) are
+/// intersected with [`rpc_space_intersection`] starting at `height`, and
+/// each ray's direction is traced by projecting its pixel to ground
+/// `RAY_HEIGHT_SPAN` meters above and below the intersection. Returns the
+/// angle between the two directions in [0, 180].
+pub fn rpc_convergence_angle(
+    rpc_a: &RpcModel,
+    pixel_a: (f64, f64),
+    rpc_b: &RpcModel,
+    pixel_b: (f64, f64),
+    height: f64,
+) -> Result<f64> {
+    let point = rpc_space_intersection(rpc_a, pixel_a, rpc_b, pixel_b, height)?;
+
+    let ray = |rpc: &RpcModel, (line, sample): (f64, f64)| -> Result<Vector3<f64>> {
+        let top = rpc.image_to_ground(line, sample, point.alt + RAY_HEIGHT_SPAN)?;
+        let bottom = rpc.image_to_ground(line, sample, point.alt - RAY_HEIGHT_SPAN)?;
+        Ok(top - bottom)
+    };
+    let (a, b) = (ray(rpc_a, pixel_a)?, ray(rpc_b, pixel_b)?);
+
+    Ok(a.cross(&b).norm().atan2(a.dot(&b)).to_degrees())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sensor::linear_rpc;
+    use std::f64::consts::PI;
+
+    /// Linear RPC with stereo parallax, mostly in sample and a little in line
+    fn parallax_rpc(parallax: f64) -> RpcModel {
+        let mut coeffs = linear_rpc(0.05, 5000.0, 5000.0, parallax);
+        coeffs.line_num_coeff[3] = 0.05 * parallax;
+        RpcModel::new(coeffs)
+    }
+
+    #[test]
+    fn test_space_intersection_recovers_point() {
+        let rpc_a = parallax_rpc(0.3);
+        let rpc_b = parallax_rpc(-0.25);
+
+        let truth = LlaCoord { lat: 39.012, lon: -76.991, alt: 432.0 };
+        let pixel_a = rpc_a.lla_to_image(&truth).unwrap();
+        let pixel_b = rpc_b.lla_to_image(&truth).unwrap();
+
+        let solved = rpc_space_intersection(&rpc_a, pixel_a, &rpc_b, pixel_b, 0.0).unwrap();
+
+        let error = lla_to_ecef(&solved).unwrap() - lla_to_ecef(&truth).unwrap();
+        assert!(error.norm() < 1.0);
+    }
+
+    #[test]
+    fn test_space_intersection_no_parallax() {
+        // Identical views constrain nothing in height
+        let rpc = parallax_rpc(0.0);
+        let pixel = (5000.0, 5000.0);
+
+        let result = rpc_space_intersection(&rpc, pixel, &rpc, pixel, 100.0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_stereo_intersect_covariance() {
+        let rpc_a = parallax_rpc(0.3);
+        let rpc_b = parallax_rpc(-0.25);
+
+        let truth = LlaCoord { lat: 39.012, lon: -76.991, alt: 432.0 };
+        let pixel_a = rpc_a.lla_to_image(&truth).unwrap();
+        let pixel_b = rpc_b.lla_to_image(&truth).unwrap();
+
+        let intersect = |var: f64| {
+            rpc_stereo_intersect_with_cov(&rpc_a, pixel_a, var, &rpc_b, pixel_b, var, 0.0)
+                .unwrap()
+        };
+        let (point, cov) = intersect(0.25);
+        let (_, cov_wide) = intersect(4.0);
+
+        assert!((point - lla_to_ecef(&truth).unwrap()).norm() < 1.0);
+        assert!((cov - cov.transpose()).abs().max() < 1e-12);
+        assert!(cov.cholesky().is_some());
+
+        // Covariance scales linearly with the pixel variance
+        assert!(cov_wide.trace() > cov.trace());
+        assert!((cov_wide.trace() / cov.trace() - 16.0).abs() < 1e-6);
+
+        let bad = rpc_stereo_intersect_with_cov(&rpc_a, pixel_a, 0.0, &rpc_b, pixel_b, 1.0, 0.0);
+        assert!(matches!(bad, Err(RspError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_convergence_angle_known_geometry() {
+        // Parallax only in sample, so each ray leans east-west by a fixed slope
+        let tilted = |parallax: f64| {
+            let mut coeffs = parallax_rpc(parallax).coefficients().clone();
+            coeffs.height_scale = 5000.0;
+            coeffs.line_num_coeff[3] = 0.0;
+            RpcModel::new(coeffs)
+        };
+        let (rpc_a, rpc_b) = (tilted(0.3), tilted(-0.25));
+
+        let truth = LlaCoord { lat: 39.01, lon: -76.99, alt: 250.0 };
+        let pixel_a = rpc_a.lla_to_image(&truth).unwrap();
+        let pixel_b = rpc_b.lla_to_image(&truth).unwrap();
+        let angle = rpc_convergence_angle(&rpc_a, pixel_a, &rpc_b, pixel_b, 0.0).unwrap();
+
+        // East offset per meter of height: -k * lon_scale / height_scale degrees
+        let (a, e2) = (6_378_137.0, 6.694_379_990_14e-3);
+        let sin_lat = truth.lat.to_radians().sin();
+        let n = a / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+        let meters_per_deg = (n + truth.alt) * truth.lat.to_radians().cos() * PI / 180.0;
+        let slope = |k: f64| -k * 0.05 / 5000.0 * meters_per_deg;
+        let expected = (slope(0.3).atan() - slope(-0.25).atan()).abs().to_degrees();
+
+        assert!((angle - expected).abs() < 0.01, "{} vs {}", angle, expected);
+        assert!(angle > 20.0 && angle < 30.0);
+    }
+
+    #[test]
+    fn test_convergence_angle_no_parallax() {
+        let rpc = parallax_rpc(0.0);
+        let result = rpc_convergence_angle(&rpc, (5000.0, 5000.0), &rpc, (5000.0, 5000.0), 100.0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_multi_intersection_downweights_outlier() {
+        let rpcs = [parallax_rpc(0.3), parallax_rpc(-0.25), parallax_rpc(0.1), parallax_rpc(-0.4)];
+        let truth = LlaCoord { lat: 38.991, lon: -77.013, alt: 275.0 };
+
+        // The last observation is mismatched by 150 pixels in sample
+        let observations: Vec<_> = rpcs
+            .iter()
+            .enumerate()
+            .map(|(i, rpc)| {
+                let (line, samp) = rpc.lla_to_image(&truth).unwrap();
+                let samp = if i == 3 { samp + 150.0 } else { samp };
+                (rpc, (line, samp))
+            })
+            .collect();
+        let truth_ecef = lla_to_ecef(&truth).unwrap();
+        let error = |lla: LlaCoord| (lla_to_ecef(&lla).unwrap() - truth_ecef).norm();
+
+        let plain = rpc_multi_intersection(&observations, 0.0, RobustLoss::None).unwrap();
+        let huber = rpc_multi_intersection(&observations, 0.0, RobustLoss::Huber(1.0)).unwrap();
+        let tukey = rpc_multi_intersection(&observations, 0.0, RobustLoss::Tukey(60.0)).unwrap();
+
+        assert!(error(plain) > 10.0);
+        // Huber keeps a bounded pull from the outlier, Tukey rejects it
+        assert!(error(huber) < 0.2 * error(plain));
+        assert!(error(tukey) < 1e-3);
+    }
+
+    #[test]
+    fn test_multi_intersection_inputs_and_weights() {
+        let rpc = parallax_rpc(0.3);
+        let result = rpc_multi_intersection(&[(&rpc, (5000.0, 5000.0))], 0.0, RobustLoss::None);
+        assert!(matches!(result, Err(RspError::InvalidInput(_))));
+
+        assert_eq!(RobustLoss::None.weight(100.0), 1.0);
+        assert_eq!(RobustLoss::Huber(2.0).weight(1.0), 1.0);
+        assert_eq!(RobustLoss::Huber(2.0).weight(-8.0), 0.25);
+        assert_eq!(RobustLoss::Tukey(4.0).weight(0.0), 1.0);
+        assert_eq!(RobustLoss::Tukey(4.0).weight(2.0), 0.5625);
+        assert_eq!(RobustLoss::Tukey(4.0).weight(5.0), 0.0);
+    }
+}
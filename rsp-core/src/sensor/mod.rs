@@ -1,5 +1,113 @@
 //! Sensor models (RPC, pushbroom, etc.)
 
+pub mod intersection;
+pub mod pushbroom;
 pub mod rpc;
+pub mod rpc_fit;
 
-pub use rpc::{RpcCoefficients, RpcModel};
+pub use intersection::{
+    rpc_convergence_angle, rpc_multi_intersection, rpc_space_intersection,
+    rpc_stereo_intersect_with_cov,
+};
+pub use pushbroom::{LinearPushbroomModel, PushbroomSupport};
+pub use rpc::{ImageToGroundOptions, RpcCoefficients, RpcModel};
+pub use rpc_fit::{fit_rpc, RpcNormalization};
+
+pub use crate::math::RobustLoss;
+
+use crate::coordinate::EcefCoord;
+use crate::error::Result;
+
+/// Common ground/image projection interface of sensor models
+///
+/// Lets downstream code stay generic over the sensor type (e.g. taking
+/// `&dyn SensorModel`).
+pub trait SensorModel {
+    /// Project a ground point (ECEF) to image coordinates (line, sample)
+    fn ground_to_image(&self, ecef: &EcefCoord) -> Result<(f64, f64)>;
+
+    /// Project image coordinates to a ground point at an ellipsoidal height (ECEF)
+    fn image_to_ground(&self, line: f64, sample: f64, height: f64) -> Result<EcefCoord>;
+}
+
+impl SensorModel for RpcModel {
+    fn ground_to_image(&self, ecef: &EcefCoord) -> Result<(f64, f64)> {
+        RpcModel::ground_to_image(self, ecef)
+    }
+
+    fn image_to_ground(&self, line: f64, sample: f64, height: f64) -> Result<EcefCoord> {
+        RpcModel::image_to_ground(self, line, sample, height)
+    }
+}
+
+impl SensorModel for LinearPushbroomModel {
+    fn ground_to_image(&self, ecef: &EcefCoord) -> Result<(f64, f64)> {
+        LinearPushbroomModel::ground_to_image(self, ecef)
+    }
+
+    fn image_to_ground(&self, line: f64, sample: f64, height: f64) -> Result<EcefCoord> {
+        LinearPushbroomModel::image_to_ground(self, line, sample, height)
+    }
+}
+
+/// Linear RPC test fixture around 39 N, 77 W and 100 m height
+///
+/// Latitude and longitude are normalized by `ground_scale` degrees, line and
+/// sample by `pixel_off` and `pixel_scale`. Lines increase southward and
+/// samples eastward, plus `parallax` times the normalized height (stereo
+/// parallax).
+#[cfg(test)]
+pub(crate) fn linear_rpc(
+    ground_scale: f64,
+    pixel_off: f64,
+    pixel_scale: f64,
+    parallax: f64,
+) -> RpcCoefficients {
+    let mut coeffs = RpcCoefficients {
+        lat_off: 39.0,
+        lat_scale: ground_scale,
+        lon_off: -77.0,
+        lon_scale: ground_scale,
+        height_off: 100.0,
+        height_scale: 500.0,
+        line_off: pixel_off,
+        line_scale: pixel_scale,
+        samp_off: pixel_off,
+        samp_scale: pixel_scale,
+        ..Default::default()
+    };
+
+    coeffs.line_num_coeff[1] = -1.0;
+    coeffs.line_den_coeff[0] = 1.0;
+    coeffs.samp_num_coeff[2] = 1.0;
+    coeffs.samp_num_coeff[3] = parallax;
+    coeffs.samp_den_coeff[0] = 1.0;
+    coeffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinate::{lla_to_ecef, LlaCoord};
+
+    /// Round trip through the trait object only
+    fn roundtrip(sensor: &dyn SensorModel, line: f64, sample: f64, height: f64) -> (f64, f64) {
+        let ground = sensor.image_to_ground(line, sample, height).unwrap();
+        sensor.ground_to_image(&ground).unwrap()
+    }
+
+    #[test]
+    fn test_rpc_as_sensor_model() {
+        let rpc = RpcModel::new(linear_rpc(0.05, 5000.0, 5000.0, 0.05));
+        let sensor: &dyn SensorModel = &rpc;
+
+        let (line, sample) = roundtrip(sensor, 1234.5, 6789.25, 300.0);
+        assert!((line - 1234.5).abs() < 1e-4);
+        assert!((sample - 6789.25).abs() < 1e-4);
+
+        // Trait and inherent methods agree
+        let ground = lla_to_ecef(&LlaCoord { lat: 39.01, lon: -77.02, alt: 50.0 }).unwrap();
+        let via_trait = sensor.ground_to_image(&ground).unwrap();
+        assert_eq!(via_trait, rpc.ground_to_image(&ground).unwrap());
+    }
+}
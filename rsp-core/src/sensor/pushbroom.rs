@@ -0,0 +1,260 @@
+//! Linear pushbroom sensor model
+
+use nalgebra::{UnitQuaternion, Vector3};
+
+use crate::coordinate::{ecef_to_lla, EcefCoord};
+use crate::error::{ProjectionError, Result, RspError};
+
+/// Maximum iterations for the line search and the height intersection
+const MAX_ITERATIONS: usize = 30;
+
+/// Exterior orientation of the sensor at one image line
+#[derive(Debug, Clone, Copy)]
+pub struct PushbroomSupport {
+    /// Image line (time) this orientation applies to
+    pub line: f64,
+    /// Perspective center in ECEF (meters)
+    pub position: EcefCoord,
+    /// Rotation from ECEF into the sensor frame
+    pub attitude: UnitQuaternion<f64>,
+}
+
+/// Linear pushbroom sensor model
+///
+/// Each image line is a 1-D perspective projection taken at its own time.
+/// In the sensor frame x points along-track, y across-track and z along the
+/// boresight; a ground point is imaged on the line where it falls in the
+/// sensor's y-z plane, at sample `principal_sample + focal_length * y / z`.
+///
+/// Per-line orientation is interpolated between support points: linearly for
+/// position and by slerp for attitude (extrapolated beyond the first/last
+/// support).
+#[derive(Debug, Clone)]
+pub struct LinearPushbroomModel {
+    supports: Vec<PushbroomSupport>,
+    focal_length: f64,
+    principal_sample: f64,
+}
+
+impl LinearPushbroomModel {
+    /// Create a model from support points and across-track interior orientation
+    ///
+    /// Needs at least two supports with strictly increasing lines and a
+    /// positive focal length (pixels).
+    pub fn new(
+        supports: Vec<PushbroomSupport>,
+        focal_length: f64,
+        principal_sample: f64,
+    ) -> Result<Self> {
+        if supports.len() < 2 {
+            return Err(RspError::InvalidInput(
+                "Pushbroom model needs at least two support points".to_string(),
+            ));
+        }
+        if supports.windows(2).any(|w| w[1].line <= w[0].line) {
+            return Err(RspError::InvalidInput(
+                "Pushbroom support lines must be strictly increasing".to_string(),
+            ));
+        }
+        if focal_length <= 0.0 {
+            return Err(RspError::InvalidInput(
+                "Pushbroom focal length must be positive".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            supports,
+            focal_length,
+            principal_sample,
+        })
+    }
+
+    /// Interpolated perspective center and attitude at an image line
+    pub fn orientation(&self, line: f64) -> (EcefCoord, UnitQuaternion<f64>) {
+        // Segment containing the line, clamped to the first/last segment
+        let idx = self
+            .supports
+            .partition_point(|s| s.line <= line)
+            .clamp(1, self.supports.len() - 1);
+        let a = &self.supports[idx - 1];
+        let b = &self.supports[idx];
+
+        let t = (line - a.line) / (b.line - a.line);
+        let position = a.position + (b.position - a.position) * t;
+        let attitude = a.attitude.slerp(&b.attitude, t);
+
+        (position, attitude)
+    }
+
+    /// Project a ground point (ECEF) to image coordinates (line, sample)
+    ///
+    /// Solves for the line whose scan plane contains the point with a secant
+    /// iteration on the along-track image coordinate.
+    pub fn ground_to_image(&self, ground_ecef: &EcefCoord) -> Result<(f64, f64)> {
+        let along_track = |line: f64| -> Result<f64> {
+            let p = self.to_sensor(line, ground_ecef)?;
+            Ok(p.x / p.z)
+        };
+
+        let first = self.supports[0].line;
+        let last = self.supports[self.supports.len() - 1].line;
+        let mut line_a = 0.5 * (first + last);
+        let mut line_b = line_a + 1.0;
+        let mut f_a = along_track(line_a)?;
+
+        for _ in 0..MAX_ITERATIONS {
+            let f_b = along_track(line_b)?;
+            let slope = (f_b - f_a) / (line_b - line_a);
+            if slope == 0.0 || !slope.is_finite() {
+                break;
+            }
+
+            let next = line_b - f_b / slope;
+            if (next - line_b).abs() < 1e-9 {
+                let p = self.to_sensor(next, ground_ecef)?;
+                let sample = self.principal_sample + self.focal_length * p.y / p.z;
+                return Ok((next, sample));
+            }
+
+            line_a = line_b;
+            f_a = f_b;
+            line_b = next;
+        }
+
+        Err(ProjectionError::NoConvergence(MAX_ITERATIONS).into())
+    }
+
+    /// Project image coordinates to a ground point at a given ellipsoidal height (ECEF)
+    ///
+    /// Casts the viewing ray of the pixel and searches along it for the
+    /// height with a secant iteration.
+    pub fn image_to_ground(&self, line: f64, sample: f64, height: f64) -> Result<EcefCoord> {
+        let (origin, attitude) = self.orientation(line);
+        let across = (sample - self.principal_sample) / self.focal_length;
+        let direction = (attitude.inverse() * Vector3::new(0.0, across, 1.0)).normalize();
+
+        let height_error = |s: f64| -> Result<f64> {
+            let lla = ecef_to_lla(&(origin + direction * s))?;
+            Ok(lla.alt - height)
+        };
+
+        // Initial range from a spherical approximation of the local vertical
+        let up = origin.normalize();
+        let cos_off_nadir = -direction.dot(&up);
+        if cos_off_nadir <= 0.0 {
+            return Err(ProjectionError::BehindCamera.into());
+        }
+        let mut s_a = height_error(0.0)? / cos_off_nadir;
+        let mut s_b = s_a + 1.0;
+        let mut e_a = height_error(s_a)?;
+
+        for _ in 0..MAX_ITERATIONS {
+            let e_b = height_error(s_b)?;
+            if e_b.abs() < 1e-4 {
+                return Ok(origin + direction * s_b);
+            }
+
+            let slope = (e_b - e_a) / (s_b - s_a);
+            if slope == 0.0 || !slope.is_finite() {
+                break;
+            }
+
+            s_a = s_b;
+            e_a = e_b;
+            s_b -= e_b / slope;
+        }
+
+        Err(ProjectionError::NoConvergence(MAX_ITERATIONS).into())
+    }
+
+    /// Ground point in the sensor frame of a given line
+    fn to_sensor(&self, line: f64, ground_ecef: &EcefCoord) -> Result<Vector3<f64>> {
+        let (position, attitude) = self.orientation(line);
+        let p = attitude * (ground_ecef - position);
+        if p.z <= 0.0 {
+            return Err(ProjectionError::BehindCamera.into());
+        }
+        Ok(p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinate::{lla_to_ecef, LlaCoord};
+    use nalgebra::{Matrix3, Rotation3};
+
+    /// Nadir-looking sensor flying north over (39, -77) at 500 km
+    fn synthetic_model() -> LinearPushbroomModel {
+        let center = lla_to_ecef(&LlaCoord { lat: 39.0, lon: -77.0, alt: 0.0 }).unwrap();
+        let up = center.normalize();
+        let north = (Vector3::z() - up * up.z).normalize();
+
+        // Sensor axes: x along-track (north), z down, y = z x x
+        let z = -up;
+        let x = north;
+        let y = z.cross(&x);
+        let base = Rotation3::from_matrix_unchecked(Matrix3::from_rows(&[
+            x.transpose(),
+            y.transpose(),
+            z.transpose(),
+        ]));
+
+        // 0.7 m of ground track per line, with a slow roll and pitch drift
+        let supports = [0.0, 5000.0, 10000.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &line)| {
+                let drift = Rotation3::from_euler_angles(1e-3 * i as f64, -5e-4 * i as f64, 0.0);
+                PushbroomSupport {
+                    line,
+                    position: center + up * 500_000.0 + north * 0.7 * (line - 5000.0),
+                    attitude: UnitQuaternion::from_rotation_matrix(&(drift * base)),
+                }
+            })
+            .collect();
+
+        LinearPushbroomModel::new(supports, 1_000_000.0, 5000.0).unwrap()
+    }
+
+    #[test]
+    fn test_pushbroom_ground_image_roundtrip() {
+        let model = synthetic_model();
+
+        for (dlat, dlon, alt) in [(0.0, 0.0, 0.0), (0.01, -0.02, 350.0), (-0.015, 0.01, -40.0)] {
+            let lla = LlaCoord { lat: 39.0 + dlat, lon: -77.0 + dlon, alt };
+            let ground = lla_to_ecef(&lla).unwrap();
+
+            let (line, sample) = model.ground_to_image(&ground).unwrap();
+            assert!((0.0..10000.0).contains(&line));
+
+            let back = model.image_to_ground(line, sample, alt).unwrap();
+            assert!((back - ground).norm() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_pushbroom_image_ground_roundtrip() {
+        let model = synthetic_model();
+
+        for (line, sample) in [(1234.5, 4000.0), (5000.0, 5000.0), (8765.0, 6100.25)] {
+            let ground = model.image_to_ground(line, sample, 120.0).unwrap();
+            let (l, s) = model.ground_to_image(&ground).unwrap();
+
+            assert!((l - line).abs() < 1e-4);
+            assert!((s - sample).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_pushbroom_invalid_supports() {
+        let support = PushbroomSupport {
+            line: 0.0,
+            position: Vector3::zeros(),
+            attitude: UnitQuaternion::identity(),
+        };
+
+        assert!(LinearPushbroomModel::new(vec![support], 1000.0, 0.0).is_err());
+        assert!(LinearPushbroomModel::new(vec![support, support], 1000.0, 0.0).is_err());
+    }
+}
@@ -1,9 +1,26 @@
 
-use crate::coordinate::{ecef_to_lla, lla_to_ecef, EcefCoord, LlaCoord};
-use crate::error::{ProjectionError, Result};
+use nalgebra::{Matrix2, Matrix3, Vector3};
+use ndarray::{Array2, Array3};
+
+use crate::coordinate::{ecef_to_lla, lla_to_ecef, EcefCoord, GeoidModel, GridDem, LlaCoord};
+use crate::error::{ProjectionError, Result, RspError};
+use crate::math::solve_2x2;
+use crate::warp::{sample_at, Interp};
+
+/// Height change (meters) at which DEM intersection is considered converged
+const DEM_HEIGHT_TOLERANCE: f64 = 1e-3;
+
+/// Maximum iterations when intersecting a viewing ray with a DEM
+const DEM_MAX_ITERATIONS: usize = 50;
+
+/// Line and sample error (pixels) at which the RPC inversion is converged
+const INVERSE_PIXEL_TOLERANCE: f64 = 1e-6;
 
 /// RPC (Rational Polynomial Coefficients) for satellite imagery
-#[derive(Debug, Clone)]
+///
+/// The `Default` is all zeros with no error terms, a starting point for
+/// filling in fields rather than a usable model.
+#[derive(Debug, Clone, Default)]
 pub struct RpcCoefficients {
     // Polynomial coefficients (20 each)
     pub line_num_coeff: [f64; 20],
@@ -22,18 +39,147 @@ pub struct RpcCoefficients {
     pub line_scale: f64,
     pub samp_off: f64,
     pub samp_scale: f64,
+
+    /// Absolute (bias) geolocation error in meters (`ERR_BIAS`), if known
+    pub err_bias: Option<f64>,
+    /// Relative (random) geolocation error in meters (`ERR_RAND`), if known
+    pub err_rand: Option<f64>,
+}
+
+impl RpcCoefficients {
+    /// Build coefficients from a NITF RPC00B parameter vector
+    ///
+    /// Values follow the RPC00B field order: `ERR_BIAS`, `ERR_RAND` (optional),
+    /// then `LINE_OFF`, `SAMP_OFF`, `LAT_OFF`, `LONG_OFF`, `HEIGHT_OFF`,
+    /// `LINE_SCALE`, `SAMP_SCALE`, `LAT_SCALE`, `LONG_SCALE`, `HEIGHT_SCALE`
+    /// and the line numerator, line denominator, sample numerator and sample
+    /// denominator blocks of 20 coefficients each; see
+    /// [`RpcCoefficients::error_term`] for the error terms.
+    ///
+    /// Takes a slice rather than a `[f64; 90]` array because both lengths
+    /// occur: the full record with the error terms is 92 values, and 90 when
+    /// they are omitted. Any other length fails with `InvalidInput`.
+    pub fn from_rpc00b(values: &[f64]) -> Result<Self> {
+        let (v, err_bias, err_rand) = match values.len() {
+            92 => (&values[2..], Self::error_term(values[0]), Self::error_term(values[1])),
+            90 => (values, None, None),
+            n => {
+                return Err(RspError::InvalidInput(format!(
+                    "RPC00B vector must have 90 or 92 values, got {}",
+                    n
+                )));
+            }
+        };
+
+        let block = |i: usize| -> [f64; 20] {
+            let start = 10 + 20 * i;
+            v[start..start + 20].try_into().expect("block has 20 values")
+        };
+
+        Ok(Self {
+            line_num_coeff: block(0),
+            line_den_coeff: block(1),
+            samp_num_coeff: block(2),
+            samp_den_coeff: block(3),
+            line_off: v[0],
+            samp_off: v[1],
+            lat_off: v[2],
+            lon_off: v[3],
+            height_off: v[4],
+            line_scale: v[5],
+            samp_scale: v[6],
+            lat_scale: v[7],
+            lon_scale: v[8],
+            height_scale: v[9],
+            err_bias,
+            err_rand,
+        })
+    }
+
+    /// Interpret an `ERR_BIAS`/`ERR_RAND` value in meters
+    ///
+    /// Providers write -1 (any negative value) for an unknown error, which
+    /// gives `None`.
+    pub fn error_term(value: f64) -> Option<f64> {
+        (value >= 0.0).then_some(value)
+    }
+
+    /// Check the normalization parameters
+    ///
+    /// Every scale divides in the normalization, so a zero or non-finite one
+    /// turns all projections into NaN; offsets must be finite. Fails with
+    /// `RspError::InvalidInput` naming the first bad field.
+    pub fn validate(&self) -> Result<()> {
+        let scales = [
+            ("lat_scale", self.lat_scale),
+            ("lon_scale", self.lon_scale),
+            ("height_scale", self.height_scale),
+            ("line_scale", self.line_scale),
+            ("samp_scale", self.samp_scale),
+        ];
+        for (name, value) in scales {
+            if value == 0.0 || !value.is_finite() {
+                return Err(RspError::InvalidInput(format!(
+                    "RPC {} must be finite and non-zero, got {}",
+                    name, value
+                )));
+            }
+        }
+
+        let offsets = [
+            ("lat_off", self.lat_off),
+            ("lon_off", self.lon_off),
+            ("height_off", self.height_off),
+            ("line_off", self.line_off),
+            ("samp_off", self.samp_off),
+        ];
+        if let Some((name, value)) = offsets.iter().find(|(_, value)| !value.is_finite()) {
+            return Err(RspError::InvalidInput(format!(
+                "RPC {} must be finite, got {}",
+                name, value
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Settings for inverting the RPC in image-to-ground projection
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImageToGroundOptions {
+    /// Starting lat/lon for the Newton iteration (altitude is ignored)
+    ///
+    /// Defaults to a first-order inversion of the RPC, which is much closer
+    /// than the normalization center for pixels near the image edges.
+    pub initial_guess: Option<LlaCoord>,
 }
 
 /// RPC sensor model for ground-to-image and image-to-ground projection
 #[derive(Debug, Clone)]
 pub struct RpcModel {
     coeffs: RpcCoefficients,
+    /// Image-space bias `a0 + a1 * line + a2 * samp` added to line
+    row_adjustment: [f64; 3],
+    /// Image-space bias `b0 + b1 * line + b2 * samp` added to sample
+    col_adjustment: [f64; 3],
 }
 
 impl RpcModel {
     /// Create a new RPC model from coefficients
     pub fn new(coeffs: RpcCoefficients) -> Self {
-        Self { coeffs }
+        Self {
+            coeffs,
+            row_adjustment: [0.0; 3],
+            col_adjustment: [0.0; 3],
+        }
+    }
+    
+    /// Create a model after checking the coefficients' normalization parameters
+    ///
+    /// See [`RpcCoefficients::validate`]; [`validate`](Self::validate) runs
+    /// the fuller check including a projection round trip.
+    pub fn try_new(coeffs: RpcCoefficients) -> Result<Self> {
+        coeffs.validate()?;
+        Ok(Self::new(coeffs))
     }
     
     /// Get reference to coefficients
@@ -41,6 +187,111 @@ impl RpcModel {
         &self.coeffs
     }
     
+    /// Provider-reported geolocation accuracy `(err_bias, err_rand)` in meters
+    ///
+    /// `None` unless both error terms were present in the source metadata.
+    pub fn geolocation_accuracy(&self) -> Option<(f64, f64)> {
+        Some((self.coeffs.err_bias?, self.coeffs.err_rand?))
+    }
+    
+    /// Set an affine image-space correction, e.g. from bundle adjustment
+    ///
+    /// After the RPC projects to `(line, samp)`, the model reports
+    /// `line + row[0] + row[1] * line + row[2] * samp` and
+    /// `samp + col[0] + col[1] * line + col[2] * samp`; image-to-ground
+    /// removes the correction first. All zeros (the default) is the identity.
+    pub fn with_image_adjustment(&mut self, row: [f64; 3], col: [f64; 3]) {
+        self.row_adjustment = row;
+        self.col_adjustment = col;
+    }
+    
+    /// Least-squares image adjustment that aligns the RPC with ground control
+    ///
+    /// Each GCP is a ground point and the `(line, sample)` where it was
+    /// observed. Fits the affine terms of [`with_image_adjustment`] that
+    /// minimize `observed - projected` over the GCPs, where the projection is
+    /// the raw RPC (any adjustment already set is ignored, so the result
+    /// replaces it). Needs at least 3 GCPs that aren't collinear in the image.
+    ///
+    /// [`with_image_adjustment`]: Self::with_image_adjustment
+    pub fn refine_bias_from_gcps(
+        &self,
+        gcps: &[(LlaCoord, (f64, f64))],
+    ) -> Result<([f64; 3], [f64; 3])> {
+        if gcps.len() < 3 {
+            return Err(RspError::InvalidInput(format!(
+                "Fitting an affine image bias needs at least 3 GCPs, got {}",
+                gcps.len()
+            )));
+        }
+        
+        // Fit in normalized image coordinates to keep the normal equations
+        // well conditioned, then convert back to pixels
+        let c = &self.coeffs;
+        let mut ata = Matrix3::zeros();
+        let mut atb_line = Vector3::zeros();
+        let mut atb_samp = Vector3::zeros();
+        for (lla, (obs_line, obs_samp)) in gcps {
+            let (line, samp) = self.lla_to_image_unadjusted(lla)?;
+            let a = Vector3::new(
+                1.0,
+                (line - c.line_off) / c.line_scale,
+                (samp - c.samp_off) / c.samp_scale,
+            );
+            ata += a * a.transpose();
+            atb_line += a * (obs_line - line);
+            atb_samp += a * (obs_samp - samp);
+        }
+        
+        let inverse = ata.try_inverse().ok_or_else(|| {
+            RspError::Numerical("GCPs are collinear in the image, bias is undetermined".to_string())
+        })?;
+        let to_pixels = |n: Vector3<f64>| {
+            let (a1, a2) = (n[1] / c.line_scale, n[2] / c.samp_scale);
+            [n[0] - a1 * c.line_off - a2 * c.samp_off, a1, a2]
+        };
+        
+        Ok((to_pixels(inverse * atb_line), to_pixels(inverse * atb_samp)))
+    }
+    
+    /// Sanity-check the model before use
+    ///
+    /// Checks the normalization parameters with [`RpcCoefficients::validate`],
+    /// that both denominators have a non-zero constant term, and that the
+    /// scene center projects to the image and back to within one pixel.
+    /// Failures are reported as `RspError::InvalidInput` naming the problem.
+    pub fn validate(&self) -> Result<()> {
+        let c = &self.coeffs;
+        c.validate()?;
+        
+        for (name, den) in [("line", &c.line_den_coeff), ("sample", &c.samp_den_coeff)] {
+            if den[0].abs() < 1e-10 || !den[0].is_finite() {
+                return Err(RspError::InvalidInput(format!(
+                    "RPC {} denominator has no constant term ({})",
+                    name, den[0]
+                )));
+            }
+        }
+        
+        let center = LlaCoord { lat: c.lat_off, lon: c.lon_off, alt: c.height_off };
+        let round_trip = self.lla_to_image(&center).and_then(|(line, samp)| {
+            let lla = self.image_to_lla(line, samp, c.height_off)?;
+            let (back_line, back_samp) = self.lla_to_image(&lla)?;
+            Ok((line - back_line).hypot(samp - back_samp))
+        });
+        match round_trip {
+            Ok(residual) if residual < 1.0 => Ok(()),
+            Ok(residual) => Err(RspError::InvalidInput(format!(
+                "RPC scene center round trip is off by {:.3} pixels",
+                residual
+            ))),
+            Err(e) => Err(RspError::InvalidInput(format!(
+                "RPC scene center doesn't round trip: {}",
+                e
+            ))),
+        }
+    }
+    
     /// Project ground point (ECEF) to image coordinates (line, sample)
     pub fn ground_to_image(&self, ground_ecef: &EcefCoord) -> Result<(f64, f64)> {
         // Convert ECEF to LLA
@@ -50,6 +301,31 @@ impl RpcModel {
     
     /// Project LLA to image coordinates (line, sample)
     pub fn lla_to_image(&self, lla: &LlaCoord) -> Result<(f64, f64)> {
+        let (line, samp) = self.lla_to_image_unadjusted(lla)?;
+        let (r, c) = (&self.row_adjustment, &self.col_adjustment);
+        
+        Ok((
+            line + r[0] + r[1] * line + r[2] * samp,
+            samp + c[0] + c[1] * line + c[2] * samp,
+        ))
+    }
+    
+    /// Remove the image-space adjustment from adjusted pixel coordinates
+    fn remove_adjustment(&self, line: f64, sample: f64) -> Result<(f64, f64)> {
+        let (r, c) = (&self.row_adjustment, &self.col_adjustment);
+        
+        // [1 + r1, r2; c1, 1 + c2] * [line; samp] = [line' - r0; samp' - c0]
+        let [line, samp] = solve_2x2(
+            [[1.0 + r[1], r[2]], [c[1], 1.0 + c[2]]],
+            [line - r[0], sample - c[0]],
+        )
+        .map_err(|_| ProjectionError::InvalidRpc)?;
+        
+        Ok((line, samp))
+    }
+    
+    /// Raw RPC projection of LLA to (line, sample), without image adjustment
+    fn lla_to_image_unadjusted(&self, lla: &LlaCoord) -> Result<(f64, f64)> {
         // Normalize coordinates
         let p = (lla.lon - self.coeffs.lon_off) / self.coeffs.lon_scale;
         let l = (lla.lat - self.coeffs.lat_off) / self.coeffs.lat_scale;
@@ -72,6 +348,74 @@ impl RpcModel {
         Ok((line, samp))
     }
     
+    /// Approximate ground sample distance (meters per pixel) at a ground point
+    ///
+    /// Returns `(line_gsd, samp_gsd)`: the ground distance covered by a
+    /// one-pixel step along the line and sample directions, on the horizontal
+    /// plane at the point's height. Found by projecting small lat/lon offsets
+    /// and measuring their ground separation with `lla_to_ecef`.
+    pub fn gsd_at(&self, lla: &LlaCoord) -> Result<(f64, f64)> {
+        let delta = 1e-5;
+        let center = lla_to_ecef(lla)?;
+        let (line, samp) = self.lla_to_image(lla)?;
+        
+        let north_lla = LlaCoord { lat: lla.lat + delta, ..*lla };
+        let east_lla = LlaCoord { lon: lla.lon + delta, ..*lla };
+        let north_m = (lla_to_ecef(&north_lla)? - center).norm();
+        let east_m = (lla_to_ecef(&east_lla)? - center).norm();
+        
+        // Pixel motion per meter north / east
+        let (line_n, samp_n) = self.lla_to_image(&north_lla)?;
+        let (line_e, samp_e) = self.lla_to_image(&east_lla)?;
+        let jacobian = Matrix2::new(
+            (line_e - line) / east_m,
+            (line_n - line) / north_m,
+            (samp_e - samp) / east_m,
+            (samp_n - samp) / north_m,
+        );
+        
+        let inverse = jacobian.try_inverse().ok_or_else(|| {
+            RspError::Numerical("RPC is singular at this point; GSD is undefined".to_string())
+        })?;
+        
+        // Ground (east, north) displacement for one line and one sample
+        Ok((inverse.column(0).norm(), inverse.column(1).norm()))
+    }
+    
+    /// Ground sample distance (meters per pixel) at an image position
+    ///
+    /// Pixel-based counterpart of [`RpcModel::gsd_at`]: `(line, samp)` is
+    /// located on the ground at `height` and `(line_gsd, samp_gsd)` is the
+    /// GSD there.
+    pub fn gsd_at_pixel(&self, line: f64, samp: f64, height: f64) -> Result<(f64, f64)> {
+        self.gsd_at(&self.image_to_lla(line, samp, height)?)
+    }
+    
+    /// Pixel bounding box of a ground polygon as (min_line, min_samp, max_line, max_samp)
+    ///
+    /// Only the vertices are projected, so this is an approximation: edges
+    /// that bow outward in image space (RPC curvature, terrain) aren't
+    /// covered. Pad the result if an exact cover matters.
+    pub fn ground_polygon_to_image_bbox(
+        &self,
+        polygon: &[LlaCoord],
+    ) -> Result<(f64, f64, f64, f64)> {
+        if polygon.is_empty() {
+            return Err(RspError::InvalidInput("Ground polygon has no vertices".to_string()));
+        }
+        
+        let mut bbox = (f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for vertex in polygon {
+            let (line, samp) = self.lla_to_image(vertex)?;
+            bbox.0 = bbox.0.min(line);
+            bbox.1 = bbox.1.min(samp);
+            bbox.2 = bbox.2.max(line);
+            bbox.3 = bbox.3.max(samp);
+        }
+        
+        Ok(bbox)
+    }
+    
     /// Project image coordinates to ground point at given height (ECEF)
     /// Uses Newton-Raphson iteration to invert the RPC
     pub fn image_to_ground(&self, line: f64, sample: f64, height: f64) -> Result<EcefCoord> {
@@ -79,47 +423,266 @@ impl RpcModel {
         lla_to_ecef(&lla)
     }
     
+    /// Project image coordinates to ground point at given height (ECEF) with explicit options
+    pub fn image_to_ground_with(
+        &self,
+        line: f64,
+        sample: f64,
+        height: f64,
+        options: &ImageToGroundOptions,
+    ) -> Result<EcefCoord> {
+        let lla = self.image_to_lla_with(line, sample, height, options)?;
+        lla_to_ecef(&lla)
+    }
+    
+    /// Project image coordinates to ground point at given orthometric height (ECEF)
+    ///
+    /// The RPC is referenced to ellipsoidal height, so the geoid undulation is
+    /// re-evaluated at each solved lat/lon until the ellipsoidal height
+    /// settles.
+    pub fn image_to_ground_orthometric(
+        &self,
+        line: f64,
+        sample: f64,
+        ortho_height: f64,
+        geoid: &GeoidModel,
+    ) -> Result<EcefCoord> {
+        let mut undulation = geoid.undulation(self.coeffs.lat_off, self.coeffs.lon_off);
+        
+        for _ in 0..10 {
+            let lla = self.image_to_lla(line, sample, ortho_height + undulation)?;
+            let updated = geoid.undulation(lla.lat, lla.lon);
+            
+            if (updated - undulation).abs() < 1e-4 {
+                return lla_to_ecef(&lla);
+            }
+            undulation = updated;
+        }
+        
+        Err(ProjectionError::NoConvergence(10).into())
+    }
+    
+    /// Project image coordinates onto a DEM surface (ECEF)
+    ///
+    /// Starts at the RPC height offset and alternates between projecting the
+    /// pixel to the current height and re-sampling the DEM (bilinearly) at
+    /// the resulting lat/lon, until the height changes by less than
+    /// `DEM_HEIGHT_TOLERANCE`. Fails with `OutOfBounds` if the ray leaves the
+    /// DEM and `NoConvergence` if the height doesn't settle.
+    pub fn image_to_ground_dem(&self, line: f64, sample: f64, dem: &GridDem) -> Result<EcefCoord> {
+        let (ground, _) = self.image_to_ground_dem_from(line, sample, dem, None)?;
+        Ok(ground)
+    }
+    
+    /// [`image_to_ground_dem`](Self::image_to_ground_dem) from a chosen starting height
+    ///
+    /// `initial_height` seeds the iteration (e.g. with the DEM's mean height
+    /// or a neighboring pixel's solution); `None` starts at the RPC height
+    /// offset. Returns the ground point and the number of projections used.
+    pub fn image_to_ground_dem_from(
+        &self,
+        line: f64,
+        sample: f64,
+        dem: &GridDem,
+        initial_height: Option<f64>,
+    ) -> Result<(EcefCoord, usize)> {
+        let mut height = initial_height.unwrap_or(self.coeffs.height_off);
+        
+        for iteration in 1..=DEM_MAX_ITERATIONS {
+            let lla = self.image_to_lla(line, sample, height)?;
+            let terrain = dem
+                .height_at(lla.lat, lla.lon)
+                .ok_or(ProjectionError::OutOfBounds { line, sample })?;
+            
+            if (terrain - height).abs() < DEM_HEIGHT_TOLERANCE {
+                return Ok((lla_to_ecef(&lla)?, iteration));
+            }
+            height = terrain;
+        }
+        
+        Err(ProjectionError::NoConvergence(DEM_MAX_ITERATIONS).into())
+    }
+    
+    /// Project image coordinates to ground using a height grid in image geometry
+    ///
+    /// `height_grid` holds one height per image pixel (shape: [lines,
+    /// samples]), e.g. a DEM already resampled into this image. The height is
+    /// sampled bilinearly at `(line, sample)`, with integer coordinates at
+    /// grid cells, and the pixel is projected at it. Fails with
+    /// `OutOfBounds` outside the grid or on a NaN height.
+    pub fn image_to_ground_with_height_grid(
+        &self,
+        line: f64,
+        sample: f64,
+        height_grid: &Array2<f32>,
+    ) -> Result<EcefCoord> {
+        let height = sample_at(height_grid, line, sample, Interp::Bilinear);
+        if !height.is_finite() {
+            return Err(ProjectionError::OutOfBounds { line, sample }.into());
+        }
+        
+        self.image_to_ground(line, sample, height as f64)
+    }
+    
     /// Project image coordinates to LLA at given height
     pub fn image_to_lla(&self, line: f64, sample: f64, height: f64) -> Result<LlaCoord> {
-        // Initial guess - use center of RPC normalization
-        let mut lat = self.coeffs.lat_off;
-        let mut lon = self.coeffs.lon_off;
+        self.image_to_lla_with(line, sample, height, &ImageToGroundOptions::default())
+    }
+    
+    /// Project image coordinates to LLA at given height with explicit options
+    pub fn image_to_lla_with(
+        &self,
+        line: f64,
+        sample: f64,
+        height: f64,
+        options: &ImageToGroundOptions,
+    ) -> Result<LlaCoord> {
+        let (line, sample) = self.remove_adjustment(line, sample)?;
+        
+        let start = match options.initial_guess {
+            Some(guess) => (guess.lat, guess.lon),
+            None => self.linear_inverse(line, sample, height),
+        };
+        
+        let (lla, _) = self.newton_image_to_lla(line, sample, height, start)?;
+        Ok(lla)
+    }
+    
+    /// Project every pixel of an image window to LLA at a given height
+    ///
+    /// Returns `[lat, lon, alt]` per pixel (shape: [h, w, 3]) for lines
+    /// `y_off..y_off + h` and samples `x_off..x_off + w`. Each pixel's
+    /// Newton-Raphson inversion starts from its left neighbor's solution
+    /// (the pixel above for the first column), so only the first pixel
+    /// needs the linear initial guess and the rest converge in a step or
+    /// two. Fails if any pixel fails to converge or the window is empty.
+    pub fn image_grid_to_ground(
+        &self,
+        x_off: usize,
+        y_off: usize,
+        w: usize,
+        h: usize,
+        height: f64,
+    ) -> Result<Array3<f64>> {
+        if w == 0 || h == 0 {
+            return Err(RspError::InvalidInput(format!("Empty pixel grid {}x{}", w, h)));
+        }
+        
+        let mut out = Array3::zeros((h, w, 3));
+        let mut row_start = None;
+        for r in 0..h {
+            let mut seed = row_start;
+            for c in 0..w {
+                let (line, sample) =
+                    self.remove_adjustment((y_off + r) as f64, (x_off + c) as f64)?;
+                let start = seed.unwrap_or_else(|| self.linear_inverse(line, sample, height));
+                
+                let (lla, _) = self.newton_image_to_lla(line, sample, height, start)?;
+                out[[r, c, 0]] = lla.lat;
+                out[[r, c, 1]] = lla.lon;
+                out[[r, c, 2]] = lla.alt;
+                
+                seed = Some((lla.lat, lla.lon));
+                if c == 0 {
+                    row_start = seed;
+                }
+            }
+        }
+        
+        Ok(out)
+    }
+    
+    /// Project image coordinates to LLA and report the final pixel residual
+    ///
+    /// The residual is the distance (pixels) between the requested image
+    /// coordinates and the reprojection of the solution, for QA of the
+    /// inversion.
+    pub fn image_to_lla_with_residual(
+        &self,
+        line: f64,
+        sample: f64,
+        height: f64,
+    ) -> Result<(LlaCoord, f64)> {
+        let lla = self.image_to_lla(line, sample, height)?;
+        let (proj_line, proj_samp) = self.lla_to_image(&lla)?;
+        Ok((lla, (line - proj_line).hypot(sample - proj_samp)))
+    }
+    
+    /// Lat/lon of unadjusted image coordinates from the first-order RPC terms
+    ///
+    /// Keeps only the constant, L, P and H terms of `Num - r * Den = 0` for
+    /// line and sample and solves the resulting 2x2 system. Falls back to the
+    /// normalization center if that system is singular.
+    fn linear_inverse(&self, line: f64, sample: f64, height: f64) -> (f64, f64) {
+        let c = &self.coeffs;
+        let h = (height - c.height_off) / c.height_scale;
+        
+        let linearize = |num: &[f64; 20], den: &[f64; 20], r: f64| {
+            let k = |i: usize| num[i] - r * den[i];
+            ([k(1), k(2)], -(k(0) + k(3) * h))
+        };
+        let (line_row, line_rhs) = linearize(
+            &c.line_num_coeff,
+            &c.line_den_coeff,
+            (line - c.line_off) / c.line_scale,
+        );
+        let (samp_row, samp_rhs) = linearize(
+            &c.samp_num_coeff,
+            &c.samp_den_coeff,
+            (sample - c.samp_off) / c.samp_scale,
+        );
+        
+        match solve_2x2([line_row, samp_row], [line_rhs, samp_rhs]) {
+            Ok([l, p]) => (c.lat_off + l * c.lat_scale, c.lon_off + p * c.lon_scale),
+            Err(_) => (c.lat_off, c.lon_off),
+        }
+    }
+    
+    /// Newton-Raphson inversion of the unadjusted RPC from a starting lat/lon
+    ///
+    /// Also returns the number of Newton steps taken.
+    fn newton_image_to_lla(
+        &self,
+        line: f64,
+        sample: f64,
+        height: f64,
+        start: (f64, f64),
+    ) -> Result<(LlaCoord, usize)> {
+        let (mut lat, mut lon) = start;
         
-        // Newton-Raphson iteration
         for iter in 0..20 {
             let lla = LlaCoord { lat, lon, alt: height };
-            let (proj_line, proj_samp) = self.lla_to_image(&lla)?;
+            let (proj_line, proj_samp) = self.lla_to_image_unadjusted(&lla)?;
             
             let line_err = line - proj_line;
             let samp_err = sample - proj_samp;
             
             // Check convergence
-            if line_err.abs() < 1e-6 && samp_err.abs() < 1e-6 {
-                return Ok(lla);
+            if line_err.abs() < INVERSE_PIXEL_TOLERANCE
+                && samp_err.abs() < INVERSE_PIXEL_TOLERANCE
+            {
+                return Ok((lla, iter));
             }
             
             // Compute Jacobian using finite differences
             let delta = 1e-7;
             
             let lla_lat_plus = LlaCoord { lat: lat + delta, lon, alt: height };
-            let (line_lat_plus, samp_lat_plus) = self.lla_to_image(&lla_lat_plus)?;
+            let (line_lat_plus, samp_lat_plus) = self.lla_to_image_unadjusted(&lla_lat_plus)?;
             let dline_dlat = (line_lat_plus - proj_line) / delta;
             let dsamp_dlat = (samp_lat_plus - proj_samp) / delta;
             
             let lla_lon_plus = LlaCoord { lat, lon: lon + delta, alt: height };
-            let (line_lon_plus, samp_lon_plus) = self.lla_to_image(&lla_lon_plus)?;
+            let (line_lon_plus, samp_lon_plus) = self.lla_to_image_unadjusted(&lla_lon_plus)?;
             let dline_dlon = (line_lon_plus - proj_line) / delta;
             let dsamp_dlon = (samp_lon_plus - proj_samp) / delta;
             
             // Solve 2x2 system: J * [dlat, dlon]' = [line_err, samp_err]'
-            let det = dline_dlat * dsamp_dlon - dline_dlon * dsamp_dlat;
-            
-            if det.abs() < 1e-10 {
-                return Err(ProjectionError::NoConvergence(iter).into());
-            }
-            
-            let dlat = (dsamp_dlon * line_err - dline_dlon * samp_err) / det;
-            let dlon = (dline_dlat * samp_err - dsamp_dlat * line_err) / det;
+            let [dlat, dlon] = solve_2x2(
+                [[dline_dlat, dline_dlon], [dsamp_dlat, dsamp_dlon]],
+                [line_err, samp_err],
+            )
+            .map_err(|_| ProjectionError::NoConvergence(iter))?;
             
             lat += dlat;
             lon += dlon;
@@ -131,39 +694,47 @@ impl RpcModel {
 
 /// Evaluate RPC polynomial with 20 coefficients
 fn eval_polynomial(coeffs: &[f64; 20], p: f64, l: f64, h: f64) -> f64 {
-    coeffs[0]
-        + coeffs[1] * l
-        + coeffs[2] * p
-        + coeffs[3] * h
-        + coeffs[4] * l * p
-        + coeffs[5] * l * h
-        + coeffs[6] * p * h
-        + coeffs[7] * l * l
-        + coeffs[8] * p * p
-        + coeffs[9] * h * h
-        + coeffs[10] * p * l * h
-        + coeffs[11] * l * l * l
-        + coeffs[12] * l * p * p
-        + coeffs[13] * l * h * h
-        + coeffs[14] * l * l * p
-        + coeffs[15] * p * p * p
-        + coeffs[16] * p * h * h
-        + coeffs[17] * l * l * h
-        + coeffs[18] * p * p * h
-        + coeffs[19] * h * h * h
+    polynomial_terms(p, l, h)
+        .iter()
+        .zip(coeffs)
+        .map(|(term, c)| term * c)
+        .sum()
+}
+
+/// The 20 RPC00B monomials of normalized (P = lon, L = lat, H = height)
+pub(crate) fn polynomial_terms(p: f64, l: f64, h: f64) -> [f64; 20] {
+    [
+        1.0,
+        l,
+        p,
+        h,
+        l * p,
+        l * h,
+        p * h,
+        l * l,
+        p * p,
+        h * h,
+        p * l * h,
+        l * l * l,
+        l * p * p,
+        l * h * h,
+        l * l * p,
+        p * p * p,
+        p * h * h,
+        l * l * h,
+        p * p * h,
+        h * h * h,
+    ]
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::coordinate::{tilted_plane, tilted_plane_dem};
     use crate::error::{RspError, ProjectionError};
 
     fn create_simple_rpc() -> RpcCoefficients {
         let mut coeffs = RpcCoefficients {
-            line_num_coeff: [0.0; 20],
-            line_den_coeff: [0.0; 20],
-            samp_num_coeff: [0.0; 20],
-            samp_den_coeff: [0.0; 20],
             lat_off: 39.0,
             lat_scale: 1.0,
             lon_off: -77.0,
@@ -174,6 +745,7 @@ mod tests {
             line_scale: 5000.0,
             samp_off: 5000.0,
             samp_scale: 5000.0,
+            ..Default::default()
         };
 
         // Simple linear RPC (just for testing)
@@ -298,6 +870,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rpc_image_to_ground_orthometric_zero_geoid() {
+        let rpc = RpcModel::new(create_simple_rpc());
+        let geoid = GeoidModel::constant(0.0);
+
+        let plain = rpc.image_to_ground(4200.0, 5300.0, 250.0).unwrap();
+        let ortho = rpc
+            .image_to_ground_orthometric(4200.0, 5300.0, 250.0, &geoid)
+            .unwrap();
+
+        assert!((plain - ortho).norm() < 1e-6);
+    }
+
+    #[test]
+    fn test_rpc_image_to_ground_orthometric_offset() {
+        let rpc = RpcModel::new(create_simple_rpc());
+        let geoid = GeoidModel::constant(-30.0);
+
+        let ortho = rpc
+            .image_to_ground_orthometric(4200.0, 5300.0, 250.0, &geoid)
+            .unwrap();
+        let lla = ecef_to_lla(&ortho).unwrap();
+
+        assert!((lla.alt - 220.0).abs() < 1e-3);
+    }
+
     #[test]
     fn test_eval_polynomial() {
         // Test polynomial evaluation with known values
@@ -350,4 +948,369 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), RspError::Projection(ProjectionError::InvalidRpc)));
     }
+
+    #[test]
+    fn test_from_rpc00b() {
+        let mut values = vec![1.5, 0.8];
+        values.extend_from_slice(&[
+            3000.0, 4000.0, 39.1, -77.2, 150.0, 3100.0, 4100.0, 0.08, 0.09, 600.0,
+        ]);
+        for block in 0..4 {
+            values.extend((0..20).map(|i| (block * 100 + i) as f64));
+        }
+
+        let coeffs = RpcCoefficients::from_rpc00b(&values).unwrap();
+        assert_eq!(coeffs.line_off, 3000.0);
+        assert_eq!(coeffs.samp_off, 4000.0);
+        assert_eq!(coeffs.lat_off, 39.1);
+        assert_eq!(coeffs.lon_off, -77.2);
+        assert_eq!(coeffs.height_off, 150.0);
+        assert_eq!(coeffs.line_scale, 3100.0);
+        assert_eq!(coeffs.samp_scale, 4100.0);
+        assert_eq!(coeffs.lat_scale, 0.08);
+        assert_eq!(coeffs.lon_scale, 0.09);
+        assert_eq!(coeffs.height_scale, 600.0);
+        assert_eq!(coeffs.line_num_coeff[0], 0.0);
+        assert_eq!(coeffs.line_den_coeff[1], 101.0);
+        assert_eq!(coeffs.samp_num_coeff[19], 219.0);
+        assert_eq!(coeffs.samp_den_coeff[5], 305.0);
+        assert_eq!(RpcModel::new(coeffs.clone()).geolocation_accuracy(), Some((1.5, 0.8)));
+
+        // Same vector without the error terms
+        let short = RpcCoefficients::from_rpc00b(&values[2..]).unwrap();
+        assert_eq!(short.samp_den_coeff, coeffs.samp_den_coeff);
+        assert_eq!(short.height_scale, coeffs.height_scale);
+        assert_eq!(RpcModel::new(short).geolocation_accuracy(), None);
+
+        // A negative error term means unknown
+        values[0] = -1.0;
+        let unknown = RpcCoefficients::from_rpc00b(&values).unwrap();
+        assert_eq!((unknown.err_bias, unknown.err_rand), (None, Some(0.8)));
+    }
+
+    #[test]
+    fn test_from_rpc00b_wrong_length() {
+        for len in [0, 20, 89, 91, 93] {
+            let result = RpcCoefficients::from_rpc00b(&vec![1.0; len]);
+            assert!(matches!(result, Err(RspError::InvalidInput(_))), "{}", len);
+        }
+        for len in [90, 92] {
+            assert!(RpcCoefficients::from_rpc00b(&vec![1.0; len]).is_ok(), "{}", len);
+        }
+    }
+
+    /// Oblique view over the tilted-plane DEM: sample shifts with height
+    fn oblique_rpc() -> RpcModel {
+        let mut coeffs = create_simple_rpc();
+        coeffs.lat_scale = 0.1;
+        coeffs.lon_scale = 0.1;
+        coeffs.samp_num_coeff[3] = 0.3;
+        RpcModel::new(coeffs)
+    }
+
+    #[test]
+    fn test_rpc_image_to_ground_dem_tilted_plane() {
+        let rpc = oblique_rpc();
+        let dem = tilted_plane_dem();
+
+        let (line, sample) = (5123.0, 4890.0);
+        let ground = rpc.image_to_ground_dem(line, sample, &dem).unwrap();
+        let lla = ecef_to_lla(&ground).unwrap();
+
+        assert!((lla.alt - tilted_plane(lla.lat, lla.lon)).abs() < 0.05);
+
+        let (l, s) = rpc.lla_to_image(&lla).unwrap();
+        assert!((l - line).abs() < 1e-3);
+        assert!((s - sample).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_rpc_image_to_ground_dem_seeded_height() {
+        let rpc = oblique_rpc();
+        let dem = tilted_plane_dem();
+
+        let (line, sample) = (5123.0, 4890.0);
+        let (ground, cold) = rpc.image_to_ground_dem_from(line, sample, &dem, None).unwrap();
+        let surface = ecef_to_lla(&ground).unwrap().alt;
+
+        let seeded = rpc.image_to_ground_dem_from(line, sample, &dem, Some(surface + 0.5));
+        let (seeded_ground, warm) = seeded.unwrap();
+
+        assert!(warm < cold, "{} vs {} iterations", warm, cold);
+        assert!((seeded_ground - ground).norm() < 0.05);
+    }
+
+    #[test]
+    fn test_rpc_image_to_ground_with_height_grid() {
+        let mut coeffs = create_simple_rpc();
+        coeffs.samp_num_coeff[3] = 0.3;
+        let rpc = RpcModel::new(coeffs);
+
+        let grid = Array2::from_elem((128, 128), 312.5f32);
+        let (line, sample) = (60.25, 40.75);
+        let from_grid = rpc.image_to_ground_with_height_grid(line, sample, &grid).unwrap();
+        let scalar = rpc.image_to_ground(line, sample, 312.5).unwrap();
+        assert!((from_grid - scalar).norm() < 1e-6);
+
+        // Past the last grid row
+        let outside = rpc.image_to_ground_with_height_grid(127.5, sample, &grid);
+        assert!(matches!(
+            outside,
+            Err(RspError::Projection(ProjectionError::OutOfBounds { line: 127.5, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_rpc_image_to_ground_dem_outside() {
+        use ndarray::Array2;
+
+        let rpc = RpcModel::new(create_simple_rpc());
+        let dem = GridDem::new([10.0, 0.001, 0.0, 10.0, 0.0, -0.001], Array2::zeros((10, 10)))
+            .unwrap();
+
+        let result = rpc.image_to_ground_dem(5000.0, 5000.0, &dem);
+        assert!(matches!(
+            result,
+            Err(RspError::Projection(ProjectionError::OutOfBounds { line: 5000.0, sample: 5000.0 }))
+        ));
+    }
+
+    #[test]
+    fn test_ground_polygon_to_image_bbox() {
+        let rpc = RpcModel::new(create_simple_rpc());
+
+        let footprint = [
+            LlaCoord { lat: 39.01, lon: -77.02, alt: 100.0 },
+            LlaCoord { lat: 39.01, lon: -76.98, alt: 100.0 },
+            LlaCoord { lat: 38.99, lon: -76.98, alt: 100.0 },
+            LlaCoord { lat: 38.99, lon: -77.02, alt: 100.0 },
+        ];
+
+        let (min_line, min_samp, max_line, max_samp) =
+            rpc.ground_polygon_to_image_bbox(&footprint).unwrap();
+
+        // line = 5000 * (lat - 39) + 5000, samp = 5000 * (lon + 77) + 5000
+        assert!((min_line - 4950.0).abs() < 1e-6);
+        assert!((max_line - 5050.0).abs() < 1e-6);
+        assert!((min_samp - 4900.0).abs() < 1e-6);
+        assert!((max_samp - 5100.0).abs() < 1e-6);
+
+        assert!(rpc.ground_polygon_to_image_bbox(&[]).is_err());
+    }
+
+    #[test]
+    fn test_rpc_gsd_at() {
+        let rpc = RpcModel::new(create_simple_rpc());
+        let lla = LlaCoord { lat: 39.0, lon: -77.0, alt: 100.0 };
+
+        let (line_gsd, samp_gsd) = rpc.gsd_at(&lla).unwrap();
+
+        // One pixel is 1/5000 degree: ~22.2 m of latitude, ~17.3 m of longitude
+        assert!((line_gsd - 22.2).abs() < 0.2);
+        assert!((samp_gsd - 17.3).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_rpc_gsd_at_pixel() {
+        let rpc = RpcModel::new(create_simple_rpc());
+
+        // One pixel is 1/5000 degree: ~22.2 m of latitude, ~17.3 m of longitude
+        let (line_gsd, samp_gsd) = rpc.gsd_at_pixel(700.0, 1200.0, 100.0).unwrap();
+        assert!((line_gsd / 22.2 - 1.0).abs() < 0.02);
+        assert!((samp_gsd / 17.3 - 1.0).abs() < 0.02);
+
+        // Same as the ground-point estimate at the same location
+        let lla = rpc.image_to_lla(700.0, 1200.0, 100.0).unwrap();
+        assert_eq!((line_gsd, samp_gsd), rpc.gsd_at(&lla).unwrap());
+    }
+
+    #[test]
+    fn test_rpc_image_grid_to_ground() {
+        let mut rpc = nonlinear_rpc();
+        rpc.with_image_adjustment([1.5, 0.001, 0.0], [-2.0, 0.0, 0.002]);
+
+        let grid = rpc.image_grid_to_ground(1190, 690, 6, 4, 250.0).unwrap();
+        assert_eq!(grid.dim(), (4, 6, 3));
+        for r in 0..4 {
+            for c in 0..6 {
+                let expected =
+                    rpc.image_to_lla((690 + r) as f64, (1190 + c) as f64, 250.0).unwrap();
+                assert!((grid[[r, c, 0]] - expected.lat).abs() < 1e-9);
+                assert!((grid[[r, c, 1]] - expected.lon).abs() < 1e-9);
+                assert_eq!(grid[[r, c, 2]], 250.0);
+            }
+        }
+
+        assert!(matches!(
+            rpc.image_grid_to_ground(0, 0, 0, 4, 250.0),
+            Err(RspError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_rpc_image_adjustment_translation() {
+        let plain = RpcModel::new(create_simple_rpc());
+        let mut adjusted = RpcModel::new(create_simple_rpc());
+        adjusted.with_image_adjustment([2.5, 0.0, 0.0], [-1.25, 0.0, 0.0]);
+
+        let lla = LlaCoord { lat: 39.01, lon: -76.99, alt: 150.0 };
+        let (line, samp) = plain.lla_to_image(&lla).unwrap();
+        let (line_adj, samp_adj) = adjusted.lla_to_image(&lla).unwrap();
+        assert!((line_adj - line - 2.5).abs() < 1e-9);
+        assert!((samp_adj - samp + 1.25).abs() < 1e-9);
+
+        // Round trip still holds through the adjustment
+        let back = adjusted.image_to_lla(line_adj, samp_adj, 150.0).unwrap();
+        assert!((back.lat - lla.lat).abs() < 1e-9);
+        assert!((back.lon - lla.lon).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rpc_image_adjustment_affine_roundtrip() {
+        let mut rpc = RpcModel::new(create_simple_rpc());
+        rpc.with_image_adjustment([1.0, 1e-4, -2e-4], [-3.0, 5e-5, 1e-4]);
+
+        let (line, samp) = (4321.0, 5678.0);
+        let lla = rpc.image_to_lla(line, samp, 50.0).unwrap();
+        let (l, s) = rpc.lla_to_image(&lla).unwrap();
+        assert!((l - line).abs() < 1e-6);
+        assert!((s - samp).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rpc_refine_bias_from_gcps() {
+        let rpc = RpcModel::new(create_simple_rpc());
+        let (row, col) = ([2.5, 1e-4, -2e-4], [-3.0, 5e-5, 1e-4]);
+        let mut biased = rpc.clone();
+        biased.with_image_adjustment(row, col);
+
+        let offsets = [(-0.03, 0.02), (0.04, 0.03), (0.01, -0.04), (-0.02, -0.01), (0.03, -0.02)];
+        let gcps: Vec<_> = offsets
+            .iter()
+            .enumerate()
+            .map(|(i, &(dlat, dlon))| {
+                let lla = LlaCoord { lat: 39.0 + dlat, lon: -77.0 + dlon, alt: 50.0 * i as f64 };
+                (lla, biased.lla_to_image(&lla).unwrap())
+            })
+            .collect();
+
+        let (fit_row, fit_col) = rpc.refine_bias_from_gcps(&gcps).unwrap();
+        for k in 0..3 {
+            assert!((fit_row[k] - row[k]).abs() < 1e-8, "row[{}] = {}", k, fit_row[k]);
+            assert!((fit_col[k] - col[k]).abs() < 1e-8, "col[{}] = {}", k, fit_col[k]);
+        }
+
+        // An already-adjusted model fits the same bias
+        let (again, _) = biased.refine_bias_from_gcps(&gcps).unwrap();
+        assert!((again[0] - row[0]).abs() < 1e-8);
+
+        assert!(rpc.refine_bias_from_gcps(&gcps[..2]).is_err());
+    }
+
+    /// RPC with strong quadratic and cross terms over a 0.1 degree scene
+    fn nonlinear_rpc() -> RpcModel {
+        let mut coeffs = create_simple_rpc();
+        coeffs.lat_scale = 0.05;
+        coeffs.lon_scale = 0.05;
+        coeffs.line_num_coeff[1] = -1.0;
+        coeffs.line_num_coeff[2] = 0.1;
+        coeffs.line_num_coeff[4] = 0.2;
+        coeffs.line_num_coeff[7] = 0.3;
+        coeffs.samp_num_coeff[1] = 0.05;
+        coeffs.samp_num_coeff[8] = -0.25;
+        coeffs.samp_num_coeff[4] = 0.15;
+        coeffs.samp_den_coeff[2] = 0.1;
+        RpcModel::new(coeffs)
+    }
+
+    #[test]
+    fn test_image_to_lla_linear_initial_guess() {
+        let rpc = nonlinear_rpc();
+        let corner = LlaCoord { lat: 38.953, lon: -76.952, alt: 100.0 };
+        let (line, samp) = rpc.lla_to_image(&corner).unwrap();
+
+        let center = (rpc.coeffs.lat_off, rpc.coeffs.lon_off);
+        let linear = rpc.linear_inverse(line, samp, 100.0);
+        let (_, from_center) = rpc.newton_image_to_lla(line, samp, 100.0, center).unwrap();
+        let (lla, from_linear) = rpc.newton_image_to_lla(line, samp, 100.0, linear).unwrap();
+        assert!(from_linear < from_center);
+        assert!((lla.lat - corner.lat).abs() < 1e-8);
+        assert!((lla.lon - corner.lon).abs() < 1e-8);
+
+        // The default options use the linear guess
+        let default = rpc.image_to_lla(line, samp, 100.0).unwrap();
+        assert!((default.lat - corner.lat).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_image_to_lla_explicit_initial_guess() {
+        let rpc = nonlinear_rpc();
+        let target = LlaCoord { lat: 39.02, lon: -77.03, alt: 250.0 };
+        let (line, samp) = rpc.lla_to_image(&target).unwrap();
+
+        let options = ImageToGroundOptions { initial_guess: Some(target) };
+        let lla = rpc.image_to_lla_with(line, samp, 250.0, &options).unwrap();
+        assert!((lla.lat - target.lat).abs() < 1e-8);
+        assert!((lla.lon - target.lon).abs() < 1e-8);
+
+        let ground = rpc.image_to_ground_with(line, samp, 250.0, &options).unwrap();
+        assert!((ground - lla_to_ecef(&lla).unwrap()).norm() < 1e-6);
+    }
+
+    #[test]
+    fn test_image_to_lla_with_residual() {
+        let rpc = nonlinear_rpc();
+        let target = LlaCoord { lat: 38.97, lon: -76.96, alt: 80.0 };
+        let (line, samp) = rpc.lla_to_image(&target).unwrap();
+
+        let (lla, residual) = rpc.image_to_lla_with_residual(line, samp, 80.0).unwrap();
+        assert!(residual < INVERSE_PIXEL_TOLERANCE);
+        assert!((lla.lat - target.lat).abs() < 1e-8);
+        assert!((lla.lon - target.lon).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_validate_good_rpc() {
+        assert!(RpcModel::new(create_simple_rpc()).validate().is_ok());
+        assert!(nonlinear_rpc().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_rpc() {
+        let mut coeffs = create_simple_rpc();
+        coeffs.lat_scale = 0.0;
+        match RpcModel::new(coeffs).validate() {
+            Err(RspError::InvalidInput(msg)) => assert!(msg.contains("lat_scale")),
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+
+        let mut coeffs = create_simple_rpc();
+        coeffs.samp_den_coeff[0] = 0.0;
+        coeffs.samp_den_coeff[1] = 1.0;
+        match RpcModel::new(coeffs).validate() {
+            Err(RspError::InvalidInput(msg)) => assert!(msg.contains("sample denominator")),
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_try_new_rejects_zero_scale() {
+        assert!(RpcModel::try_new(create_simple_rpc()).is_ok());
+
+        // Unchecked, a zero scale only shows up as NaN pixels
+        let mut coeffs = create_simple_rpc();
+        coeffs.lat_scale = 0.0;
+        let center = LlaCoord { lat: coeffs.lat_off + 0.01, lon: coeffs.lon_off, alt: 0.0 };
+        let (line, _) = RpcModel::new(coeffs.clone()).lla_to_image(&center).unwrap();
+        assert!(line.is_nan());
+
+        match RpcModel::try_new(coeffs) {
+            Err(RspError::InvalidInput(msg)) => assert!(msg.contains("lat_scale"), "{}", msg),
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+
+        let mut coeffs = create_simple_rpc();
+        coeffs.samp_off = f64::NAN;
+        assert!(matches!(coeffs.validate(), Err(RspError::InvalidInput(_))));
+    }
 }
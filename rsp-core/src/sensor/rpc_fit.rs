@@ -0,0 +1,245 @@
+//! Fitting RPCs to ground/image correspondences
+
+use nalgebra::{DMatrix, DVector};
+
+use super::rpc::polynomial_terms;
+use super::RpcCoefficients;
+use crate::coordinate::LlaCoord;
+use crate::error::{Result, RspError};
+
+/// Unknowns per image coordinate: 20 numerator + 19 denominator terms
+const UNKNOWNS: usize = 39;
+
+/// Offsets and scales used to normalize ground and image coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RpcNormalization {
+    pub lat_off: f64,
+    pub lat_scale: f64,
+    pub lon_off: f64,
+    pub lon_scale: f64,
+    pub height_off: f64,
+    pub height_scale: f64,
+    pub line_off: f64,
+    pub line_scale: f64,
+    pub samp_off: f64,
+    pub samp_scale: f64,
+}
+
+impl RpcNormalization {
+    /// Derive a normalization from the extent of the correspondences
+    ///
+    /// Offsets are the center of each coordinate's range and scales its
+    /// half-width, so the samples fall in [-1, 1]. Degenerate (constant)
+    /// coordinates get a scale of 1.
+    pub fn from_correspondences(correspondences: &[(LlaCoord, (f64, f64))]) -> Self {
+        let range = |values: &mut dyn Iterator<Item = f64>| {
+            let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                (lo.min(v), hi.max(v))
+            });
+            if !min.is_finite() || !max.is_finite() {
+                return (0.0, 1.0);
+            }
+            let half = 0.5 * (max - min);
+            (0.5 * (min + max), if half > 0.0 { half } else { 1.0 })
+        };
+
+        let (lat_off, lat_scale) = range(&mut correspondences.iter().map(|c| c.0.lat));
+        let (lon_off, lon_scale) = range(&mut correspondences.iter().map(|c| c.0.lon));
+        let (height_off, height_scale) = range(&mut correspondences.iter().map(|c| c.0.alt));
+        let (line_off, line_scale) = range(&mut correspondences.iter().map(|c| c.1.0));
+        let (samp_off, samp_scale) = range(&mut correspondences.iter().map(|c| c.1.1));
+
+        Self {
+            lat_off,
+            lat_scale,
+            lon_off,
+            lon_scale,
+            height_off,
+            height_scale,
+            line_off,
+            line_scale,
+            samp_off,
+            samp_scale,
+        }
+    }
+}
+
+/// Fit RPC coefficients to ground/image correspondences
+///
+/// Correspondences are `(ground, (line, sample))`. With the denominator
+/// constant fixed to 1, `r = Num(P, L, H) / Den(P, L, H)` becomes linear in
+/// the remaining 39 coefficients as `Num - r * (Den - 1) = r`, which is solved
+/// by least squares (SVD) separately for line and sample in normalized
+/// coordinates. Terms the samples can't constrain (e.g. height terms when all
+/// points share one height) are left at zero.
+///
+/// Needs at least 39 correspondences; use a 3-D grid spanning the full
+/// height range for a well-conditioned fit.
+pub fn fit_rpc(
+    correspondences: &[(LlaCoord, (f64, f64))],
+    normalization: &RpcNormalization,
+) -> Result<RpcCoefficients> {
+    if correspondences.len() < UNKNOWNS {
+        return Err(RspError::InvalidInput(format!(
+            "RPC fit needs at least {} correspondences, got {}",
+            UNKNOWNS,
+            correspondences.len()
+        )));
+    }
+
+    let n = normalization;
+    let rows = correspondences.len();
+    let mut terms = Vec::with_capacity(rows);
+    let mut line_obs = DVector::zeros(rows);
+    let mut samp_obs = DVector::zeros(rows);
+
+    for (i, (ground, (line, samp))) in correspondences.iter().enumerate() {
+        let p = (ground.lon - n.lon_off) / n.lon_scale;
+        let l = (ground.lat - n.lat_off) / n.lat_scale;
+        let h = (ground.alt - n.height_off) / n.height_scale;
+        terms.push(polynomial_terms(p, l, h));
+        line_obs[i] = (line - n.line_off) / n.line_scale;
+        samp_obs[i] = (samp - n.samp_off) / n.samp_scale;
+    }
+
+    let (line_num_coeff, line_den_coeff) = solve_rational(&terms, &line_obs)?;
+    let (samp_num_coeff, samp_den_coeff) = solve_rational(&terms, &samp_obs)?;
+
+    Ok(RpcCoefficients {
+        line_num_coeff,
+        line_den_coeff,
+        samp_num_coeff,
+        samp_den_coeff,
+        lat_off: n.lat_off,
+        lat_scale: n.lat_scale,
+        lon_off: n.lon_off,
+        lon_scale: n.lon_scale,
+        height_off: n.height_off,
+        height_scale: n.height_scale,
+        line_off: n.line_off,
+        line_scale: n.line_scale,
+        samp_off: n.samp_off,
+        samp_scale: n.samp_scale,
+        ..Default::default()
+    })
+}
+
+/// Least-squares numerator/denominator for one normalized image coordinate
+fn solve_rational(terms: &[[f64; 20]], obs: &DVector<f64>) -> Result<([f64; 20], [f64; 20])> {
+    let a = DMatrix::from_fn(terms.len(), UNKNOWNS, |row, col| {
+        if col < 20 {
+            terms[row][col]
+        } else {
+            -obs[row] * terms[row][col - 19]
+        }
+    });
+
+    let x = a
+        .svd(true, true)
+        .solve(obs, 1e-12)
+        .map_err(|e| RspError::Numerical(format!("RPC least squares failed: {}", e)))?;
+
+    let mut num = [0.0; 20];
+    let mut den = [0.0; 20];
+    num.copy_from_slice(&x.as_slice()[..20]);
+    den[0] = 1.0;
+    den[1..].copy_from_slice(&x.as_slice()[20..]);
+
+    Ok((num, den))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sensor::RpcModel;
+
+    /// RPC with cross terms and a non-trivial denominator
+    fn reference_rpc() -> RpcModel {
+        let mut coeffs = RpcCoefficients {
+            lat_off: 39.0,
+            lat_scale: 0.05,
+            lon_off: -77.0,
+            lon_scale: 0.06,
+            height_off: 200.0,
+            height_scale: 400.0,
+            line_off: 8000.0,
+            line_scale: 8000.0,
+            samp_off: 9000.0,
+            samp_scale: 9000.0,
+            ..Default::default()
+        };
+
+        coeffs.line_num_coeff[1] = -1.0;
+        coeffs.line_num_coeff[2] = 0.02;
+        coeffs.line_num_coeff[3] = 0.05;
+        coeffs.line_num_coeff[4] = 0.003;
+        coeffs.line_num_coeff[7] = -0.002;
+        coeffs.line_den_coeff[0] = 1.0;
+        coeffs.line_den_coeff[1] = 0.001;
+        coeffs.line_den_coeff[3] = -0.002;
+
+        coeffs.samp_num_coeff[2] = 1.0;
+        coeffs.samp_num_coeff[1] = 0.01;
+        coeffs.samp_num_coeff[3] = 0.2;
+        coeffs.samp_num_coeff[8] = 0.004;
+        coeffs.samp_num_coeff[15] = -0.001;
+        coeffs.samp_den_coeff[0] = 1.0;
+        coeffs.samp_den_coeff[2] = 0.002;
+
+        RpcModel::new(coeffs)
+    }
+
+    fn sample_grid(rpc: &RpcModel, n: usize, inset: f64) -> Vec<(LlaCoord, (f64, f64))> {
+        let t = |v: usize| inset + (1.0 - 2.0 * inset) * v as f64 / (n - 1) as f64;
+        let mut out = Vec::new();
+        for i in 0..n {
+            for j in 0..n {
+                for k in 0..n {
+                    let ground = LlaCoord {
+                        lat: 38.95 + 0.1 * t(i),
+                        lon: -77.06 + 0.12 * t(j),
+                        alt: -200.0 + 800.0 * t(k),
+                    };
+                    out.push((ground, rpc.lla_to_image(&ground).unwrap()));
+                }
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_fit_rpc_reproduces_projections() {
+        let reference = reference_rpc();
+        let samples = sample_grid(&reference, 6, 0.0);
+        let normalization = RpcNormalization::from_correspondences(&samples);
+
+        let fitted = RpcModel::new(fit_rpc(&samples, &normalization).unwrap());
+
+        // Check points between the fitting grid nodes
+        for (ground, (line, samp)) in sample_grid(&reference, 5, 0.1) {
+            let (l, s) = fitted.lla_to_image(&ground).unwrap();
+            assert!((l - line).abs() < 0.01);
+            assert!((s - samp).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_normalization_from_extent() {
+        let samples = sample_grid(&reference_rpc(), 3, 0.0);
+        let n = RpcNormalization::from_correspondences(&samples);
+
+        assert!((n.lat_off - 39.0).abs() < 1e-12);
+        assert!((n.lat_scale - 0.05).abs() < 1e-12);
+        assert!((n.height_off - 200.0).abs() < 1e-12);
+        assert!((n.height_scale - 400.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fit_rpc_too_few_points() {
+        let samples = sample_grid(&reference_rpc(), 3, 0.0);
+        let normalization = RpcNormalization::from_correspondences(&samples);
+
+        let result = fit_rpc(&samples, &normalization);
+        assert!(matches!(result, Err(RspError::InvalidInput(_))));
+    }
+}
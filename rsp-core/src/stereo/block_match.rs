@@ -0,0 +1,406 @@
+use ndarray::Array2;
+
+use super::subpixel::parabola_offset;
+use super::DisparityMap;
+
+/// Settings for SAD block matching
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockMatchOptions {
+    /// Half-size of the square matching window (window is `2 * radius + 1` wide)
+    pub radius: usize,
+    /// Smallest disparity searched at full resolution
+    pub min_disparity: i32,
+    /// Largest disparity searched at full resolution
+    pub max_disparity: i32,
+    /// Search half-range around the upsampled guess at finer pyramid levels
+    pub refine_range: i32,
+}
+
+impl Default for BlockMatchOptions {
+    fn default() -> Self {
+        Self {
+            radius: 3,
+            min_disparity: 0,
+            max_disparity: 64,
+            refine_range: 2,
+        }
+    }
+}
+
+/// Dense disparity by sum-of-absolute-differences block matching
+///
+/// Searches `min_disparity..=max_disparity` for every left pixel, where the
+/// left pixel `(row, col)` is compared with the right pixel `(row, col - d)`.
+/// The winning disparity is refined to sub-pixel precision with a parabola
+/// fit. Pixels whose window doesn't fit in either image are NaN.
+pub fn block_match(left: &Array2<u8>, right: &Array2<u8>, opts: BlockMatchOptions) -> Array2<f32> {
+    match_in_range(left, right, opts.radius, |_, _| {
+        Some((opts.min_disparity, opts.max_disparity))
+    })
+}
+
+/// Build a Gaussian-style image pyramid by repeated 2x2 box averaging
+///
+/// Level 0 is the input. Stops early once a level would be smaller than
+/// 2 pixels on a side, so fewer than `levels` images may be returned.
+pub fn build_pyramid(image: &Array2<u8>, levels: usize) -> Vec<Array2<u8>> {
+    let mut pyramid = vec![image.clone()];
+
+    while pyramid.len() < levels {
+        let prev = pyramid.last().expect("pyramid is non-empty");
+        let (rows, cols) = (prev.nrows() / 2, prev.ncols() / 2);
+        if rows < 2 || cols < 2 {
+            break;
+        }
+
+        let next = Array2::from_shape_fn((rows, cols), |(r, c)| {
+            let sum = prev[[2 * r, 2 * c]] as u32
+                + prev[[2 * r, 2 * c + 1]] as u32
+                + prev[[2 * r + 1, 2 * c]] as u32
+                + prev[[2 * r + 1, 2 * c + 1]] as u32;
+            ((sum + 2) / 4) as u8
+        });
+        pyramid.push(next);
+    }
+
+    pyramid
+}
+
+/// Coarse-to-fine block matching over an image pyramid
+///
+/// Matches the coarsest level over the full (scaled) disparity range, then at
+/// each finer level upsamples and doubles the disparity as a per-pixel guess
+/// and searches only `refine_range` around it. Pixels without a guess fall
+/// back to the full range. Large disparities stay cheap to find without a
+/// wide search at full resolution.
+pub fn disparity_pyramid(
+    left: &Array2<u8>,
+    right: &Array2<u8>,
+    levels: usize,
+    opts: BlockMatchOptions,
+) -> Array2<f32> {
+    let left_pyramid = build_pyramid(left, levels.max(1));
+    let right_pyramid = build_pyramid(right, left_pyramid.len());
+    let top = left_pyramid.len().min(right_pyramid.len()) - 1;
+
+    // Full disparity range expressed at a pyramid level
+    let level_range = |level: usize| {
+        let scale = 1i32 << level;
+        (
+            opts.min_disparity.div_euclid(scale),
+            -(-opts.max_disparity).div_euclid(scale),
+        )
+    };
+
+    let (lo, hi) = level_range(top);
+    let mut disparity = match_in_range(
+        &left_pyramid[top],
+        &right_pyramid[top],
+        opts.radius,
+        |_, _| Some((lo, hi)),
+    );
+
+    for level in (0..top).rev() {
+        let coarse = disparity;
+        let (lo, hi) = level_range(level);
+
+        disparity = match_in_range(
+            &left_pyramid[level],
+            &right_pyramid[level],
+            opts.radius,
+            |row, col| {
+                let r = (row / 2).min(coarse.nrows() - 1);
+                let c = (col / 2).min(coarse.ncols() - 1);
+                let guess = coarse[[r, c]];
+                if !guess.is_finite() {
+                    return Some((lo, hi));
+                }
+
+                let center = (2.0 * guess).round() as i32;
+                let start = (center - opts.refine_range).max(lo);
+                let end = (center + opts.refine_range).min(hi);
+                (start <= end).then_some((start, end))
+            },
+        );
+    }
+
+    disparity
+}
+
+/// Coarse-to-fine disparity with a square `block`-pixel SAD window
+///
+/// Convenience over [`disparity_pyramid`]: `max_disp_coarse` is the largest
+/// disparity searched at the coarsest of `levels` levels, so the full
+/// resolution range is `0..=max_disp_coarse * 2^(levels - 1)`, and finer
+/// levels refine within the default `refine_range` of the upsampled prior.
+/// The range is capped at the image width, beyond which nothing can match.
+/// An even `block` is rounded up to the next odd size.
+pub fn coarse_to_fine_disparity(
+    left: &Array2<u8>,
+    right: &Array2<u8>,
+    levels: usize,
+    block: usize,
+    max_disp_coarse: i32,
+) -> DisparityMap {
+    let levels = levels.max(1);
+    // Saturate rather than overflow for many levels, then cap at the width
+    let scale = u32::try_from(levels - 1).ok().and_then(|exp| 2i32.checked_pow(exp));
+    let width = i32::try_from(left.ncols()).unwrap_or(i32::MAX);
+    let opts = BlockMatchOptions {
+        radius: block / 2,
+        min_disparity: 0,
+        max_disparity: max_disp_coarse.saturating_mul(scale.unwrap_or(i32::MAX)).min(width),
+        ..Default::default()
+    };
+
+    DisparityMap::new(disparity_pyramid(left, right, levels, opts))
+}
+
+/// SAD matching with a per-pixel inclusive disparity range
+fn match_in_range<F>(left: &Array2<u8>, right: &Array2<u8>, radius: usize, range: F) -> Array2<f32>
+where
+    F: Fn(usize, usize) -> Option<(i32, i32)>,
+{
+    let (rows, cols) = left.dim();
+    let mut out = Array2::from_elem((rows, cols), f32::NAN);
+    if !can_match(left, right, radius) {
+        return out;
+    }
+
+    let slice = out.as_slice_mut().expect("freshly allocated array is contiguous");
+    for (row, out_row) in slice.chunks_mut(cols).enumerate() {
+        match_row(left, right, radius, &range, row, out_row);
+    }
+    out
+}
+
+/// [`block_match`] with output rows striped across the rayon thread pool
+///
+/// Each worker fills whole rows, computing every cost exactly as the serial
+/// path does, so the result is bit-identical to [`block_match`].
+#[cfg(feature = "rayon")]
+pub fn block_match_parallel(
+    left: &Array2<u8>,
+    right: &Array2<u8>,
+    opts: BlockMatchOptions,
+) -> Array2<f32> {
+    use rayon::prelude::*;
+
+    let (rows, cols) = left.dim();
+    let mut out = Array2::from_elem((rows, cols), f32::NAN);
+    if !can_match(left, right, opts.radius) {
+        return out;
+    }
+
+    let range = |_, _| Some((opts.min_disparity, opts.max_disparity));
+    let slice = out.as_slice_mut().expect("freshly allocated array is contiguous");
+    slice.par_chunks_mut(cols).enumerate().for_each(|(row, out_row)| {
+        match_row(left, right, opts.radius, &range, row, out_row);
+    });
+    out
+}
+
+/// Whether any matching window fits in the pair
+fn can_match(left: &Array2<u8>, right: &Array2<u8>, radius: usize) -> bool {
+    let (rows, cols) = left.dim();
+    cols > 0 && rows > 2 * radius && right.nrows() >= rows
+}
+
+/// SAD matching of one output row; rows too close to the border stay NaN
+fn match_row<F>(
+    left: &Array2<u8>,
+    right: &Array2<u8>,
+    radius: usize,
+    range: &F,
+    row: usize,
+    out: &mut [f32],
+) where
+    F: Fn(usize, usize) -> Option<(i32, i32)>,
+{
+    let (rows, cols) = left.dim();
+    if row < radius || row + radius >= rows {
+        return;
+    }
+
+    let right_cols = right.ncols() as i64;
+    let r = radius as i64;
+    let sad = |col: usize, d: i32| -> Option<u32> {
+        let right_col = col as i64 - d as i64;
+        if right_col < r || right_col + r >= right_cols {
+            return None;
+        }
+        let right_col = right_col as usize;
+
+        let mut cost = 0u32;
+        for y in row - radius..=row + radius {
+            for dx in 0..=2 * radius {
+                let a = left[[y, col - radius + dx]];
+                let b = right[[y, right_col - radius + dx]];
+                cost += a.abs_diff(b) as u32;
+            }
+        }
+        Some(cost)
+    };
+
+    let inner = out.iter_mut().enumerate().take(cols.saturating_sub(radius)).skip(radius);
+    for (col, value) in inner {
+        let Some((lo, hi)) = range(row, col) else {
+            continue;
+        };
+
+        let mut best: Option<(i32, u32)> = None;
+        for d in lo..=hi {
+            if let Some(cost) = sad(col, d)
+                && best.is_none_or(|(_, best_cost)| cost < best_cost)
+            {
+                best = Some((d, cost));
+            }
+        }
+
+        let Some((d, cost)) = best else {
+            continue;
+        };
+
+        // Parabola through the neighboring costs, if both were searched
+        let mut offset = 0.0;
+        if d > lo
+            && d < hi
+            && let (Some(c_minus), Some(c_plus)) = (sad(col, d - 1), sad(col, d + 1))
+        {
+            offset = parabola_offset(c_minus as f32, cost as f32, c_plus as f32).unwrap_or(0.0);
+        }
+
+        *value = d as f32 + offset;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// [`test_util::texture`](crate::test_util::texture) at unsigned pixel positions
+    fn texture(row: usize, col: usize) -> u8 {
+        crate::test_util::texture(row as i64, col as i64)
+    }
+
+    /// Stereo pair where every left pixel matches `shift` columns to the left
+    fn shifted_pair(rows: usize, cols: usize, shift: usize) -> (Array2<u8>, Array2<u8>) {
+        let left = Array2::from_shape_fn((rows, cols), |(r, c)| texture(r, c + shift));
+        let right = Array2::from_shape_fn((rows, cols), |(r, c)| texture(r, c + 2 * shift));
+        // left(r, c) == right(r, c - shift)
+        (left, right)
+    }
+
+    #[test]
+    fn test_block_match_constant_shift() {
+        let (left, right) = shifted_pair(24, 48, 5);
+        let opts = BlockMatchOptions {
+            radius: 2,
+            min_disparity: 0,
+            max_disparity: 10,
+            refine_range: 2,
+        };
+
+        let disparity = block_match(&left, &right, opts);
+        for row in 2..22 {
+            for col in 20..46 {
+                assert!((disparity[[row, col]] - 5.0).abs() < 0.5);
+            }
+        }
+
+        // Windows that don't fit are invalid
+        assert!(disparity[[0, 30]].is_nan());
+        assert!(disparity[[10, 1]].is_nan());
+    }
+
+    #[test]
+    fn test_build_pyramid_box_average() {
+        let image = Array2::from_shape_fn((8, 6), |(r, c)| (r * 6 + c) as u8);
+        let pyramid = build_pyramid(&image, 5);
+
+        // 8x6 -> 4x3 -> stops (next level would be 2x1)
+        assert_eq!(pyramid.len(), 2);
+        assert_eq!(pyramid[1].dim(), (4, 3));
+        // mean of 0, 1, 6, 7 rounded
+        assert_eq!(pyramid[1][[0, 0]], 4);
+    }
+
+    #[test]
+    fn test_pyramid_recovers_large_shift() {
+        let shift = 40;
+        let (left, right) = shifted_pair(64, 160, shift);
+        let opts = BlockMatchOptions {
+            radius: 2,
+            min_disparity: 0,
+            max_disparity: 48,
+            refine_range: 2,
+        };
+
+        // A narrow search at full resolution can't reach the true disparity
+        let narrow = BlockMatchOptions {
+            max_disparity: 8,
+            ..opts
+        };
+        let single = block_match(&left, &right, narrow);
+        assert!(single[[32, 100]].is_finite());
+        assert!((single[[32, 100]] - shift as f32).abs() > 10.0);
+
+        let disparity = disparity_pyramid(&left, &right, 3, opts);
+        let mut checked = 0;
+        for row in 8..56 {
+            for col in 56..150 {
+                assert!((disparity[[row, col]] - shift as f32).abs() < 0.5);
+                checked += 1;
+            }
+        }
+        assert!(checked > 1000);
+    }
+
+    #[test]
+    fn test_coarse_to_fine_disparity_large_shift() {
+        let shift = 52;
+        let (left, right) = shifted_pair(64, 192, shift);
+
+        // Searching 0..=16 at full resolution can't reach a 52 pixel shift
+        let single = coarse_to_fine_disparity(&left, &right, 1, 5, 16);
+        assert!((single.get(32, 120).unwrap() - shift as f32).abs() > 10.0);
+
+        // The same range at a 4x coarser level covers 0..=64
+        let disparity = coarse_to_fine_disparity(&left, &right, 3, 5, 16);
+        for row in 8..56 {
+            for col in 64..184 {
+                let d = disparity.get(row, col).unwrap();
+                assert!((d - shift as f32).abs() < 0.5, "({}, {}): {}", row, col, d);
+            }
+        }
+
+        // More levels than the image supports saturate instead of overflowing
+        for levels in [33, usize::MAX] {
+            let disparity = coarse_to_fine_disparity(&left, &right, levels, 5, 16);
+            assert_eq!(disparity.size(), (192, 64));
+            assert!((disparity.get(32, 120).unwrap() - shift as f32).abs() < 0.5);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_block_match_parallel_matches_serial() {
+        // Non-constant disparity so the sub-pixel fits vary across rows
+        let left = Array2::from_shape_fn((57, 90), |(r, c)| texture(r, c + r / 8));
+        let right = Array2::from_shape_fn((57, 90), |(r, c)| texture(r, c + 2 * (r / 8)));
+        let opts = BlockMatchOptions {
+            radius: 3,
+            min_disparity: 0,
+            max_disparity: 12,
+            refine_range: 2,
+        };
+
+        let serial = block_match(&left, &right, opts);
+        let parallel = block_match_parallel(&left, &right, opts);
+        assert_eq!(serial.dim(), parallel.dim());
+        for (a, b) in serial.iter().zip(parallel.iter()) {
+            assert_eq!(a.to_bits(), b.to_bits());
+        }
+        assert!(serial.iter().filter(|d| d.is_finite()).count() > 2000);
+    }
+}
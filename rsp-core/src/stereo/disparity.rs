@@ -0,0 +1,54 @@
+use ndarray::Array2;
+
+/// Dense disparity map over the left image of a stereo pair
+///
+/// Disparities follow the usual convention: the left pixel `(row, col)`
+/// matches the right pixel `(row, col - d)`. Invalid pixels hold NaN.
+#[derive(Debug, Clone)]
+pub struct DisparityMap {
+    data: Array2<f32>,
+}
+
+impl DisparityMap {
+    /// Wrap a [height, width] disparity array (NaN marks invalid pixels)
+    pub fn new(data: Array2<f32>) -> Self {
+        Self { data }
+    }
+
+    /// Get map dimensions (width, height)
+    pub fn size(&self) -> (usize, usize) {
+        (self.data.ncols(), self.data.nrows())
+    }
+
+    /// Disparity at a pixel, or `None` if invalid or out of range
+    pub fn get(&self, row: usize, col: usize) -> Option<f32> {
+        self.data.get((row, col)).copied().filter(|d| d.is_finite())
+    }
+
+    /// Get reference to the underlying array
+    pub fn data(&self) -> &Array2<f32> {
+        &self.data
+    }
+
+    /// Consume the map and return the underlying array
+    pub fn into_data(self) -> Array2<f32> {
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disparity_map_access() {
+        let mut data = Array2::from_elem((2, 3), 4.5f32);
+        data[[1, 2]] = f32::NAN;
+        let map = DisparityMap::new(data);
+
+        assert_eq!(map.size(), (3, 2));
+        assert_eq!(map.get(0, 0), Some(4.5));
+        assert_eq!(map.get(1, 2), None);
+        assert_eq!(map.get(5, 0), None);
+    }
+}
@@ -0,0 +1,222 @@
+use ndarray::Array2;
+
+use super::DisparityMap;
+use crate::coordinate::{ecef_to_lla, EcefCoord};
+use crate::error::{Result, RspError};
+use crate::sensor::RpcModel;
+
+/// Maximum secant iterations when intersecting a pair of RPC rays
+const MAX_INTERSECTION_ITERS: usize = 10;
+
+/// Height tolerance (meters) for the ray intersection
+const HEIGHT_TOLERANCE: f64 = 1e-3;
+
+/// North-up geographic output grid for a DSM
+///
+/// Uses a GDAL-style geotransform where x is longitude and y is latitude
+/// (degrees). Rotated grids (non-zero `geotransform[2]`/`[4]`) are not
+/// supported.
+#[derive(Debug, Clone, Copy)]
+pub struct DsmGrid {
+    pub geotransform: [f64; 6],
+    pub width: usize,
+    pub height: usize,
+}
+
+impl DsmGrid {
+    /// Create a new grid from a geotransform and size
+    pub fn new(geotransform: [f64; 6], width: usize, height: usize) -> Self {
+        Self {
+            geotransform,
+            width,
+            height,
+        }
+    }
+
+    /// Map a longitude/latitude to the (row, col) cell containing it
+    pub fn cell_of(&self, lon: f64, lat: f64) -> Option<(usize, usize)> {
+        let gt = &self.geotransform;
+        let col = ((lon - gt[0]) / gt[1]).floor();
+        let row = ((lat - gt[3]) / gt[5]).floor();
+
+        if col < 0.0 || row < 0.0 || col >= self.width as f64 || row >= self.height as f64 {
+            return None;
+        }
+
+        Some((row as usize, col as usize))
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        let gt = &self.geotransform;
+        if self.width == 0 || self.height == 0 {
+            return Err(RspError::InvalidInput("DSM grid has zero size".to_string()));
+        }
+        if gt[1] == 0.0 || gt[5] == 0.0 || gt[2] != 0.0 || gt[4] != 0.0 {
+            return Err(RspError::InvalidInput(
+                "DSM grid geotransform must be north-up with non-zero resolution".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Convert a stereo disparity map to a gridded elevation model
+///
+/// For each valid left pixel `(line, sample)` the right pixel is
+/// `(line, sample - d)`. The ground point is found by intersecting the two RPC
+/// viewing rays: both pixels are projected with `image_to_ground` at trial
+/// heights and the height where the two ground points coincide is solved for
+/// with a secant iteration. Heights are averaged per output cell; cells with
+/// no samples are NaN.
+///
+/// Pixels whose rays can't be intersected (no parallax, projection failure)
+/// are skipped.
+pub fn disparity_to_dsm(
+    disparity: &DisparityMap,
+    rpc_left: &RpcModel,
+    rpc_right: &RpcModel,
+    grid: DsmGrid,
+) -> Result<Array2<f32>> {
+    grid.validate()?;
+
+    let mut sum = Array2::<f64>::zeros((grid.height, grid.width));
+    let mut count = Array2::<u32>::zeros((grid.height, grid.width));
+
+    let (width, height) = disparity.size();
+    for row in 0..height {
+        for col in 0..width {
+            let Some(d) = disparity.get(row, col) else {
+                continue;
+            };
+
+            let line = row as f64;
+            let left = (line, col as f64);
+            let right = (line, col as f64 - d as f64);
+
+            let Some(ground) = intersect_rays(rpc_left, left, rpc_right, right) else {
+                continue;
+            };
+            let Ok(lla) = ecef_to_lla(&ground) else {
+                continue;
+            };
+
+            if let Some(cell) = grid.cell_of(lla.lon, lla.lat) {
+                sum[cell] += lla.alt;
+                count[cell] += 1;
+            }
+        }
+    }
+
+    Ok(Array2::from_shape_fn((grid.height, grid.width), |cell| {
+        if count[cell] == 0 {
+            f32::NAN
+        } else {
+            (sum[cell] / count[cell] as f64) as f32
+        }
+    }))
+}
+
+/// Intersect two RPC viewing rays by searching along height
+///
+/// Returns the ECEF midpoint of the two ground points at the solved height.
+fn intersect_rays(
+    rpc_left: &RpcModel,
+    left: (f64, f64),
+    rpc_right: &RpcModel,
+    right: (f64, f64),
+) -> Option<EcefCoord> {
+    let separation = |h: f64| -> Option<(EcefCoord, EcefCoord)> {
+        let a = rpc_left.image_to_ground(left.0, left.1, h).ok()?;
+        let b = rpc_right.image_to_ground(right.0, right.1, h).ok()?;
+        Some((a, b))
+    };
+
+    let coeffs = rpc_left.coefficients();
+    let mut h_a = coeffs.height_off;
+    let mut h_b = coeffs.height_off + 0.5 * coeffs.height_scale.abs().max(1.0);
+
+    let (pa, pb) = separation(h_a)?;
+    let mut d_a = pa - pb;
+
+    for _ in 0..MAX_INTERSECTION_ITERS {
+        let (pa, pb) = separation(h_b)?;
+        let d_b = pa - pb;
+
+        // Linear model D(h) = d_a + (h - h_a) * s; minimize |D(h)|^2
+        let s = (d_b - d_a) / (h_b - h_a);
+        let s_norm2 = s.norm_squared();
+        if s_norm2 < 1e-18 {
+            return None;
+        }
+        let h_next = h_a - d_a.dot(&s) / s_norm2;
+
+        if (h_next - h_b).abs() < HEIGHT_TOLERANCE {
+            let (pa, pb) = separation(h_next)?;
+            return Some((pa + pb) / 2.0);
+        }
+
+        h_a = h_b;
+        d_a = d_b;
+        h_b = h_next;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinate::LlaCoord;
+    use crate::sensor::linear_rpc;
+
+    /// Linear RPC whose sample shifts with height by `parallax`
+    fn parallax_rpc(parallax: f64) -> RpcModel {
+        RpcModel::new(linear_rpc(0.01, 25.0, 100.0, parallax))
+    }
+
+    #[test]
+    fn test_flat_surface_height() {
+        let rpc_left = parallax_rpc(0.2);
+        let rpc_right = parallax_rpc(-0.2);
+        let surface = 300.0;
+
+        // Constant disparity: sample shift between the two views at the surface
+        let probe = LlaCoord { lat: 39.0, lon: -77.0, alt: surface };
+        let (_, samp_left) = rpc_left.lla_to_image(&probe).unwrap();
+        let (_, samp_right) = rpc_right.lla_to_image(&probe).unwrap();
+        let d = (samp_left - samp_right) as f32;
+
+        let disparity = DisparityMap::new(Array2::from_elem((50, 50), d));
+
+        let grid = DsmGrid::new([-77.004, 0.0002, 0.0, 39.0026, 0.0, -0.0002], 30, 30);
+        let dsm = disparity_to_dsm(&disparity, &rpc_left, &rpc_right, grid).unwrap();
+
+        let valid: Vec<f32> = dsm.iter().copied().filter(|h| h.is_finite()).collect();
+        assert!(valid.len() > 100);
+        for h in valid {
+            assert!((h as f64 - surface).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_invalid_disparity_skipped() {
+        let rpc_left = parallax_rpc(0.2);
+        let rpc_right = parallax_rpc(-0.2);
+
+        let disparity = DisparityMap::new(Array2::from_elem((10, 10), f32::NAN));
+        let grid = DsmGrid::new([-77.004, 0.0002, 0.0, 39.0026, 0.0, -0.0002], 30, 30);
+        let dsm = disparity_to_dsm(&disparity, &rpc_left, &rpc_right, grid).unwrap();
+
+        assert!(dsm.iter().all(|h| h.is_nan()));
+    }
+
+    #[test]
+    fn test_rotated_grid_rejected() {
+        let rpc = parallax_rpc(0.2);
+        let disparity = DisparityMap::new(Array2::zeros((2, 2)));
+        let grid = DsmGrid::new([0.0, 1.0, 0.5, 0.0, 0.0, -1.0], 4, 4);
+
+        let result = disparity_to_dsm(&disparity, &rpc, &rpc, grid);
+        assert!(matches!(result, Err(RspError::InvalidInput(_))));
+    }
+}
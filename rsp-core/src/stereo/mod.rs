@@ -0,0 +1,21 @@
+//! Stereo matching and reconstruction
+
+mod block_match;
+mod disparity;
+mod dsm;
+mod normals;
+mod pointcloud;
+mod rig;
+mod subpixel;
+
+#[cfg(feature = "rayon")]
+pub use block_match::block_match_parallel;
+pub use block_match::{
+    block_match, build_pyramid, coarse_to_fine_disparity, disparity_pyramid, BlockMatchOptions,
+};
+pub use disparity::DisparityMap;
+pub use dsm::{disparity_to_dsm, DsmGrid};
+pub use normals::depth_to_normals;
+pub use pointcloud::stereo_pointcloud;
+pub use rig::StereoRig;
+pub use subpixel::refine_subpixel;
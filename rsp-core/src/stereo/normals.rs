@@ -0,0 +1,103 @@
+use ndarray::{Array2, Array3};
+
+/// Per-pixel surface normals of a depth map
+///
+/// Depth gradients are taken by central differences (one-sided next to the
+/// border or an invalid neighbor) and turned into camera-frame tangents
+/// `(Z / fx, 0, dZ/du)` and `(0, Z / fy, dZ/dv)`, whose cross product is the
+/// normal. The tangents leave out the `(u - cx) / fx * dZ/du` perspective
+/// term, which is exact at the principal point and small for narrow fields
+/// of view.
+///
+/// Returns unit normals (shape: [height, width, 3]) oriented towards the
+/// camera, so a frontal plane gives `(0, 0, -1)`. Pixels with a non-finite
+/// or non-positive depth, or no valid neighbor along an axis, are NaN.
+pub fn depth_to_normals(depth: &Array2<f32>, fx: f64, fy: f64) -> Array3<f32> {
+    let (rows, cols) = depth.dim();
+    let mut normals = Array3::from_elem((rows, cols, 3), f32::NAN);
+
+    let valid = |r: usize, c: usize| {
+        let z = depth[[r, c]];
+        (z.is_finite() && z > 0.0).then_some(z as f64)
+    };
+
+    // Derivative from whichever neighbors along one axis are valid
+    let gradient = |prev: Option<f64>, z: f64, next: Option<f64>| match (prev, next) {
+        (Some(p), Some(n)) => Some(0.5 * (n - p)),
+        (None, Some(n)) => Some(n - z),
+        (Some(p), None) => Some(z - p),
+        (None, None) => None,
+    };
+
+    for r in 0..rows {
+        for c in 0..cols {
+            let Some(z) = valid(r, c) else {
+                continue;
+            };
+
+            let left = if c > 0 { valid(r, c - 1) } else { None };
+            let right = if c + 1 < cols { valid(r, c + 1) } else { None };
+            let up = if r > 0 { valid(r - 1, c) } else { None };
+            let down = if r + 1 < rows { valid(r + 1, c) } else { None };
+
+            let (Some(dz_du), Some(dz_dv)) = (gradient(left, z, right), gradient(up, z, down))
+            else {
+                continue;
+            };
+
+            // (0, Z/fy, Zv) x (Z/fx, 0, Zu)
+            let n = [
+                dz_du * z / fy,
+                dz_dv * z / fx,
+                -z * z / (fx * fy),
+            ];
+            let norm = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+            for (k, value) in n.iter().enumerate() {
+                normals[[r, c, k]] = (value / norm) as f32;
+            }
+        }
+    }
+
+    normals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depth_to_normals_frontal_plane() {
+        let mut depth = Array2::from_elem((5, 6), 12.5f32);
+        depth[[2, 3]] = f32::NAN;
+
+        let normals = depth_to_normals(&depth, 800.0, 820.0);
+        assert_eq!(normals.dim(), (5, 6, 3));
+
+        for r in 0..5 {
+            for c in 0..6 {
+                let n = [normals[[r, c, 0]], normals[[r, c, 1]], normals[[r, c, 2]]];
+                if (r, c) == (2, 3) {
+                    assert!(n.iter().all(|v| v.is_nan()));
+                } else {
+                    assert_eq!(n, [0.0, 0.0, -1.0], "pixel ({}, {})", r, c);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_depth_to_normals_tilted_plane() {
+        // Depth rising to the right tilts the normal towards +x
+        let (fx, fy) = (500.0, 500.0);
+        let depth = Array2::from_shape_fn((4, 4), |(_, c)| (10.0 + 0.02 * c as f64) as f32);
+        let normals = depth_to_normals(&depth, fx, fy);
+
+        // Tangents (Z/fx, 0, 0.02) and (0, Z/fy, 0) at Z = 10.02
+        let z = 10.02f64;
+        let expected = nalgebra::Vector3::new(0.02 * z / fy, 0.0, -z * z / (fx * fy)).normalize();
+        for k in 0..3 {
+            assert!((normals[[1, 1, k]] as f64 - expected[k]).abs() < 1e-5);
+        }
+        assert!(normals[[1, 1, 0]] > 0.0);
+    }
+}
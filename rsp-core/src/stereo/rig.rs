@@ -0,0 +1,100 @@
+use nalgebra::{Matrix4, Rotation3, Vector3};
+
+use crate::camera::PinholeCamera;
+
+/// Calibrated stereo pair
+///
+/// `rotation` and `translation` map points from the left camera frame into
+/// the right camera frame (`x_right = rotation * x_left + translation`), so
+/// a right camera sitting a baseline `b` to the right of the left one has
+/// `translation = (-b, 0, 0)`.
+#[derive(Debug, Clone)]
+pub struct StereoRig {
+    pub left: PinholeCamera,
+    pub right: PinholeCamera,
+    pub rotation: Rotation3<f64>,
+    pub translation: Vector3<f64>,
+}
+
+impl StereoRig {
+    pub fn new(
+        left: PinholeCamera,
+        right: PinholeCamera,
+        rotation: Rotation3<f64>,
+        translation: Vector3<f64>,
+    ) -> Self {
+        Self { left, right, rotation, translation }
+    }
+
+    /// Distance between the camera centers, in the translation's units
+    pub fn baseline(&self) -> f64 {
+        self.translation.norm()
+    }
+
+    /// Disparity-to-depth reprojection matrix for a rectified rig
+    ///
+    /// `Q * [col, row, d, 1]` is the homogeneous left-camera point seen at a
+    /// left pixel with disparity `d = col_left - col_right`, as used by
+    /// [`stereo_pointcloud`](super::stereo_pointcloud). Uses the left
+    /// camera's focal length and principal point, the right camera's
+    /// principal column and the x component of the translation, so it only
+    /// holds for a rectified rig (identity rotation, translation along x);
+    /// the translation's x must be non-zero.
+    pub fn q_matrix(&self) -> Matrix4<f64> {
+        let (f, _) = self.left.focal_length();
+        let (cx, cy) = self.left.principal_point();
+        let (cx_right, _) = self.right.principal_point();
+        let tx = self.translation.x;
+
+        Matrix4::new(
+            1.0, 0.0, 0.0, -cx,
+            0.0, 1.0, 0.0, -cy,
+            0.0, 0.0, 0.0, f,
+            0.0, 0.0, -1.0 / tx, (cx - cx_right) / tx,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::CameraModel;
+    use nalgebra::Vector4;
+
+    fn rig(cx_right: f64) -> StereoRig {
+        let left = PinholeCamera::new_ideal(640, 480, 800.0, 800.0, 320.0, 240.0);
+        let right = PinholeCamera::new_ideal(640, 480, 800.0, 800.0, cx_right, 240.0);
+        StereoRig::new(left, right, Rotation3::identity(), Vector3::new(-0.12, 0.0, 0.0))
+    }
+
+    #[test]
+    fn test_stereo_rig_q_matrix_structure() {
+        let rig = rig(320.0);
+        assert!((rig.baseline() - 0.12).abs() < 1e-15);
+
+        let q = rig.q_matrix();
+        let expected = Matrix4::new(
+            1.0, 0.0, 0.0, -320.0,
+            0.0, 1.0, 0.0, -240.0,
+            0.0, 0.0, 0.0, 800.0,
+            0.0, 0.0, 1.0 / 0.12, 0.0,
+        );
+        assert!((q - expected).abs().max() < 1e-12);
+    }
+
+    #[test]
+    fn test_stereo_rig_q_matrix_reprojects_points() {
+        // A shifted right principal point changes the disparity offset too
+        let rig = rig(335.0);
+        let q = rig.q_matrix();
+
+        for point in [Vector3::new(0.3, -0.2, 5.0), Vector3::new(-1.1, 0.4, 12.5)] {
+            let (col, row) = rig.left.project(&point).unwrap();
+            let right_point = rig.rotation * point + rig.translation;
+            let (col_right, _) = rig.right.project(&right_point).unwrap();
+
+            let h = q * Vector4::new(col, row, col - col_right, 1.0);
+            assert!((h.xyz() / h.w - point).norm() < 1e-9);
+        }
+    }
+}
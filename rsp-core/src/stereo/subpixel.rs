@@ -0,0 +1,88 @@
+use ndarray::{Array2, Array3};
+
+/// Refine integer disparities to sub-pixel precision with a parabola fit
+///
+/// `cost_volume[[row, col, d]]` is the matching cost of disparity index `d`
+/// (lower is better) and `integer_disp` holds the winning index per pixel.
+/// A parabola through the costs at `d - 1`, `d` and `d + 1` shifts each
+/// disparity to its vertex. Invalid (NaN) pixels, disparities at either end
+/// of the volume and pixels whose costs don't form a strict minimum are
+/// returned unchanged.
+pub fn refine_subpixel(cost_volume: &Array3<f32>, integer_disp: &Array2<f32>) -> Array2<f32> {
+    let (rows, cols, levels) = cost_volume.dim();
+
+    Array2::from_shape_fn(integer_disp.dim(), |(row, col)| {
+        let disp = integer_disp[[row, col]];
+        if !disp.is_finite() || row >= rows || col >= cols {
+            return disp;
+        }
+
+        let d = disp.round();
+        if d < 1.0 || d + 1.0 >= levels as f32 {
+            return disp;
+        }
+
+        let d = d as usize;
+        let cost = |level: usize| cost_volume[[row, col, level]];
+        match parabola_offset(cost(d - 1), cost(d), cost(d + 1)) {
+            Some(offset) => d as f32 + offset,
+            None => disp,
+        }
+    })
+}
+
+/// Vertex offset of the parabola through three equally spaced costs
+///
+/// Returns `None` unless the costs are finite and curve upward, so that the
+/// offset lies within half a step of the center.
+pub(super) fn parabola_offset(c_minus: f32, c0: f32, c_plus: f32) -> Option<f32> {
+    let denom = c_minus - 2.0 * c0 + c_plus;
+    if denom <= 0.0 || !denom.is_finite() {
+        return None;
+    }
+
+    let offset = (c_minus - c_plus) / (2.0 * denom);
+    (offset.abs() <= 0.5).then_some(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refine_subpixel_recovers_minimum() {
+        let (rows, cols, levels) = (6, 8, 16);
+        let truth = |row: usize, col: usize| 3.0 + 0.37 * (row * cols + col) as f32 / 5.0;
+
+        // Quadratic cost around the true disparity, integer winner by rounding
+        let volume = Array3::from_shape_fn((rows, cols, levels), |(r, c, d)| {
+            let dx = d as f32 - truth(r, c);
+            2.0 + 1.5 * dx * dx
+        });
+        let integer = Array2::from_shape_fn((rows, cols), |(r, c)| truth(r, c).round());
+
+        let refined = refine_subpixel(&volume, &integer);
+        for ((r, c), &d) in refined.indexed_iter() {
+            assert!((d - truth(r, c)).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn test_refine_subpixel_leaves_edges_and_invalid() {
+        let volume = Array3::from_shape_fn((1, 3, 4), |(_, _, d)| d as f32);
+        let integer = Array2::from_shape_vec((1, 3), vec![0.0, 3.0, f32::NAN]).unwrap();
+
+        let refined = refine_subpixel(&volume, &integer);
+        assert_eq!(refined[[0, 0]], 0.0);
+        assert_eq!(refined[[0, 1]], 3.0);
+        assert!(refined[[0, 2]].is_nan());
+    }
+
+    #[test]
+    fn test_refine_subpixel_flat_cost_unchanged() {
+        let volume = Array3::from_elem((1, 1, 5), 7.0f32);
+        let integer = Array2::from_elem((1, 1), 2.0f32);
+
+        assert_eq!(refine_subpixel(&volume, &integer)[[0, 0]], 2.0);
+    }
+}
@@ -0,0 +1,10 @@
+//! Shared test fixtures
+
+/// Deterministic high-frequency texture, for matching tests
+pub(crate) fn texture(row: i64, col: i64) -> u8 {
+    let mut h = (row as u32).wrapping_mul(0x9E37_79B1) ^ (col as u32).wrapping_mul(0x85EB_CA77);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0xC2B2_AE3D);
+    h ^= h >> 13;
+    (h & 0xFF) as u8
+}
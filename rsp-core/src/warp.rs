@@ -0,0 +1,254 @@
+//! Image resampling through per-pixel coordinate maps
+
+use ndarray::{Array2, ArrayView2};
+
+/// Interpolation kernel used when sampling between pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interp {
+    /// Value of the closest pixel
+    Nearest,
+    /// Weighted average of the 2x2 neighborhood
+    Bilinear,
+    /// Catmull-Rom spline over the 4x4 neighborhood
+    Bicubic,
+}
+
+/// Pluggable interpolation used by the warping and resampling routines
+///
+/// `x` is the column (sample) and `y` the row (line) in `img`. Positions
+/// outside `[0, cols - 1]` x `[0, rows - 1]` must give NaN, which callers
+/// treat as no data.
+pub trait Resampler: Send + Sync {
+    /// Interpolated value at `(x, y)`
+    fn sample(&self, img: &ArrayView2<f32>, x: f64, y: f64) -> f32;
+}
+
+/// Value of the closest pixel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NearestResampler;
+
+/// Weighted average of the 2x2 neighborhood
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BilinearResampler;
+
+/// Catmull-Rom spline over the 4x4 neighborhood, clamped at the edges
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BicubicResampler;
+
+impl Resampler for NearestResampler {
+    fn sample(&self, img: &ArrayView2<f32>, x: f64, y: f64) -> f32 {
+        if !in_bounds(img, x, y) {
+            return f32::NAN;
+        }
+        img[[y.round() as usize, x.round() as usize]]
+    }
+}
+
+impl Resampler for BilinearResampler {
+    fn sample(&self, img: &ArrayView2<f32>, x: f64, y: f64) -> f32 {
+        if !in_bounds(img, x, y) {
+            return f32::NAN;
+        }
+        sample_bilinear(img, y, x)
+    }
+}
+
+impl Resampler for BicubicResampler {
+    fn sample(&self, img: &ArrayView2<f32>, x: f64, y: f64) -> f32 {
+        if !in_bounds(img, x, y) {
+            return f32::NAN;
+        }
+        sample_bicubic(img, y, x)
+    }
+}
+
+impl Resampler for Interp {
+    fn sample(&self, img: &ArrayView2<f32>, x: f64, y: f64) -> f32 {
+        match self {
+            Interp::Nearest => NearestResampler.sample(img, x, y),
+            Interp::Bilinear => BilinearResampler.sample(img, x, y),
+            Interp::Bicubic => BicubicResampler.sample(img, x, y),
+        }
+    }
+}
+
+/// Resample an image through a coordinate map
+///
+/// `map[[row, col]]` is the `(line, sample)` position in `src` that output
+/// pixel `(row, col)` is taken from. Positions outside `[0, rows - 1]` x
+/// `[0, cols - 1]` give NaN. Bicubic clamps its neighborhood at the image
+/// edges, so it is only exact for linear data at least one pixel inside.
+pub fn remap(
+    src: &Array2<f32>,
+    map: &Array2<(f64, f64)>,
+    resampler: &dyn Resampler,
+) -> Array2<f32> {
+    let view = src.view();
+    map.mapv(|(line, sample)| resampler.sample(&view, sample, line))
+}
+
+/// [`remap`] with bilinear interpolation
+pub fn remap_bilinear(src: &Array2<f32>, map: &Array2<(f64, f64)>) -> Array2<f32> {
+    remap(src, map, &BilinearResampler)
+}
+
+/// Interpolated value at a (line, sample) position; NaN outside the image
+pub(crate) fn sample_at(image: &Array2<f32>, line: f64, sample: f64, interp: Interp) -> f32 {
+    interp.sample(&image.view(), sample, line)
+}
+
+/// Whether `(x, y)` lies within the pixel centers of `img`
+fn in_bounds(img: &ArrayView2<f32>, x: f64, y: f64) -> bool {
+    let (rows, cols) = img.dim();
+    rows > 0
+        && cols > 0
+        && (0.0..=(cols - 1) as f64).contains(&x)
+        && (0.0..=(rows - 1) as f64).contains(&y)
+}
+
+/// Bilinear sample at an in-bounds position
+fn sample_bilinear(image: &ArrayView2<f32>, line: f64, sample: f64) -> f32 {
+    let (rows, cols) = image.dim();
+    let r0 = line.floor() as usize;
+    let c0 = sample.floor() as usize;
+    let r1 = (r0 + 1).min(rows - 1);
+    let c1 = (c0 + 1).min(cols - 1);
+    let fr = line - r0 as f64;
+    let fc = sample - c0 as f64;
+
+    let at = |r: usize, c: usize| image[[r, c]] as f64;
+    let top = at(r0, c0) * (1.0 - fc) + at(r0, c1) * fc;
+    let bottom = at(r1, c0) * (1.0 - fc) + at(r1, c1) * fc;
+
+    (top * (1.0 - fr) + bottom * fr) as f32
+}
+
+/// Catmull-Rom sample at an in-bounds position, clamping at the edges
+fn sample_bicubic(image: &ArrayView2<f32>, line: f64, sample: f64) -> f32 {
+    let (rows, cols) = image.dim();
+    let r0 = line.floor();
+    let c0 = sample.floor();
+    let wr = catmull_rom_weights(line - r0);
+    let wc = catmull_rom_weights(sample - c0);
+
+    let clamp = |i: f64, n: usize| i.clamp(0.0, (n - 1) as f64) as usize;
+
+    let mut value = 0.0;
+    for (i, wy) in wr.iter().enumerate() {
+        let r = clamp(r0 + i as f64 - 1.0, rows);
+        let mut row_value = 0.0;
+        for (j, wx) in wc.iter().enumerate() {
+            let c = clamp(c0 + j as f64 - 1.0, cols);
+            row_value += wx * image[[r, c]] as f64;
+        }
+        value += wy * row_value;
+    }
+
+    value as f32
+}
+
+/// Catmull-Rom weights for the samples at offsets -1, 0, 1, 2 from `t` in [0, 1)
+fn catmull_rom_weights(t: f64) -> [f64; 4] {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    [
+        0.5 * (-t3 + 2.0 * t2 - t),
+        0.5 * (3.0 * t3 - 5.0 * t2 + 2.0),
+        0.5 * (-3.0 * t3 + 4.0 * t2 + t),
+        0.5 * (t3 - t2),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn textured() -> Array2<f32> {
+        Array2::from_shape_fn((12, 15), |(r, c)| ((r * 37 + c * 11) % 23) as f32)
+    }
+
+    #[test]
+    fn test_remap_nearest_integer_coordinates() {
+        let src = textured();
+
+        // Flip horizontally; integer positions must copy values exactly
+        let map = Array2::from_shape_fn((12, 15), |(r, c)| (r as f64, (14 - c) as f64));
+        let out = remap(&src, &map, &NearestResampler);
+        for ((r, c), &v) in out.indexed_iter() {
+            assert_eq!(v, src[[r, 14 - c]]);
+        }
+
+        // Rounds to the closest pixel between integers
+        let map = Array2::from_elem((1, 1), (3.4, 7.6));
+        assert_eq!(remap(&src, &map, &NearestResampler)[[0, 0]], src[[3, 8]]);
+    }
+
+    #[test]
+    fn test_remap_bicubic_linear_ramp() {
+        let src =
+            Array2::from_shape_fn((10, 10), |(r, c)| (2.0 * r as f64 + 0.5 * c as f64) as f32);
+        let map = Array2::from_shape_fn((8, 8), |(r, c)| {
+            (1.0 + r as f64 * 0.93 + 0.17, 1.0 + c as f64 * 0.87 + 0.31)
+        });
+
+        let out = remap(&src, &map, &BicubicResampler);
+        for (&(line, sample), &v) in map.iter().zip(out.iter()) {
+            assert!((v as f64 - (2.0 * line + 0.5 * sample)).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_remap_bilinear_wrapper_and_bounds() {
+        let src = textured();
+        let map =
+            Array2::from_shape_vec((1, 3), vec![(2.25, 3.5), (-0.1, 4.0), (11.0, 14.5)]).unwrap();
+
+        let out = remap_bilinear(&src, &map);
+        let direct = remap(&src, &map, &BilinearResampler);
+        assert!(out.iter().zip(direct.iter()).all(|(a, b)| a.to_bits() == b.to_bits()));
+        let expected = 0.75 * (0.5 * src[[2, 3]] + 0.5 * src[[2, 4]])
+            + 0.25 * (0.5 * src[[3, 3]] + 0.5 * src[[3, 4]]);
+        assert!((out[[0, 0]] - expected).abs() < 1e-5);
+        assert!(out[[0, 1]].is_nan());
+        assert!(out[[0, 2]].is_nan());
+
+        for interp in [Interp::Nearest, Interp::Bicubic] {
+            assert!(remap(&src, &map, &interp)[[0, 1]].is_nan());
+        }
+    }
+
+    #[test]
+    fn test_resamplers_at_fractional_positions() {
+        let src = textured();
+        let view = src.view();
+        let (x, y) = (4.3, 6.8);
+
+        assert_eq!(NearestResampler.sample(&view, x, y), src[[7, 4]]);
+
+        let at = |r: usize, c: usize| src[[r, c]] as f64;
+        let bilinear =
+            0.2 * (0.7 * at(6, 4) + 0.3 * at(6, 5)) + 0.8 * (0.7 * at(7, 4) + 0.3 * at(7, 5));
+        assert!((BilinearResampler.sample(&view, x, y) as f64 - bilinear).abs() < 1e-5);
+
+        // Bicubic blends the 4x4 patch around (4, 6) with Catmull-Rom weights
+        let (wx, wy) = (catmull_rom_weights(0.3), catmull_rom_weights(0.8));
+        let mut bicubic = 0.0;
+        for (i, wy) in wy.iter().enumerate() {
+            for (j, wx) in wx.iter().enumerate() {
+                bicubic += wy * wx * at(5 + i, 3 + j);
+            }
+        }
+        assert!((BicubicResampler.sample(&view, x, y) as f64 - bicubic).abs() < 1e-4);
+
+        // The enum dispatches to the matching implementor
+        for (interp, resampler) in [
+            (Interp::Nearest, &NearestResampler as &dyn Resampler),
+            (Interp::Bilinear, &BilinearResampler),
+            (Interp::Bicubic, &BicubicResampler),
+        ] {
+            let (a, b) = (interp.sample(&view, x, y), resampler.sample(&view, x, y));
+            assert_eq!(a.to_bits(), b.to_bits());
+            assert!(resampler.sample(&view, 14.5, y).is_nan());
+        }
+    }
+}
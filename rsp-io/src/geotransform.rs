@@ -0,0 +1,117 @@
+//! GDAL-style affine geotransforms
+
+use rsp_core::error::{Result, RspError};
+
+/// North-up geotransform covering a bounding box with a pixel grid
+///
+/// `(min_x, max_y)` is the outer top-left corner and `(max_x, min_y)` the
+/// outer bottom-right corner, in map units. The y resolution is negative, as
+/// GDAL expects for north-up rasters, and there is no rotation. Fails with
+/// `InvalidInput` if `width` or `height` is zero.
+pub fn geotransform_from_bounds(
+    min_x: f64,
+    max_y: f64,
+    max_x: f64,
+    min_y: f64,
+    width: usize,
+    height: usize,
+) -> Result<[f64; 6]> {
+    if width == 0 || height == 0 {
+        return Err(RspError::InvalidInput(format!(
+            "Raster size must be non-zero, got {}x{}",
+            width, height
+        )));
+    }
+
+    Ok([
+        min_x,
+        (max_x - min_x) / width as f64,
+        0.0,
+        max_y,
+        0.0,
+        -(max_y - min_y) / height as f64,
+    ])
+}
+
+/// Map coordinates of a (fractional) pixel position through a geotransform
+///
+/// `(0, 0)` is the outer top-left corner of the first pixel; add 0.5 for
+/// pixel centers.
+pub fn pixel_to_geo(gt: &[f64; 6], col: f64, row: f64) -> (f64, f64) {
+    (
+        gt[0] + col * gt[1] + row * gt[2],
+        gt[3] + col * gt[4] + row * gt[5],
+    )
+}
+
+/// Inverse of a geotransform, mapping map coordinates back to pixel positions
+///
+/// The result is itself a geotransform, so [`pixel_to_geo`] with it takes
+/// `(x, y)` to `(col, row)`. Returns `None` if the affine part is singular.
+pub fn invert_geotransform(gt: &[f64; 6]) -> Option<[f64; 6]> {
+    let det = gt[1] * gt[5] - gt[2] * gt[4];
+    if det == 0.0 || !det.is_finite() {
+        return None;
+    }
+
+    let (a, b, d, e) = (gt[5] / det, -gt[2] / det, -gt[4] / det, gt[1] / det);
+    Some([
+        -(a * gt[0] + b * gt[3]),
+        a,
+        b,
+        -(d * gt[0] + e * gt[3]),
+        d,
+        e,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geotransform_from_bounds_corners() {
+        let (min_x, max_y, max_x, min_y) = (500_000.0, 4_300_000.0, 503_000.0, 4_298_000.0);
+        let gt = geotransform_from_bounds(min_x, max_y, max_x, min_y, 1500, 1000).unwrap();
+
+        assert_eq!(gt, [500_000.0, 2.0, 0.0, 4_300_000.0, 0.0, -2.0]);
+        assert_eq!(pixel_to_geo(&gt, 0.0, 0.0), (min_x, max_y));
+        assert_eq!(pixel_to_geo(&gt, 1500.0, 0.0), (max_x, max_y));
+        assert_eq!(pixel_to_geo(&gt, 1500.0, 1000.0), (max_x, min_y));
+        assert_eq!(pixel_to_geo(&gt, 0.0, 1000.0), (min_x, min_y));
+    }
+
+    #[test]
+    fn test_geotransform_from_bounds_geographic() {
+        let gt = geotransform_from_bounds(-77.1, 39.05, -76.9, 38.95, 400, 300).unwrap();
+        let (x, y) = pixel_to_geo(&gt, 400.0, 300.0);
+        assert!((x - -76.9).abs() < 1e-12);
+        assert!((y - 38.95).abs() < 1e-12);
+
+        // Center of the first pixel
+        let (x, y) = pixel_to_geo(&gt, 0.5, 0.5);
+        assert!((x - (-77.1 + 0.25 * 0.001)).abs() < 1e-12);
+        assert!((y - (39.05 - 0.5 * 0.1 / 300.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_geotransform_from_bounds_zero_size() {
+        for (width, height) in [(0, 300), (400, 0), (0, 0)] {
+            let gt = geotransform_from_bounds(-77.1, 39.05, -76.9, 38.95, width, height);
+            assert!(matches!(gt, Err(RspError::InvalidInput(_))), "{}x{}", width, height);
+        }
+    }
+
+    #[test]
+    fn test_invert_geotransform_round_trip() {
+        let gt = [500_000.0, 2.0, 0.3, 4_300_000.0, -0.2, -2.5];
+        let inv = invert_geotransform(&gt).unwrap();
+        for (col, row) in [(0.0, 0.0), (10.5, 3.25), (-4.0, 700.0)] {
+            let (x, y) = pixel_to_geo(&gt, col, row);
+            let (c, r) = pixel_to_geo(&inv, x, y);
+            assert!((c - col).abs() < 1e-6 && (r - row).abs() < 1e-6);
+        }
+
+        assert_eq!(invert_geotransform(&[0.0, 1.0, 2.0, 0.0, 0.5, 1.0]), None);
+    }
+}
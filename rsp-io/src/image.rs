@@ -1,9 +1,14 @@
 
-use gdal::Dataset;
-use ndarray::Array3;
-use std::path::Path;
+use gdal::raster::{Buffer, ColorInterpretation, GdalDataType, GdalType, RasterCreationOptions};
+use gdal::spatial_ref::{AxisMappingStrategy, CoordTransform, SpatialRef};
+use gdal::{Dataset, DriverManager, Metadata};
+use ndarray::{Array2, Array3, Axis};
+use rsp_core::coordinate::LlaCoord;
+use rsp_core::math::percentile_array;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use crate::geotransform::pixel_to_geo;
 use crate::metadata::ImageMetadata;
 
 #[derive(Error, Debug)]
@@ -12,10 +17,76 @@ pub enum ImageError {
     Gdal(#[from] gdal::errors::GdalError),
     #[error("Invalid image dimensions")]
     InvalidDimensions,
+    #[error("Band {0} has no color table")]
+    NoColorTable(usize),
+    #[error("Unsupported band data type: {0}")]
+    InvalidBandType(GdalDataType),
+    #[error("Bands have mixed data types: {}", type_names(.types))]
+    MixedBandTypes { types: Vec<GdalDataType> },
+    #[error("{count} values out of range [0, {max}]")]
+    OutOfRange { count: usize, max: u32 },
+    #[error("Band {requested} requested but the image has {available} bands")]
+    InvalidBand { requested: usize, available: usize },
+    #[error("Image has no geotransform or spatial reference")]
+    NotGeoreferenced,
+    #[error("Failed to open {}: {source}", path.display())]
+    OpenFailed { path: PathBuf, source: gdal::errors::GdalError },
+}
+
+/// Comma-separated GDAL names of band data types
+fn type_names(types: &[GdalDataType]) -> String {
+    types.iter().map(|t| t.name()).collect::<Vec<_>>().join(", ")
 }
 
 pub type Result<T> = std::result::Result<T, ImageError>;
 
+/// Resampling used when a window is read at a different output size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResampleAlg {
+    /// Nearest neighbour (GDAL's default; aliases when downsampling)
+    #[default]
+    Nearest,
+    Bilinear,
+    Cubic,
+    /// Mean of the source pixels covered by each output pixel
+    Average,
+    Gauss,
+}
+
+impl ResampleAlg {
+    fn to_gdal(self) -> gdal::raster::ResampleAlg {
+        match self {
+            ResampleAlg::Nearest => gdal::raster::ResampleAlg::NearestNeighbour,
+            ResampleAlg::Bilinear => gdal::raster::ResampleAlg::Bilinear,
+            ResampleAlg::Cubic => gdal::raster::ResampleAlg::Cubic,
+            ResampleAlg::Average => gdal::raster::ResampleAlg::Average,
+            ResampleAlg::Gauss => gdal::raster::ResampleAlg::Gauss,
+        }
+    }
+}
+
+/// Axis order of arrays returned by image reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrayLayout {
+    /// [height, width, bands] (HWC), as returned by `read_u8`
+    #[default]
+    BandsLast,
+    /// [bands, height, width] (CHW), as expected by most ML frameworks
+    BandsFirst,
+}
+
+/// Pixel data read at the image's native band type (shape: [height, width, bands])
+#[derive(Debug, Clone, PartialEq)]
+pub enum RasterData {
+    U8(Array3<u8>),
+    U16(Array3<u16>),
+    I16(Array3<i16>),
+    U32(Array3<u32>),
+    I32(Array3<i32>),
+    F32(Array3<f32>),
+    F64(Array3<f64>),
+}
+
 /// Core image structure with metadata
 pub struct Image {
     dataset: Dataset,
@@ -27,21 +98,116 @@ pub struct Image {
 
 impl Image {
     /// Open an image from file path and extract all metadata
+    ///
+    /// Also accepts GDAL subdataset connection strings as returned by
+    /// [`subdatasets`] (e.g. `NITF_IM:1:scene.ntf`).
+    ///
+    /// Failures are reported as [`ImageError::OpenFailed`] carrying the path.
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let dataset = Dataset::open(path)?;
+        let dataset = open_dataset(path.as_ref())?;
+        Ok(Self::from_dataset(dataset))
+    }
+    
+    /// Wrap an already-open GDAL dataset (e.g. an in-memory MEM dataset)
+    pub fn from_dataset(dataset: Dataset) -> Self {
         let (width, height) = dataset.raster_size();
         let band_count = dataset.raster_count() as usize;
         
         // Extract all available metadata
         let metadata = ImageMetadata::from_gdal_dataset(&dataset);
         
-        Ok(Self {
+        Self {
             dataset,
             width,
             height,
             band_count,
             metadata,
-        })
+        }
+    }
+    
+    /// Create an in-memory (MEM) image from a u8 array (shape: [height, width, bands])
+    ///
+    /// The projection may be any definition GDAL understands (WKT,
+    /// `EPSG:4326`, PROJ string).
+    pub fn from_array_u8(
+        data: &Array3<u8>,
+        geotransform: Option<[f64; 6]>,
+        projection: Option<&str>,
+    ) -> Result<Self> {
+        let (height, width, bands) = data.dim();
+        if width == 0 || height == 0 || bands == 0 {
+            return Err(ImageError::InvalidDimensions);
+        }
+        
+        let driver = DriverManager::get_driver_by_name("MEM")?;
+        let mut dataset = driver.create_with_band_type::<u8, _>("", width, height, bands)?;
+        
+        for (band_idx, plane) in data.axis_iter(Axis(2)).enumerate() {
+            let mut buffer = Buffer::new((width, height), plane.iter().copied().collect());
+            let mut band = dataset.rasterband(band_idx + 1)?;
+            band.write((0, 0), (width, height), &mut buffer)?;
+        }
+        
+        if let Some(gt) = geotransform {
+            dataset.set_geo_transform(&gt)?;
+        }
+        if let Some(definition) = projection {
+            dataset.set_spatial_ref(&SpatialRef::from_definition(definition)?)?;
+        }
+        
+        Ok(Self::from_dataset(dataset))
+    }
+    
+    /// Create a GeoTIFF on disk with bands of type `T`
+    ///
+    /// `creation_options` are forwarded to GDAL's GTiff driver, e.g.
+    /// `("TILED", "YES")`, `("COMPRESS", "DEFLATE")` or `("BIGTIFF", "YES")`
+    /// for outputs past 4 GB. Fill it with the `write_window_*` methods; the
+    /// file is flushed when the image is dropped.
+    pub fn create<T: GdalType, P: AsRef<Path>>(
+        path: P,
+        width: usize,
+        height: usize,
+        bands: usize,
+        creation_options: &[(&str, &str)],
+    ) -> Result<Self> {
+        if width == 0 || height == 0 || bands == 0 {
+            return Err(ImageError::InvalidDimensions);
+        }
+        
+        let driver = DriverManager::get_driver_by_name("GTiff")?;
+        let options = creation_option_list(creation_options)?;
+        let dataset = driver
+            .create_with_band_type_with_options::<T, _>(path, width, height, bands, &options)?;
+        Ok(Self::from_dataset(dataset))
+    }
+    
+    /// Copy the image to a Cloud-Optimized GeoTIFF
+    ///
+    /// GDAL's COG driver can only write by copying a complete dataset, so
+    /// build the image first (e.g. with [`Image::from_array_u8`]) and export
+    /// it here. The preset is DEFLATE compression, 512 pixel tiles,
+    /// `BIGTIFF=IF_SAFER` and automatic overviews; `extra_options` are
+    /// applied on top and override it.
+    pub fn create_cog<P: AsRef<Path>>(
+        &self,
+        path: P,
+        extra_options: &[(&str, &str)],
+    ) -> Result<Image> {
+        const COG_PRESET: [(&str, &str); 4] = [
+            ("COMPRESS", "DEFLATE"),
+            ("BLOCKSIZE", "512"),
+            ("BIGTIFF", "IF_SAFER"),
+            ("OVERVIEWS", "AUTO"),
+        ];
+        
+        let driver = DriverManager::get_driver_by_name("COG")?;
+        let mut options = creation_option_list(&COG_PRESET)?;
+        for (key, value) in extra_options {
+            options.set_name_value(key, value)?;
+        }
+        let dataset = self.dataset.create_copy(&driver, path, &options)?;
+        Ok(Self::from_dataset(dataset))
     }
     
     /// Get reference to underlying GDAL dataset
@@ -59,6 +225,30 @@ impl Image {
         &self.metadata
     }
     
+    /// `KEY=VALUE` pairs of a GDAL metadata domain (`""` is the default one)
+    ///
+    /// Keys are trimmed and items without `=` are skipped. Returns `None` if
+    /// the dataset has no such domain.
+    pub fn metadata_domain(&self, domain: &str) -> Option<Vec<(String, String)>> {
+        let items = self.dataset.metadata_domain(domain)?;
+        Some(
+            items
+                .iter()
+                .filter_map(|item| {
+                    let (key, value) = item.split_once('=')?;
+                    Some((key.trim().to_string(), value.to_string()))
+                })
+                .collect(),
+        )
+    }
+    
+    /// Value of one metadata item, from `domain` or the default domain
+    pub fn metadata_item(&self, key: &str, domain: Option<&str>) -> Option<String> {
+        self.metadata_domain(domain.unwrap_or(""))?
+            .into_iter()
+            .find_map(|(k, value)| (k == key).then_some(value))
+    }
+    
     /// Get image dimensions (width, height)
     pub fn size(&self) -> (usize, usize) {
         (self.width, self.height)
@@ -98,29 +288,22 @@ impl Image {
         width: usize,
         height: usize,
     ) -> Result<Array3<u8>> {
-        if x_off + width > self.width || y_off + height > self.height {
-            return Err(ImageError::InvalidDimensions);
-        }
-        
-        let mut data = Array3::<u8>::zeros((height, width, self.band_count));
-        
-        for band_idx in 0..self.band_count {
-            let band = self.dataset.rasterband(band_idx + 1)?;
-            let buffer = band.read_as::<u8>(
-                (x_off as isize, y_off as isize),
-                (width, height),
-                (width, height),
-                None,
-            )?;
-            
-            for y in 0..height {
-                for x in 0..width {
-                    data[[y, x, band_idx]] = buffer.data()[y * width + x];
-                }
-            }
-        }
-        
-        Ok(data)
+        self.read_window_bands(&self.all_bands(), x_off, y_off, width, height)
+    }
+    
+    /// Read image window as u8 into a caller-provided array
+    ///
+    /// Lets tile-streaming loops reuse one buffer across many windows; `out`
+    /// is only reallocated when its shape isn't [height, width, bands].
+    pub fn read_window_into_u8(
+        &self,
+        x_off: usize,
+        y_off: usize,
+        width: usize,
+        height: usize,
+        out: &mut Array3<u8>,
+    ) -> Result<()> {
+        self.read_window_bands_into(&self.all_bands(), x_off, y_off, width, height, out)
     }
     
     /// Read full image as u16 array
@@ -136,67 +319,538 @@ impl Image {
         width: usize,
         height: usize,
     ) -> Result<Array3<u16>> {
-        if x_off + width > self.width || y_off + height > self.height {
+        self.read_window_bands(&self.all_bands(), x_off, y_off, width, height)
+    }
+    
+    /// Read full image as f32 array
+    pub fn read_f32(&self) -> Result<Array3<f32>> {
+        self.read_window_f32(0, 0, self.width, self.height)
+    }
+    
+    /// Read image window as f32 array
+    pub fn read_window_f32(
+        &self,
+        x_off: usize,
+        y_off: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<Array3<f32>> {
+        self.read_window_bands(&self.all_bands(), x_off, y_off, width, height)
+    }
+    
+    /// Read full image as u8 array in the requested layout
+    pub fn read_u8_layout(&self, layout: ArrayLayout) -> Result<Array3<u8>> {
+        self.read_window_u8_layout(0, 0, self.width, self.height, layout)
+    }
+    
+    /// Read image window as u8 array in the requested layout
+    ///
+    /// Both layouts are filled directly from GDAL's per-band reads.
+    pub fn read_window_u8_layout(
+        &self,
+        x_off: usize,
+        y_off: usize,
+        width: usize,
+        height: usize,
+        layout: ArrayLayout,
+    ) -> Result<Array3<u8>> {
+        match layout {
+            ArrayLayout::BandsLast => self.read_window_u8(x_off, y_off, width, height),
+            ArrayLayout::BandsFirst => self.read_window_u8_chw(x_off, y_off, width, height),
+        }
+    }
+    
+    /// Read full image bands-first as u8 array (shape: [bands, height, width])
+    ///
+    /// The CHW layout expected by most deep-learning frameworks.
+    pub fn read_u8_chw(&self) -> Result<Array3<u8>> {
+        self.read_window_u8_chw(0, 0, self.width, self.height)
+    }
+    
+    /// Read image window bands-first as u8 array (shape: [bands, height, width])
+    pub fn read_window_u8_chw(
+        &self,
+        x_off: usize,
+        y_off: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<Array3<u8>> {
+        self.read_window_bands_chw(&self.all_bands(), x_off, y_off, width, height)
+    }
+    
+    /// Read full image bands-first as u16 array (shape: [bands, height, width])
+    pub fn read_u16_chw(&self) -> Result<Array3<u16>> {
+        self.read_window_bands_chw(&self.all_bands(), 0, 0, self.width, self.height)
+    }
+    
+    /// Read full image bands-first as f32 array (shape: [bands, height, width])
+    pub fn read_f32_chw(&self) -> Result<Array3<f32>> {
+        self.read_window_f32_chw(0, 0, self.width, self.height)
+    }
+    
+    /// Read image window bands-first as f32 array (shape: [bands, height, width])
+    pub fn read_window_f32_chw(
+        &self,
+        x_off: usize,
+        y_off: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<Array3<f32>> {
+        self.read_window_bands_chw(&self.all_bands(), x_off, y_off, width, height)
+    }
+    
+    /// Write an f32 array into a window of u8 bands
+    ///
+    /// `data` is [height, width, bands] with one band per image band. Values
+    /// are rounded to the nearest integer; with `clamp` they are saturated to
+    /// [0, 255] (NaN becomes 0), otherwise any value outside that range fails
+    /// with `OutOfRange` before anything is written.
+    pub fn write_window_u8_from_f32(
+        &mut self,
+        x_off: usize,
+        y_off: usize,
+        data: &Array3<f32>,
+        clamp: bool,
+    ) -> Result<()> {
+        self.write_window_from_f32(x_off, y_off, data, clamp, u8::MAX as u32, |v| v as u8)
+    }
+    
+    /// Write an f32 array into a window of u16 bands
+    ///
+    /// Same as [`Image::write_window_u8_from_f32`] with the range [0, 65535].
+    pub fn write_window_u16_from_f32(
+        &mut self,
+        x_off: usize,
+        y_off: usize,
+        data: &Array3<f32>,
+        clamp: bool,
+    ) -> Result<()> {
+        self.write_window_from_f32(x_off, y_off, data, clamp, u16::MAX as u32, |v| v as u16)
+    }
+    
+    /// Data type of each band, in band order
+    ///
+    /// Bands that can't be accessed report `GdalDataType::Unknown`.
+    pub fn band_types(&self) -> Vec<GdalDataType> {
+        (1..=self.band_count)
+            .map(|b| {
+                self.dataset
+                    .rasterband(b)
+                    .map_or(GdalDataType::Unknown, |band| band.band_type())
+            })
+            .collect()
+    }
+    
+    /// Read the full image at its native data type
+    ///
+    /// The type is taken from band 1. Fails with `MixedBandTypes` if the bands
+    /// differ and `InvalidBandType` for types without a `RasterData` variant.
+    pub fn read_auto(&self) -> Result<RasterData> {
+        self.check_band(1)?;
+        let band_type = self.dataset.rasterband(1)?.band_type();
+        let bands = self.all_bands();
+        let (w, h) = (self.width, self.height);
+        
+        Ok(match band_type {
+            GdalDataType::UInt8 => RasterData::U8(self.read_window_bands(&bands, 0, 0, w, h)?),
+            GdalDataType::UInt16 => RasterData::U16(self.read_window_bands(&bands, 0, 0, w, h)?),
+            GdalDataType::Int16 => RasterData::I16(self.read_window_bands(&bands, 0, 0, w, h)?),
+            GdalDataType::UInt32 => RasterData::U32(self.read_window_bands(&bands, 0, 0, w, h)?),
+            GdalDataType::Int32 => RasterData::I32(self.read_window_bands(&bands, 0, 0, w, h)?),
+            GdalDataType::Float32 => RasterData::F32(self.read_window_bands(&bands, 0, 0, w, h)?),
+            GdalDataType::Float64 => RasterData::F64(self.read_window_bands(&bands, 0, 0, w, h)?),
+            other => return Err(ImageError::InvalidBandType(other)),
+        })
+    }
+    
+    /// Read a subset of bands within a window as u8 array
+    ///
+    /// `bands` are 1-indexed GDAL band numbers; the third dimension of the
+    /// result follows their order (shape: [height, width, bands.len()]).
+    pub fn read_bands_u8(
+        &self,
+        bands: &[usize],
+        x_off: usize,
+        y_off: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<Array3<u8>> {
+        self.read_window_bands(bands, x_off, y_off, width, height)
+    }
+    
+    /// Read an image window resampled to `out_width` x `out_height` as u8 array
+    ///
+    /// Use `ResampleAlg::Average` (or `Gauss`) when decimating to avoid the
+    /// aliasing of GDAL's default nearest-neighbour read.
+    pub fn read_window_resampled_u8(
+        &self,
+        x_off: usize,
+        y_off: usize,
+        width: usize,
+        height: usize,
+        out_size: (usize, usize),
+        alg: ResampleAlg,
+    ) -> Result<Array3<u8>> {
+        let mut data = Array3::<u8>::zeros((0, 0, 0));
+        self.read_bands_resampled_into(
+            &self.all_bands(),
+            (x_off, y_off, width, height),
+            out_size,
+            Some(alg),
+            &mut data,
+        )?;
+        Ok(data)
+    }
+    
+    /// Read a downsampled overview of the whole image as u8 array
+    ///
+    /// The longer side is scaled to at most `max_dim` pixels, preserving the
+    /// aspect ratio, using `ResampleAlg::Average`. Images already within
+    /// `max_dim` are read at full resolution.
+    pub fn thumbnail_u8(&self, max_dim: usize) -> Result<Array3<u8>> {
+        if max_dim == 0 {
             return Err(ImageError::InvalidDimensions);
         }
         
-        let mut data = Array3::<u16>::zeros((height, width, self.band_count));
+        let longest = self.width.max(self.height);
+        let out_size = if longest <= max_dim {
+            (self.width, self.height)
+        } else {
+            let scale = max_dim as f64 / longest as f64;
+            (
+                ((self.width as f64 * scale).round() as usize).max(1),
+                ((self.height as f64 * scale).round() as usize).max(1),
+            )
+        };
         
-        for band_idx in 0..self.band_count {
-            let band = self.dataset.rasterband(band_idx + 1)?;
-            let buffer = band.read_as::<u16>(
-                (x_off as isize, y_off as isize),
-                (width, height),
-                (width, height),
-                None,
-            )?;
-            
-            for y in 0..height {
-                for x in 0..width {
-                    data[[y, x, band_idx]] = buffer.data()[y * width + x];
+        self.read_window_resampled_u8(
+            0,
+            0,
+            self.width,
+            self.height,
+            out_size,
+            ResampleAlg::Average,
+        )
+    }
+    
+    /// Read a 1-indexed band scaled to u8 for display
+    ///
+    /// u8 bands are returned unchanged. Other types are read as f32 and
+    /// linearly stretched so the 2nd percentile maps to 0 and the 98th to 255,
+    /// clamping beyond. NaN pixels and bands without any spread map to 0.
+    pub fn read_display_u8(&self, band: usize) -> Result<Array2<u8>> {
+        self.check_band(band)?;
+        
+        let (w, h) = (self.width, self.height);
+        if self.dataset.rasterband(band)?.band_type() == GdalDataType::UInt8 {
+            let data = self.read_window_bands::<u8>(&[band], 0, 0, w, h)?;
+            return Ok(data.index_axis_move(Axis(2), 0));
+        }
+        
+        let data = self.read_window_bands::<f32>(&[band], 0, 0, w, h)?;
+        Ok(percentile_stretch_u8(&data.index_axis_move(Axis(2), 0)))
+    }
+    
+    /// Get the GDAL color interpretation name of a 1-indexed band (e.g. "Red")
+    pub fn color_interpretation(&self, band: usize) -> Result<String> {
+        self.check_band(band)?;
+        
+        let band = self.dataset.rasterband(band)?;
+        Ok(band.color_interpretation().name())
+    }
+    
+    /// Find the 1-indexed (red, green, blue) bands
+    ///
+    /// Scans the band color interpretations first; images with three or more
+    /// bands but no RGB tagging fall back to (1, 2, 3).
+    pub fn rgb_band_indices(&self) -> Option<(usize, usize, usize)> {
+        let mut red = None;
+        let mut green = None;
+        let mut blue = None;
+        
+        for band_idx in 1..=self.band_count {
+            let Ok(band) = self.dataset.rasterband(band_idx) else {
+                continue;
+            };
+            match band.color_interpretation() {
+                ColorInterpretation::RedBand => red = red.or(Some(band_idx)),
+                ColorInterpretation::GreenBand => green = green.or(Some(band_idx)),
+                ColorInterpretation::BlueBand => blue = blue.or(Some(band_idx)),
+                _ => {}
+            }
+        }
+        
+        match (red, green, blue) {
+            (Some(r), Some(g), Some(b)) => Some((r, g, b)),
+            _ if self.band_count >= 3 => Some((1, 2, 3)),
+            _ => None,
+        }
+    }
+    
+    /// Get the palette entries of a 1-indexed band as RGBA
+    ///
+    /// Returns `None` if the band doesn't exist or has no color table.
+    pub fn color_table(&self, band: usize) -> Option<Vec<[u8; 4]>> {
+        if band == 0 || band > self.band_count {
+            return None;
+        }
+        
+        let band = self.dataset.rasterband(band).ok()?;
+        let table = band.color_table()?;
+        
+        let clamp = |v: i16| v.clamp(0, 255) as u8;
+        let entries = (0..table.entry_count())
+            .map(|i| {
+                table
+                    .entry_as_rgb(i)
+                    .map(|e| [clamp(e.r), clamp(e.g), clamp(e.b), clamp(e.a)])
+                    .unwrap_or([0, 0, 0, 0])
+            })
+            .collect();
+        
+        Some(entries)
+    }
+    
+    /// Read a paletted band and expand it through its color table
+    ///
+    /// Returns an RGB array (shape: [height, width, 3]). Pixel values beyond
+    /// the end of the palette map to black.
+    pub fn read_band_as_rgb(&self, band: usize) -> Result<Array3<u8>> {
+        let indices = self.read_window_bands::<u16>(&[band], 0, 0, self.width, self.height)?;
+        let palette = self.color_table(band).ok_or(ImageError::NoColorTable(band))?;
+        
+        let mut rgb = Array3::<u8>::zeros((self.height, self.width, 3));
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if let Some(entry) = palette.get(indices[[y, x, 0]] as usize) {
+                    for (c, &value) in entry.iter().take(3).enumerate() {
+                        rgb[[y, x, c]] = value;
+                    }
                 }
             }
         }
         
-        Ok(data)
+        Ok(rgb)
     }
     
-    /// Read full image as f32 array
-    pub fn read_f32(&self) -> Result<Array3<f32>> {
-        self.read_window_f32(0, 0, self.width, self.height)
+    /// Read the validity mask of a 1-indexed band (shape: [height, width])
+    ///
+    /// Wraps GDAL's mask band, which may be an internal mask, an alpha band
+    /// or derived from the nodata value. Valid pixels are 255 and invalid
+    /// ones 0; bands without any mask are all 255.
+    pub fn read_mask(&self, band: usize) -> Result<Array2<u8>> {
+        self.check_band(band)?;
+        
+        let (w, h) = (self.width, self.height);
+        let band = self.dataset.rasterband(band)?;
+        if band.mask_flags()?.is_all_valid() {
+            return Ok(Array2::from_elem((h, w), 255));
+        }
+        
+        let mut data = vec![0u8; w * h];
+        band.open_mask_band()?
+            .read_into_slice::<u8>((0, 0), (w, h), (w, h), &mut data, None)?;
+        
+        // Alpha masks can hold partial transparency; anything non-zero is valid
+        let mask = Array2::from_shape_vec((h, w), data).expect("buffer length matches image size");
+        Ok(mask.mapv(|v| if v > 0 { 255 } else { 0 }))
     }
     
-    /// Read image window as f32 array
-    pub fn read_window_f32(
+    /// 1-indexed numbers of every band in the image
+    fn all_bands(&self) -> Vec<usize> {
+        (1..=self.band_count).collect()
+    }
+    
+    /// Read the listed 1-indexed bands of a window into a [height, width, bands] array
+    fn read_window_bands<T: GdalType + Copy + Default>(
         &self,
+        bands: &[usize],
         x_off: usize,
         y_off: usize,
         width: usize,
         height: usize,
-    ) -> Result<Array3<f32>> {
-        if x_off + width > self.width || y_off + height > self.height {
+    ) -> Result<Array3<T>> {
+        // Sized by read_window_bands_into once the window is validated
+        let mut data = Array3::<T>::from_elem((0, 0, 0), T::default());
+        self.read_window_bands_into(bands, x_off, y_off, width, height, &mut data)?;
+        Ok(data)
+    }
+    
+    /// Read the listed 1-indexed bands of a window into `out`
+    ///
+    /// `out` is reallocated only if its shape isn't [height, width, bands.len()].
+    fn read_window_bands_into<T: GdalType + Copy + Default>(
+        &self,
+        bands: &[usize],
+        x_off: usize,
+        y_off: usize,
+        width: usize,
+        height: usize,
+        out: &mut Array3<T>,
+    ) -> Result<()> {
+        self.read_bands_resampled_into(
+            bands,
+            (x_off, y_off, width, height),
+            (width, height),
+            None,
+            out,
+        )
+    }
+    
+    /// Read the listed 1-indexed bands of a `(x_off, y_off, width, height)`
+    /// window at `out_size` (width, height) into `out`
+    ///
+    /// `out` is reallocated only if its shape isn't [out_height, out_width, bands.len()].
+    fn read_bands_resampled_into<T: GdalType + Copy + Default>(
+        &self,
+        bands: &[usize],
+        window: (usize, usize, usize, usize),
+        out_size: (usize, usize),
+        alg: Option<ResampleAlg>,
+        out: &mut Array3<T>,
+    ) -> Result<()> {
+        let (x_off, y_off, width, height) = window;
+        let (out_width, out_height) = out_size;
+        self.check_window(x_off, y_off, width, height)?;
+        
+        if out_width == 0 || out_height == 0 {
             return Err(ImageError::InvalidDimensions);
         }
+        self.check_bands(bands)?;
+        
+        if out.shape() != [out_height, out_width, bands.len()] {
+            *out = Array3::<T>::from_elem((out_height, out_width, bands.len()), T::default());
+        }
         
-        let mut data = Array3::<f32>::zeros((height, width, self.band_count));
+        // One scratch row-major buffer shared by all bands
+        let mut scratch = vec![T::default(); out_width * out_height];
         
-        for band_idx in 0..self.band_count {
-            let band = self.dataset.rasterband(band_idx + 1)?;
-            let buffer = band.read_as::<f32>(
+        for (out_idx, &band_idx) in bands.iter().enumerate() {
+            let band = self.dataset.rasterband(band_idx)?;
+            band.read_into_slice::<T>(
                 (x_off as isize, y_off as isize),
                 (width, height),
-                (width, height),
-                None,
+                (out_width, out_height),
+                &mut scratch,
+                alg.map(ResampleAlg::to_gdal),
             )?;
             
-            for y in 0..height {
-                for x in 0..width {
-                    data[[y, x, band_idx]] = buffer.data()[y * width + x];
+            for y in 0..out_height {
+                for x in 0..out_width {
+                    out[[y, x, out_idx]] = scratch[y * out_width + x];
                 }
             }
         }
         
-        Ok(data)
+        Ok(())
+    }
+    
+    /// Read the listed 1-indexed bands of a window bands-first
+    ///
+    /// Each band is read by GDAL straight into its contiguous slice of the
+    /// [bands, height, width] result, so no transpose copy is needed.
+    fn read_window_bands_chw<T: GdalType + Copy + Default>(
+        &self,
+        bands: &[usize],
+        x_off: usize,
+        y_off: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<Array3<T>> {
+        self.check_window(x_off, y_off, width, height)?;
+        self.check_bands(bands)?;
+        
+        let mut data = vec![T::default(); bands.len() * width * height];
+        for (&band_idx, plane) in bands.iter().zip(data.chunks_mut(width * height)) {
+            let band = self.dataset.rasterband(band_idx)?;
+            band.read_into_slice::<T>(
+                (x_off as isize, y_off as isize),
+                (width, height),
+                (width, height),
+                plane,
+                None,
+            )?;
+        }
+        
+        Ok(Array3::from_shape_vec((bands.len(), height, width), data)
+            .expect("buffer length matches window size"))
+    }
+    
+    /// Round, range-check and write an f32 [height, width, bands] array
+    fn write_window_from_f32<T: GdalType + Copy>(
+        &mut self,
+        x_off: usize,
+        y_off: usize,
+        data: &Array3<f32>,
+        clamp: bool,
+        max: u32,
+        cast: impl Fn(f32) -> T,
+    ) -> Result<()> {
+        let (height, width, bands) = data.dim();
+        if bands != self.band_count {
+            return Err(ImageError::InvalidDimensions);
+        }
+        self.check_window(x_off, y_off, width, height)?;
+        
+        let (values, count) = quantize(data, max as f32);
+        if count > 0 && !clamp {
+            return Err(ImageError::OutOfRange { count, max });
+        }
+        
+        for b in 0..bands {
+            let band_values: Vec<T> =
+                values.index_axis(Axis(2), b).iter().map(|&v| cast(v)).collect();
+            let mut buffer = Buffer::new((width, height), band_values);
+            let mut band = self.dataset.rasterband(b + 1)?;
+            band.write((x_off as isize, y_off as isize), (width, height), &mut buffer)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Validate a non-empty list of 1-indexed bands sharing one data type
+    fn check_bands(&self, bands: &[usize]) -> Result<()> {
+        if bands.is_empty() {
+            return Err(ImageError::InvalidDimensions);
+        }
+        for &band in bands {
+            self.check_band(band)?;
+        }
+        
+        // Don't let GDAL silently convert bands of different types to one
+        let all_types = self.band_types();
+        let types: Vec<_> = bands.iter().map(|&b| all_types[b - 1]).collect();
+        if types.windows(2).any(|pair| pair[0] != pair[1]) {
+            return Err(ImageError::MixedBandTypes { types });
+        }
+        
+        Ok(())
+    }
+    
+    /// Validate a 1-indexed band number before it reaches GDAL
+    fn check_band(&self, band: usize) -> Result<()> {
+        if band == 0 || band > self.band_count {
+            return Err(ImageError::InvalidBand { requested: band, available: self.band_count });
+        }
+        Ok(())
+    }
+    
+    /// Validate that a window is non-empty and lies inside the image
+    ///
+    /// Uses checked arithmetic so pathological offsets can't wrap around.
+    fn check_window(&self, x_off: usize, y_off: usize, width: usize, height: usize) -> Result<()> {
+        if width == 0 || height == 0 {
+            return Err(ImageError::InvalidDimensions);
+        }
+        
+        let x_end = x_off.checked_add(width).ok_or(ImageError::InvalidDimensions)?;
+        let y_end = y_off.checked_add(height).ok_or(ImageError::InvalidDimensions)?;
+        
+        if x_end > self.width || y_end > self.height {
+            return Err(ImageError::InvalidDimensions);
+        }
+        
+        Ok(())
     }
     
     /// Get geotransform if available
@@ -213,18 +867,360 @@ impl Image {
             Some(proj)
         }
     }
-}
-
-#[cfg(test)]
+    
+    /// EPSG code of the dataset CRS, if it has (or can be identified with) one
+    pub fn epsg_code(&self) -> Option<u32> {
+        let mut srs = self.dataset.spatial_ref().ok()?;
+        if srs.auth_name().ok().as_deref() != Some("EPSG") {
+            srs.auto_identify_epsg().ok()?;
+        }
+        
+        if srs.auth_name().ok()? != "EPSG" {
+            return None;
+        }
+        u32::try_from(srs.auth_code().ok()?).ok()
+    }
+    
+    /// Set the dataset CRS from an EPSG code
+    ///
+    /// Same as [`Image::set_spatial_ref_from_epsg`].
+    pub fn set_projection_epsg(&mut self, code: u32) -> Result<()> {
+        self.set_spatial_ref_from_epsg(code)
+    }
+    
+    /// Set the dataset CRS from an EPSG code via OSR
+    ///
+    /// The full WKT is written to the dataset, so GeoTIFF outputs get their
+    /// GeoKeys from it. Replaces any CRS set earlier.
+    pub fn set_spatial_ref_from_epsg(&mut self, epsg: u32) -> Result<()> {
+        let srs = SpatialRef::from_epsg(epsg)?;
+        self.dataset.set_spatial_ref(&srs)?;
+        Ok(())
+    }
+    
+    /// Set the dataset CRS from a WKT string via OSR
+    ///
+    /// The WKT is parsed first, so malformed strings fail here instead of
+    /// producing an unreadable output.
+    pub fn set_spatial_ref_from_wkt(&mut self, wkt: &str) -> Result<()> {
+        let srs = SpatialRef::from_wkt(wkt)?;
+        self.dataset.set_spatial_ref(&srs)?;
+        Ok(())
+    }
+    
+    /// Map coordinates of the four image corners from the geotransform
+    ///
+    /// Corners are the outer pixel edges in the order top-left, top-right,
+    /// bottom-right, bottom-left. Returns `None` without a geotransform.
+    pub fn corner_coordinates(&self) -> Option<[(f64, f64); 4]> {
+        let gt = self.geotransform()?;
+        let at = |col: f64, row: f64| pixel_to_geo(&gt, col, row);
+        
+        let (w, h) = (self.width as f64, self.height as f64);
+        Some([at(0.0, 0.0), at(w, 0.0), at(w, h), at(0.0, h)])
+    }
+    
+    /// Reproject into an in-memory image in another CRS
+    ///
+    /// The output is a north-up grid in `dst_epsg` with square pixels of
+    /// `out_gsd` target CRS units (meters for UTM), sized to cover the
+    /// source image edges once transformed. Pixels are resampled bilinearly
+    /// by GDAL's warper and areas outside the source are 0. Band count is
+    /// preserved, and every output band takes the first band's type.
+    pub fn reproject(&self, dst_epsg: u32, out_gsd: f64) -> Result<Image> {
+        // Points per image edge; corners alone miss edges that bow outward
+        const EDGE_SAMPLES: usize = 20;
+        
+        if !(out_gsd.is_finite() && out_gsd > 0.0) {
+            return Err(ImageError::InvalidDimensions);
+        }
+        self.check_band(1)?;
+        
+        let gt = self.geotransform().ok_or(ImageError::NotGeoreferenced)?;
+        let mut src_srs = self.dataset.spatial_ref().map_err(|_| ImageError::NotGeoreferenced)?;
+        src_srs.set_axis_mapping_strategy(AxisMappingStrategy::TraditionalGisOrder);
+        let mut dst_srs = SpatialRef::from_epsg(dst_epsg)?;
+        dst_srs.set_axis_mapping_strategy(AxisMappingStrategy::TraditionalGisOrder);
+        
+        let (w, h) = (self.width as f64, self.height as f64);
+        let (mut xs, mut ys) = (Vec::new(), Vec::new());
+        for i in 0..=EDGE_SAMPLES {
+            let t = i as f64 / EDGE_SAMPLES as f64;
+            for (col, row) in [(t * w, 0.0), (t * w, h), (0.0, t * h), (w, t * h)] {
+                let (x, y) = pixel_to_geo(&gt, col, row);
+                xs.push(x);
+                ys.push(y);
+            }
+        }
+        let mut zs = vec![0.0; xs.len()];
+        CoordTransform::new(&src_srs, &dst_srs)?.transform_coords(&mut xs, &mut ys, &mut zs)?;
+        
+        let min_x = xs.iter().copied().fold(f64::INFINITY, f64::min);
+        let max_x = xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let min_y = ys.iter().copied().fold(f64::INFINITY, f64::min);
+        let max_y = ys.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let width = ((max_x - min_x) / out_gsd).ceil().max(1.0) as usize;
+        let height = ((max_y - min_y) / out_gsd).ceil().max(1.0) as usize;
+        
+        let bands = self.band_count;
+        let mut dataset = match self.dataset.rasterband(1)?.band_type() {
+            GdalDataType::UInt8 => mem_dataset::<u8>(width, height, bands)?,
+            GdalDataType::UInt16 => mem_dataset::<u16>(width, height, bands)?,
+            GdalDataType::Int16 => mem_dataset::<i16>(width, height, bands)?,
+            GdalDataType::UInt32 => mem_dataset::<u32>(width, height, bands)?,
+            GdalDataType::Int32 => mem_dataset::<i32>(width, height, bands)?,
+            GdalDataType::Float32 => mem_dataset::<f32>(width, height, bands)?,
+            GdalDataType::Float64 => mem_dataset::<f64>(width, height, bands)?,
+            other => return Err(ImageError::InvalidBandType(other)),
+        };
+        dataset.set_geo_transform(&[min_x, out_gsd, 0.0, max_y, 0.0, -out_gsd])?;
+        dataset.set_spatial_ref(&dst_srs)?;
+        
+        gdal::raster::reproject(&self.dataset, &dataset)?;
+        Ok(Self::from_dataset(dataset))
+    }
+    
+    /// Geographic footprint of the image corners (altitude 0)
+    ///
+    /// Corners are taken as (lon, lat) directly when the dataset CRS is
+    /// geographic, and reprojected to WGS84 otherwise. Returns `None` if the
+    /// image isn't georeferenced or the reprojection fails.
+    pub fn footprint_lla(&self) -> Option<[LlaCoord; 4]> {
+        let mut srs = self.dataset.spatial_ref().ok()?;
+        if srs.is_geographic() {
+            return self.footprint_lla_with(|x, y| Some((x, y)));
+        }
+        
+        srs.set_axis_mapping_strategy(AxisMappingStrategy::TraditionalGisOrder);
+        let mut wgs84 = SpatialRef::from_epsg(4326).ok()?;
+        wgs84.set_axis_mapping_strategy(AxisMappingStrategy::TraditionalGisOrder);
+        let transform = CoordTransform::new(&srs, &wgs84).ok()?;
+        
+        self.footprint_lla_with(|x, y| {
+            let (mut xs, mut ys, mut zs) = ([x], [y], [0.0]);
+            transform.transform_coords(&mut xs, &mut ys, &mut zs).ok()?;
+            Some((xs[0], ys[0]))
+        })
+    }
+    
+    /// Geographic footprint using a caller-provided map (x, y) -> (lon, lat) transform
+    pub fn footprint_lla_with<F>(&self, to_lon_lat: F) -> Option<[LlaCoord; 4]>
+    where
+        F: Fn(f64, f64) -> Option<(f64, f64)>,
+    {
+        let corners = self.corner_coordinates()?;
+        let mut footprint = [LlaCoord { lat: 0.0, lon: 0.0, alt: 0.0 }; 4];
+        
+        for (corner, (x, y)) in footprint.iter_mut().zip(corners) {
+            let (lon, lat) = to_lon_lat(x, y)?;
+            *corner = LlaCoord { lat, lon, alt: 0.0 };
+        }
+        
+        Some(footprint)
+    }
+}
+
+/// List the subdataset connection strings of a container file (NITF, HDF, ...)
+///
+/// Each string can be passed to [`Image::open`]. Files without subdatasets
+/// give an empty list.
+pub fn subdatasets<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
+    let dataset = open_dataset(path.as_ref())?;
+    Ok(subdataset_names(&dataset))
+}
+
+/// Open a dataset, attaching the path to any GDAL error
+fn open_dataset(path: &Path) -> Result<Dataset> {
+    Dataset::open(path).map_err(|source| ImageError::OpenFailed {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// `SUBDATASET_<n>_NAME` entries of the `SUBDATASETS` domain, ordered by `n`
+fn subdataset_names(dataset: &Dataset) -> Vec<String> {
+    let mut names: Vec<(usize, String)> = dataset
+        .metadata_domain("SUBDATASETS")
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|item| {
+            let (key, value) = item.split_once('=')?;
+            let index = key.strip_prefix("SUBDATASET_")?.strip_suffix("_NAME")?;
+            Some((index.parse().ok()?, value.to_string()))
+        })
+        .collect();
+    
+    names.sort_by_key(|(index, _)| *index);
+    names.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Empty in-memory dataset with bands of type `T`
+fn mem_dataset<T: GdalType>(width: usize, height: usize, bands: usize) -> Result<Dataset> {
+    let driver = DriverManager::get_driver_by_name("MEM")?;
+    Ok(driver.create_with_band_type::<T, _>("", width, height, bands)?)
+}
+
+/// `KEY=VALUE` creation option list from key/value pairs
+fn creation_option_list(pairs: &[(&str, &str)]) -> Result<RasterCreationOptions> {
+    let mut options = RasterCreationOptions::new();
+    for (key, value) in pairs {
+        options.set_name_value(key, value)?;
+    }
+    Ok(options)
+}
+
+/// Round values and saturate them to [0, max], counting those that didn't fit
+///
+/// NaN counts as out of range and becomes 0.
+fn quantize(values: &Array3<f32>, max: f32) -> (Array3<f32>, usize) {
+    let mut out_of_range = 0;
+    let quantized = values.mapv(|v| {
+        let rounded = v.round();
+        if (0.0..=max).contains(&rounded) {
+            rounded
+        } else {
+            out_of_range += 1;
+            if rounded > max { max } else { 0.0 }
+        }
+    });
+    (quantized, out_of_range)
+}
+
+/// Linear 2-98 percentile stretch to u8; NaN and flat data map to 0
+fn percentile_stretch_u8(values: &Array2<f32>) -> Array2<u8> {
+    let (Some(lo), Some(hi)) = (percentile_array(values, 2.0), percentile_array(values, 98.0))
+    else {
+        return Array2::zeros(values.dim());
+    };
+    if hi <= lo {
+        return Array2::zeros(values.dim());
+    }
+    
+    let scale = 255.0 / (hi - lo);
+    values.mapv(|v| {
+        if v.is_nan() { 0 } else { ((v - lo) * scale).round().clamp(0.0, 255.0) as u8 }
+    })
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Build an in-memory u8 image whose pixel value is
+    /// `(x + y * width + 100 * (band - 1)) % 256`
+    fn mem_image(width: usize, height: usize, bands: usize) -> Image {
+        let driver = DriverManager::get_driver_by_name("MEM").unwrap();
+        let dataset = driver
+            .create_with_band_type::<u8, _>("", width, height, bands)
+            .unwrap();
+
+        for band_idx in 1..=bands {
+            let mut band = dataset.rasterband(band_idx).unwrap();
+            let data: Vec<u8> = (0..width * height)
+                .map(|i| ((i + 100 * (band_idx - 1)) % 256) as u8)
+                .collect();
+            let mut buffer = gdal::raster::Buffer::new((width, height), data);
+            band.write((0, 0), (width, height), &mut buffer).unwrap();
+        }
+
+        Image::from_dataset(dataset)
+    }
+
     #[test]
     fn test_image_error_display() {
         let err = ImageError::InvalidDimensions;
         assert_eq!(err.to_string(), "Invalid image dimensions");
     }
 
+    #[test]
+    fn test_open_failed_carries_path() {
+        let path = std::env::temp_dir().join("rsp_io_missing").join("scene.tif");
+        match Image::open(&path) {
+            Err(ImageError::OpenFailed { path: failed, .. }) => assert_eq!(failed, path),
+            other => panic!("expected OpenFailed, got {:?}", other.err()),
+        }
+
+        let err = subdatasets(&path).err().unwrap();
+        assert!(err.to_string().contains(path.to_str().unwrap()));
+    }
+
+    #[test]
+    fn test_no_color_table_display() {
+        let err = ImageError::NoColorTable(2);
+        assert_eq!(err.to_string(), "Band 2 has no color table");
+    }
+
+    #[test]
+    fn test_invalid_band_type_display() {
+        let err = ImageError::InvalidBandType(GdalDataType::Unknown);
+        assert_eq!(err.to_string(), "Unsupported band data type: Unknown");
+    }
+
+    /// Two-band image with a u8 and an f32 band
+    ///
+    /// The MEM driver can't mix types at creation, so this uses an in-memory
+    /// VRT (sourceless bands read as zero).
+    fn mixed_type_image() -> Image {
+        let xml = r#"<VRTDataset rasterXSize="4" rasterYSize="3">
+            <VRTRasterBand dataType="Byte" band="1"/>
+            <VRTRasterBand dataType="Float32" band="2"/>
+        </VRTDataset>"#;
+        Image::open(xml).unwrap()
+    }
+
+    #[test]
+    fn test_band_types_mixed() {
+        let img = mixed_type_image();
+        assert_eq!(img.band_types(), [GdalDataType::UInt8, GdalDataType::Float32]);
+        
+        let result = img.read_u8();
+        match result {
+            Err(ImageError::MixedBandTypes { types }) => {
+                assert_eq!(types, [GdalDataType::UInt8, GdalDataType::Float32]);
+            }
+            other => panic!("expected MixedBandTypes, got {:?}", other.map(|a| a.dim())),
+        }
+        assert!(matches!(img.read_auto(), Err(ImageError::MixedBandTypes { .. })));
+        
+        // A single band is still readable
+        assert_eq!(img.read_bands_u8(&[1], 0, 0, 4, 3).unwrap().dim(), (3, 4, 1));
+    }
+
+    #[test]
+    fn test_mixed_band_types_display() {
+        let err = ImageError::MixedBandTypes {
+            types: vec![GdalDataType::UInt8, GdalDataType::Float32],
+        };
+        assert_eq!(err.to_string(), "Bands have mixed data types: Byte, Float32");
+    }
+
+    #[test]
+    fn test_read_auto_u8() {
+        let img = mem_image(5, 4, 2);
+        match img.read_auto().unwrap() {
+            RasterData::U8(data) => assert_eq!(data, img.read_u8().unwrap()),
+            other => panic!("expected U8, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_read_auto_f32() {
+        let driver = DriverManager::get_driver_by_name("MEM").unwrap();
+        let dataset = driver.create_with_band_type::<f32, _>("", 3, 2, 1).unwrap();
+        let values = vec![0.5f32, -1.25, 2.0, 3.5, 1e6, -0.0];
+        let mut buffer = Buffer::new((3, 2), values.clone());
+        dataset.rasterband(1).unwrap().write((0, 0), (3, 2), &mut buffer).unwrap();
+        let img = Image::from_dataset(dataset);
+
+        match img.read_auto().unwrap() {
+            RasterData::F32(data) => {
+                assert_eq!(data.shape(), &[2, 3, 1]);
+                assert_eq!(data.iter().copied().collect::<Vec<_>>(), values);
+            }
+            other => panic!("expected F32, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_image_error_from_gdal() {
         // Test that ImageError can be created from GdalError
@@ -238,6 +1234,498 @@ mod tests {
         // _takes_image_error(img_err);
     }
 
+    #[test]
+    fn test_read_window_offset_overflow() {
+        let img = mem_image(8, 8, 1);
+        let result = img.read_window_u8(usize::MAX, 0, 2, 2);
+        assert!(matches!(result, Err(ImageError::InvalidDimensions)));
+
+        let result = img.read_window_u8(0, usize::MAX, 2, 2);
+        assert!(matches!(result, Err(ImageError::InvalidDimensions)));
+    }
+
+    #[test]
+    fn test_read_window_zero_size() {
+        let img = mem_image(8, 8, 1);
+        let result = img.read_window_u8(0, 0, 0, 4);
+        assert!(matches!(result, Err(ImageError::InvalidDimensions)));
+
+        let result = img.read_window_u8(0, 0, 4, 0);
+        assert!(matches!(result, Err(ImageError::InvalidDimensions)));
+    }
+
+    #[test]
+    fn test_read_window_valid() {
+        let img = mem_image(8, 8, 1);
+        let data = img.read_window_u8(2, 3, 4, 2).unwrap();
+        assert_eq!(data.shape(), &[2, 4, 1]);
+        assert_eq!(data[[0, 0, 0]], (2 + 3 * 8) as u8);
+        assert_eq!(data[[1, 3, 0]], (5 + 4 * 8) as u8);
+    }
+
+    #[test]
+    fn test_read_bands_subset() {
+        let img = mem_image(6, 4, 3);
+        let data = img.read_bands_u8(&[1, 3], 0, 0, 6, 4).unwrap();
+        assert_eq!(data.shape(), &[4, 6, 2]);
+
+        let full = img.read_u8().unwrap();
+        for y in 0..4 {
+            for x in 0..6 {
+                assert_eq!(data[[y, x, 0]], full[[y, x, 0]]);
+                assert_eq!(data[[y, x, 1]], full[[y, x, 2]]);
+            }
+        }
+        assert_eq!(data[[0, 0, 1]], 200);
+    }
+
+    #[test]
+    fn test_read_bands_invalid_index() {
+        let img = mem_image(6, 4, 3);
+        assert!(img.read_bands_u8(&[0], 0, 0, 6, 4).is_err());
+        assert!(img.read_bands_u8(&[4], 0, 0, 6, 4).is_err());
+        assert!(img.read_bands_u8(&[], 0, 0, 6, 4).is_err());
+    }
+
+    #[test]
+    fn test_invalid_band_before_gdal() {
+        let img = mem_image(6, 4, 1);
+        let invalid = |result: Result<()>| {
+            matches!(result, Err(ImageError::InvalidBand { requested: 5, available: 1 }))
+        };
+
+        assert!(invalid(img.read_bands_u8(&[1, 5], 0, 0, 6, 4).map(drop)));
+        assert!(invalid(img.read_display_u8(5).map(drop)));
+        assert!(invalid(img.color_interpretation(5).map(drop)));
+        assert!(invalid(img.read_band_as_rgb(5).map(drop)));
+        assert!(matches!(
+            img.read_bands_u8(&[0], 0, 0, 6, 4),
+            Err(ImageError::InvalidBand { requested: 0, .. })
+        ));
+
+        let err = ImageError::InvalidBand { requested: 5, available: 1 };
+        assert_eq!(err.to_string(), "Band 5 requested but the image has 1 bands");
+    }
+
+    #[test]
+    fn test_read_mask_all_valid_without_mask() {
+        let img = mem_image(6, 4, 2);
+        let mask = img.read_mask(2).unwrap();
+        assert_eq!(mask.dim(), (4, 6));
+        assert!(mask.iter().all(|&v| v == 255));
+
+        assert!(matches!(img.read_mask(3), Err(ImageError::InvalidBand { .. })));
+    }
+
+    #[test]
+    fn test_read_mask_from_nodata() {
+        let img = mem_image(6, 4, 1);
+        img.dataset().rasterband(1).unwrap().set_no_data_value(Some(5.0)).unwrap();
+
+        // Band 1 holds x + 6y, so only (0, 5) matches the nodata value
+        let mask = img.read_mask(1).unwrap();
+        for ((y, x), &v) in mask.indexed_iter() {
+            assert_eq!(v, if (y, x) == (0, 5) { 0 } else { 255 });
+        }
+    }
+
+    #[test]
+    fn test_create_tiled_deflate_geotiff() {
+        let path = std::env::temp_dir().join(format!("rsp_io_tiled_{}.tif", std::process::id()));
+        let options = [
+            ("TILED", "YES"),
+            ("COMPRESS", "DEFLATE"),
+            ("BLOCKXSIZE", "16"),
+            ("BLOCKYSIZE", "16"),
+        ];
+        let data =
+            Array3::from_shape_fn((30, 40, 2), |(y, x, b)| ((x + 3 * y + 50 * b) % 256) as f32);
+        {
+            let mut img = Image::create::<u8, _>(&path, 40, 30, 2, &options).unwrap();
+            img.write_window_u8_from_f32(0, 0, &data, false).unwrap();
+        }
+
+        let img = Image::open(&path).unwrap();
+        assert_eq!(img.dataset().rasterband(1).unwrap().block_size(), (16, 16));
+        let compression = img.metadata_item("COMPRESSION", Some("IMAGE_STRUCTURE"));
+        assert_eq!(compression.as_deref(), Some("DEFLATE"));
+        assert_eq!(img.read_u8().unwrap(), data.mapv(|v| v as u8));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            Image::create::<u8, _>(&path, 0, 30, 1, &[]),
+            Err(ImageError::InvalidDimensions)
+        ));
+    }
+
+    #[test]
+    fn test_create_cog_preset() {
+        let path = std::env::temp_dir().join(format!("rsp_io_cog_{}.tif", std::process::id()));
+        let src = mem_image(64, 48, 3);
+        let cog = src.create_cog(&path, &[("BLOCKSIZE", "32")]).unwrap();
+        drop(cog);
+
+        let img = Image::open(&path).unwrap();
+        assert_eq!(img.metadata_item("LAYOUT", Some("IMAGE_STRUCTURE")).as_deref(), Some("COG"));
+        assert_eq!(img.dataset().rasterband(1).unwrap().block_size(), (32, 32));
+        assert_eq!(img.read_u8().unwrap(), src.read_u8().unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_reproject_geographic_to_utm() {
+        let data = Array3::from_elem((40, 50, 2), 200u8);
+        let gt = [-77.01, 0.0002, 0.0, 39.01, 0.0, -0.0002];
+        let img = Image::from_array_u8(&data, Some(gt), Some("EPSG:4326")).unwrap();
+
+        let utm = img.reproject(32618, 10.0).unwrap();
+        assert_eq!(utm.epsg_code(), Some(32618));
+        assert_eq!(utm.band_count(), 2);
+        assert_eq!(utm.band_types(), vec![GdalDataType::UInt8; 2]);
+
+        // 0.01 deg of longitude is ~865 m and 0.008 deg of latitude ~888 m at 39N
+        let (w, h) = utm.size();
+        assert!((86..=95).contains(&w), "width {}", w);
+        assert!((88..=97).contains(&h), "height {}", h);
+        let out_gt = utm.geotransform().unwrap();
+        assert_eq!((out_gt[1], out_gt[5]), (10.0, -10.0));
+
+        // The middle of the scene is covered by the source
+        let pixels = utm.read_u8().unwrap();
+        assert_eq!(pixels[[h / 2, w / 2, 0]], 200);
+        assert_eq!(pixels[[h / 2, w / 2, 1]], 200);
+    }
+
+    #[test]
+    fn test_reproject_requires_georeferencing() {
+        let img = mem_image(8, 8, 1);
+        assert!(matches!(img.reproject(32618, 10.0), Err(ImageError::NotGeoreferenced)));
+    }
+
+    #[test]
+    fn test_color_interpretation_rgb_detection() {
+        let img = mem_image(4, 4, 3);
+        let interps = [
+            ColorInterpretation::BlueBand,
+            ColorInterpretation::GreenBand,
+            ColorInterpretation::RedBand,
+        ];
+        for (idx, interp) in interps.into_iter().enumerate() {
+            let mut band = img.dataset().rasterband(idx + 1).unwrap();
+            band.set_color_interpretation(interp).unwrap();
+        }
+
+        assert_eq!(img.color_interpretation(3).unwrap(), "Red");
+        assert_eq!(img.color_interpretation(1).unwrap(), "Blue");
+        assert_eq!(img.rgb_band_indices(), Some((3, 2, 1)));
+        assert!(img.color_interpretation(4).is_err());
+    }
+
+    #[test]
+    fn test_rgb_band_indices_fallback() {
+        let img = mem_image(4, 4, 4);
+        for idx in 1..=4 {
+            let mut band = img.dataset().rasterband(idx).unwrap();
+            band.set_color_interpretation(ColorInterpretation::Undefined).unwrap();
+        }
+        assert_eq!(img.rgb_band_indices(), Some((1, 2, 3)));
+
+        let gray = mem_image(4, 4, 1);
+        assert_eq!(gray.rgb_band_indices(), None);
+    }
+
+    #[test]
+    fn test_palette_lookup() {
+        use gdal::raster::{ColorEntry, ColorTable, PaletteInterpretation};
+
+        // Pixel values 0..4 along a single row
+        let img = mem_image(4, 1, 1);
+        let mut table = ColorTable::new(PaletteInterpretation::Rgba);
+        table.set_color_entry(0, &ColorEntry::rgba(0, 0, 0, 255));
+        table.set_color_entry(1, &ColorEntry::rgba(255, 0, 0, 255));
+        table.set_color_entry(2, &ColorEntry::rgba(0, 255, 0, 255));
+        table.set_color_entry(3, &ColorEntry::rgba(0, 0, 255, 128));
+        img.dataset().rasterband(1).unwrap().set_color_table(&table);
+
+        let palette = img.color_table(1).unwrap();
+        assert_eq!(palette.len(), 4);
+        assert_eq!(palette[3], [0, 0, 255, 128]);
+
+        let rgb = img.read_band_as_rgb(1).unwrap();
+        assert_eq!(rgb.shape(), &[1, 4, 3]);
+        assert_eq!(rgb.slice(ndarray::s![0, 1, ..]).to_vec(), vec![255, 0, 0]);
+        assert_eq!(rgb.slice(ndarray::s![0, 2, ..]).to_vec(), vec![0, 255, 0]);
+        assert_eq!(rgb.slice(ndarray::s![0, 3, ..]).to_vec(), vec![0, 0, 255]);
+    }
+
+    #[test]
+    fn test_read_band_as_rgb_without_palette() {
+        let img = mem_image(4, 4, 1);
+        assert!(img.color_table(1).is_none());
+        assert!(matches!(img.read_band_as_rgb(1), Err(ImageError::NoColorTable(1))));
+    }
+
+    #[test]
+    fn test_read_window_into_reuses_buffer() {
+        let img = mem_image(16, 16, 2);
+        let mut buffer = Array3::<u8>::zeros((4, 4, 2));
+
+        for (x_off, y_off) in [(0, 0), (4, 8), (12, 12)] {
+            img.read_window_into_u8(x_off, y_off, 4, 4, &mut buffer).unwrap();
+            let fresh = img.read_window_u8(x_off, y_off, 4, 4).unwrap();
+            assert_eq!(buffer, fresh);
+        }
+    }
+
+    #[test]
+    fn test_read_window_into_adjacent_windows() {
+        let (width, height) = (12, 6);
+        let img = mem_image(width, height, 2);
+        let mut buffer = Array3::<u8>::zeros((6, 6, 2));
+        let ptr = buffer.as_ptr();
+
+        // Left and right halves, side by side, through one buffer
+        for x_off in [0, 6] {
+            img.read_window_into_u8(x_off, 0, 6, 6, &mut buffer).unwrap();
+            assert_eq!(buffer.as_ptr(), ptr, "buffer was reallocated");
+
+            for ((y, x, b), &value) in buffer.indexed_iter() {
+                let expected = ((x_off + x) + y * width + 100 * b) % 256;
+                assert_eq!(value as usize, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_window_into_resizes() {
+        let img = mem_image(16, 16, 2);
+        let mut buffer = Array3::<u8>::zeros((1, 1, 1));
+
+        img.read_window_into_u8(2, 2, 5, 3, &mut buffer).unwrap();
+        assert_eq!(buffer.shape(), &[3, 5, 2]);
+        assert_eq!(buffer, img.read_window_u8(2, 2, 5, 3).unwrap());
+    }
+
+    #[test]
+    fn test_average_downsample_matches_block_means() {
+        let (width, height) = (8, 6);
+        let driver = DriverManager::get_driver_by_name("MEM").unwrap();
+        let dataset = driver
+            .create_with_band_type::<u8, _>("", width, height, 1)
+            .unwrap();
+
+        // Each 2x2 block holds base + {0, 2, 4, 6}, so its mean is base + 3
+        let data: Vec<u8> = (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                let base = 10 * (x / 2 + 4 * (y / 2));
+                (base + 2 * (x % 2 + 2 * (y % 2))) as u8
+            })
+            .collect();
+        let mut buffer = gdal::raster::Buffer::new((width, height), data);
+        dataset
+            .rasterband(1)
+            .unwrap()
+            .write((0, 0), (width, height), &mut buffer)
+            .unwrap();
+        let img = Image::from_dataset(dataset);
+
+        let half = img
+            .read_window_resampled_u8(0, 0, width, height, (4, 3), ResampleAlg::Average)
+            .unwrap();
+        assert_eq!(half.shape(), &[3, 4, 1]);
+        for by in 0..3 {
+            for bx in 0..4 {
+                assert_eq!(half[[by, bx, 0]], (10 * (bx + 4 * by) + 3) as u8);
+            }
+        }
+
+        // The thumbnail helper averages the same way
+        assert_eq!(img.thumbnail_u8(4).unwrap(), half);
+    }
+
+    #[test]
+    fn test_thumbnail_small_image_full_resolution() {
+        let img = mem_image(6, 4, 2);
+        assert_eq!(img.thumbnail_u8(10).unwrap(), img.read_u8().unwrap());
+        assert!(img.thumbnail_u8(0).is_err());
+    }
+
+    /// In-memory single-band image with a geotransform and EPSG CRS
+    fn georeferenced_image(width: usize, height: usize, gt: [f64; 6], epsg: u32) -> Image {
+        let driver = DriverManager::get_driver_by_name("MEM").unwrap();
+        let mut dataset = driver
+            .create_with_band_type::<u8, _>("", width, height, 1)
+            .unwrap();
+        dataset.set_geo_transform(&gt).unwrap();
+        dataset
+            .set_spatial_ref(&SpatialRef::from_epsg(epsg).unwrap())
+            .unwrap();
+
+        Image::from_dataset(dataset)
+    }
+
+    #[test]
+    fn test_corner_coordinates() {
+        let gt = [500000.0, 10.0, 0.0, 4300000.0, 0.0, -10.0];
+        let img = georeferenced_image(100, 50, gt, 32618);
+
+        let corners = img.corner_coordinates().unwrap();
+        assert_eq!(corners[0], (500000.0, 4300000.0));
+        assert_eq!(corners[1], (501000.0, 4300000.0));
+        assert_eq!(corners[2], (501000.0, 4299500.0));
+        assert_eq!(corners[3], (500000.0, 4299500.0));
+
+        // No geotransform on a plain MEM dataset
+        assert!(mem_image(4, 4, 1).corner_coordinates().is_none());
+    }
+
+    #[test]
+    fn test_footprint_lla_geographic() {
+        let gt = [-77.0, 0.001, 0.0, 39.0, 0.0, -0.001];
+        let img = georeferenced_image(200, 100, gt, 4326);
+
+        let footprint = img.footprint_lla().unwrap();
+        assert!((footprint[0].lon + 77.0).abs() < 1e-12);
+        assert!((footprint[0].lat - 39.0).abs() < 1e-12);
+        assert!((footprint[2].lon + 76.8).abs() < 1e-12);
+        assert!((footprint[2].lat - 38.9).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_footprint_lla_projected() {
+        // UTM 18N: easting 500000 lies on the -75 degree central meridian
+        let gt = [500000.0, 10.0, 0.0, 4300000.0, 0.0, -10.0];
+        let img = georeferenced_image(100, 50, gt, 32618);
+
+        let footprint = img.footprint_lla().unwrap();
+        assert!((footprint[0].lon + 75.0).abs() < 1e-9);
+        assert!((footprint[0].lat - 38.85).abs() < 0.05);
+        assert!(footprint[1].lon > footprint[0].lon);
+        assert!(footprint[3].lat < footprint[0].lat);
+    }
+
+    #[test]
+    fn test_footprint_lla_with_transform() {
+        let img = georeferenced_image(10, 10, [0.0, 1.0, 0.0, 10.0, 0.0, -1.0], 4326);
+
+        let footprint = img.footprint_lla_with(|x, y| Some((x * 0.5, y * 0.25))).unwrap();
+        assert_eq!(footprint[2].lon, 5.0);
+        assert_eq!(footprint[2].lat, 0.0);
+        assert_eq!(footprint[0].lat, 2.5);
+    }
+
+    #[test]
+    fn test_from_array_u8_roundtrip() {
+        let data = Array3::from_shape_fn((5, 7, 3), |(y, x, b)| (x + 10 * y + 100 * b) as u8);
+        let gt = [500000.0, 2.0, 0.0, 4000000.0, 0.0, -2.0];
+        
+        let img = Image::from_array_u8(&data, Some(gt), Some("EPSG:32617")).unwrap();
+        assert_eq!(img.size(), (7, 5));
+        assert_eq!(img.band_count(), 3);
+        assert_eq!(img.read_u8().unwrap(), data);
+        assert_eq!(img.geotransform(), Some(gt));
+        assert!(img.dataset().spatial_ref().unwrap().is_projected());
+    }
+    
+    #[test]
+    fn test_from_array_u8_without_georeferencing() {
+        let data = Array3::from_elem((2, 3, 1), 9u8);
+        let img = Image::from_array_u8(&data, None, None).unwrap();
+        assert_eq!(img.read_u8().unwrap(), data);
+        assert!(img.projection().is_none());
+        
+        let empty = Array3::<u8>::zeros((0, 3, 1));
+        assert!(matches!(
+            Image::from_array_u8(&empty, None, None),
+            Err(ImageError::InvalidDimensions)
+        ));
+    }
+    
+    #[test]
+    fn test_projection_epsg_roundtrip() {
+        let mut img = mem_image(4, 4, 1);
+        assert_eq!(img.epsg_code(), None);
+        
+        img.set_projection_epsg(32617).unwrap();
+        assert_eq!(img.epsg_code(), Some(32617));
+        
+        img.set_projection_epsg(4326).unwrap();
+        assert_eq!(img.epsg_code(), Some(4326));
+    }
+    
+    #[test]
+    fn test_set_spatial_ref_from_epsg_and_wkt() {
+        let mut img = mem_image(4, 4, 1);
+        img.set_spatial_ref_from_epsg(32617).unwrap();
+        assert_eq!(img.epsg_code(), Some(32617));
+        
+        // The WKT written for the EPSG code reads back as the same CRS
+        let wkt = img.projection().unwrap();
+        let mut other = mem_image(4, 4, 1);
+        other.set_spatial_ref_from_wkt(&wkt).unwrap();
+        assert_eq!(other.epsg_code(), Some(32617));
+        
+        // Coexists with set_projection_epsg: the last call wins
+        other.set_projection_epsg(4326).unwrap();
+        assert_eq!(other.epsg_code(), Some(4326));
+        
+        assert!(other.set_spatial_ref_from_wkt("not a wkt").is_err());
+        assert_eq!(other.epsg_code(), Some(4326));
+    }
+    
+    #[test]
+    fn test_subdataset_names_ordered() {
+        let driver = DriverManager::get_driver_by_name("MEM").unwrap();
+        let mut dataset = driver.create_with_band_type::<u8, _>("", 4, 4, 1).unwrap();
+        
+        // Out of order, with an index past 9 and descriptions mixed in
+        for (key, value) in [
+            ("SUBDATASET_2_NAME", "NITF_IM:1:scene.ntf"),
+            ("SUBDATASET_10_NAME", "NITF_IM:9:scene.ntf"),
+            ("SUBDATASET_1_NAME", "NITF_IM:0:scene.ntf"),
+            ("SUBDATASET_1_DESC", "Image 1"),
+        ] {
+            dataset.set_metadata_item(key, value, "SUBDATASETS").unwrap();
+        }
+        
+        assert_eq!(
+            subdataset_names(&dataset),
+            ["NITF_IM:0:scene.ntf", "NITF_IM:1:scene.ntf", "NITF_IM:9:scene.ntf"]
+        );
+    }
+    
+    #[test]
+    fn test_metadata_domain_and_item() {
+        let mut img = mem_image(4, 4, 1);
+        for (key, value) in [("SENSOR", "WV03"), ("SUN_ELEVATION", "54.2"), ("NOTE", "a=b")] {
+            img.dataset.set_metadata_item(key, value, "VENDOR").unwrap();
+        }
+        img.dataset.set_metadata_item("SENSOR", "default", "").unwrap();
+        
+        let mut items = img.metadata_domain("VENDOR").unwrap();
+        items.sort();
+        assert_eq!(
+            items,
+            [
+                ("NOTE".to_string(), "a=b".to_string()),
+                ("SENSOR".to_string(), "WV03".to_string()),
+                ("SUN_ELEVATION".to_string(), "54.2".to_string()),
+            ]
+        );
+        
+        assert_eq!(img.metadata_item("SENSOR", Some("VENDOR")).as_deref(), Some("WV03"));
+        assert_eq!(img.metadata_item("SENSOR", None).as_deref(), Some("default"));
+        assert_eq!(img.metadata_item("MISSING", Some("VENDOR")), None);
+        assert!(img.metadata_domain("NO_SUCH_DOMAIN").is_none());
+    }
+    
+    #[test]
+    fn test_subdataset_names_none() {
+        let img = mem_image(4, 4, 1);
+        assert!(subdataset_names(img.dataset()).is_empty());
+    }
+
     // Note: Full integration tests for Image would require actual GDAL-compatible
     // image files. These would be better placed in an integration test directory
     // with test fixtures. The tests below document the expected API.
@@ -278,4 +1766,109 @@ mod tests {
     //         assert!(metadata.rpc.is_some());
     //     }
     // }
+
+    #[test]
+    fn test_read_display_u8_u16_ramp() {
+        let driver = DriverManager::get_driver_by_name("MEM").unwrap();
+        let dataset = driver.create_with_band_type::<u16, _>("", 100, 4, 1).unwrap();
+        let values: Vec<u16> = (0..400).map(|i| (i % 100) as u16 * 600).collect();
+        let mut buffer = Buffer::new((100, 4), values);
+        dataset.rasterband(1).unwrap().write((0, 0), (100, 4), &mut buffer).unwrap();
+        let img = Image::from_dataset(dataset);
+
+        let display = img.read_display_u8(1).unwrap();
+        assert_eq!(display.dim(), (4, 100));
+        assert_eq!(display.iter().min(), Some(&0));
+        assert_eq!(display.iter().max(), Some(&255));
+        for row in display.rows() {
+            assert!(row.iter().zip(row.iter().skip(1)).all(|(a, b)| a <= b));
+        }
+    }
+
+    #[test]
+    fn test_read_display_u8_passthrough() {
+        let img = mem_image(7, 5, 2);
+        let display = img.read_display_u8(2).unwrap();
+        let raw = img.read_u8().unwrap();
+        assert_eq!(display, raw.index_axis(Axis(2), 1));
+
+        assert!(matches!(
+            img.read_display_u8(3),
+            Err(ImageError::InvalidBand { requested: 3, available: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_read_u8_chw_matches_hwc() {
+        let img = mem_image(6, 4, 3);
+        let hwc = img.read_u8().unwrap();
+        let chw = img.read_u8_chw().unwrap();
+
+        assert_eq!(chw.shape(), &[3, 4, 6]);
+        assert_eq!(chw.view().permuted_axes([1, 2, 0]), hwc);
+
+        let window = img.read_window_u8_chw(1, 2, 4, 2).unwrap();
+        let expected = img.read_window_u8(1, 2, 4, 2).unwrap();
+        assert_eq!(window.view().permuted_axes([1, 2, 0]), expected);
+
+        let f32_chw = img.read_f32_chw().unwrap();
+        assert_eq!(f32_chw, chw.mapv(|v| v as f32));
+    }
+
+    #[test]
+    fn test_read_window_chw_invalid() {
+        let img = mem_image(6, 4, 1);
+        assert!(matches!(img.read_window_u8_chw(4, 0, 3, 1), Err(ImageError::InvalidDimensions)));
+        assert!(matches!(img.read_window_u8_chw(0, 0, 0, 1), Err(ImageError::InvalidDimensions)));
+    }
+
+    #[test]
+    fn test_read_u8_layout() {
+        let img = mem_image(5, 3, 2);
+        let hwc = img.read_u8_layout(ArrayLayout::default()).unwrap();
+        let chw = img.read_u8_layout(ArrayLayout::BandsFirst).unwrap();
+
+        assert_eq!(hwc, img.read_u8().unwrap());
+        assert_eq!(chw.shape(), &[2, 3, 5]);
+        assert_eq!(chw.view().permuted_axes([1, 2, 0]), hwc);
+    }
+
+    #[test]
+    fn test_write_window_u8_from_f32_clamps() {
+        let mut img = mem_image(4, 2, 1);
+        let data = Array3::from_shape_vec((1, 3, 1), vec![-5.0f32, 127.5, 300.0]).unwrap();
+
+        img.write_window_u8_from_f32(1, 1, &data, true).unwrap();
+        let written = img.read_window_u8(1, 1, 3, 1).unwrap();
+        assert_eq!(written.iter().copied().collect::<Vec<_>>(), vec![0, 128, 255]);
+
+        // Without clamping nothing is written and the bad values are counted
+        let before = img.read_u8().unwrap();
+        let result = img.write_window_u8_from_f32(0, 0, &data, false);
+        assert!(matches!(result, Err(ImageError::OutOfRange { count: 2, max: 255 })));
+        assert_eq!(img.read_u8().unwrap(), before);
+    }
+
+    #[test]
+    fn test_write_window_u16_from_f32() {
+        let driver = DriverManager::get_driver_by_name("MEM").unwrap();
+        let dataset = driver.create_with_band_type::<u16, _>("", 2, 2, 2).unwrap();
+        let mut img = Image::from_dataset(dataset);
+
+        let data = Array3::from_shape_fn((2, 2, 2), |(y, x, b)| {
+            [-1.0f32, 1000.4, 70000.0, f32::NAN][y * 2 + x] + b as f32
+        });
+        img.write_window_u16_from_f32(0, 0, &data, true).unwrap();
+        let written = img.read_u16().unwrap();
+        let first: Vec<u16> = written.index_axis(Axis(2), 0).iter().copied().collect();
+        assert_eq!(first, vec![0, 1000, 65535, 0]);
+        assert_eq!(written[[0, 0, 1]], 0);
+        assert_eq!(written[[0, 1, 1]], 1001);
+
+        let wrong_bands = Array3::<f32>::zeros((2, 2, 1));
+        assert!(matches!(
+            img.write_window_u16_from_f32(0, 0, &wrong_bands, true),
+            Err(ImageError::InvalidDimensions)
+        ));
+    }
 }
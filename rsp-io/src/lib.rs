@@ -1,9 +1,14 @@
 
 //! I/O operations for photogrammetry data
 
+pub mod geotransform;
 pub mod image;
 pub mod metadata;
+pub mod processing;
+pub mod rpb;
 
-pub use image::{Image, ImageError};
-pub use metadata::ImageMetadata;
+pub use geotransform::{geotransform_from_bounds, invert_geotransform, pixel_to_geo};
+pub use image::{subdatasets, ArrayLayout, Image, ImageError, RasterData, ResampleAlg};
+pub use metadata::{parse_rpc00b_tre, ImageMetadata};
+pub use rpb::{read_rpb, write_rpb};
 pub use rsp_core::sensor::RpcCoefficients;
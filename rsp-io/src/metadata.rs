@@ -1,8 +1,16 @@
+use std::collections::HashMap;
+
 use gdal::{Dataset, Metadata};
 use nalgebra::{Vector3, UnitQuaternion};
 use rsp_core::sensor::RpcCoefficients;
 use rsp_core::error::{RspError, Result};
 
+/// Widths of the RPC00B fields from `ERR_BIAS` through `HEIGHT_SCALE`
+const RPC00B_HEADER_WIDTHS: [usize; 12] = [7, 7, 6, 5, 8, 9, 5, 6, 5, 8, 9, 5];
+
+/// Width of each of the 80 RPC00B polynomial coefficients
+const RPC00B_COEFF_WIDTH: usize = 12;
+
 /// Container for all image metadata
 #[derive(Debug, Clone, Default)]
 pub struct ImageMetadata {
@@ -11,13 +19,24 @@ pub struct ImageMetadata {
     pub imu_orientation: Option<UnitQuaternion<f64>>,
     pub timestamp: Option<f64>,
     pub camera_id: Option<String>,
+    /// Raw NITF tagged record extensions (GDAL `TRE` domain), keyed by tag
+    pub tres: HashMap<String, String>,
 }
 
 impl ImageMetadata {
     /// Extract all available metadata from GDAL dataset
+    ///
+    /// RPCs come from the `RPC` domain, falling back to an `RPC00B` TRE when
+    /// the driver didn't translate it.
     pub fn from_gdal_dataset(dataset: &Dataset) -> Self {
+        let tres = extract_tres(dataset);
+        let rpc = extract_rpc(dataset)
+            .ok()
+            .or_else(|| tres.get("RPC00B").and_then(|tre| parse_rpc00b_tre(tre).ok()));
+
         Self {
-            rpc: extract_rpc(dataset).ok(),
+            rpc,
+            tres,
             ..Default::default()
         }
     }
@@ -65,9 +84,52 @@ fn extract_rpc(dataset: &Dataset) -> Result<RpcCoefficients> {
         line_scale: parse_single(&metadata, "LINE_SCALE")?,
         samp_off: parse_single(&metadata, "SAMP_OFF")?,
         samp_scale: parse_single(&metadata, "SAMP_SCALE")?,
+        // Optional; missing or unreadable error terms are unknown
+        err_bias: parse_single(&metadata, "ERR_BIAS").ok().and_then(RpcCoefficients::error_term),
+        err_rand: parse_single(&metadata, "ERR_RAND").ok().and_then(RpcCoefficients::error_term),
     })
 }
 
+/// Collect NITF TREs from the GDAL `TRE` metadata domain
+fn extract_tres(dataset: &Dataset) -> HashMap<String, String> {
+    dataset
+        .metadata_domain("TRE")
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|item| {
+            let (tag, value) = item.split_once('=')?;
+            Some((tag.trim().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Parse the fixed-width body of a NITF RPC00B TRE
+///
+/// The body is a one-character `SUCCESS` flag followed by `ERR_BIAS`,
+/// `ERR_RAND`, the ten normalization offsets/scales and 80 coefficients of
+/// 12 characters each (1041 characters in total).
+pub fn parse_rpc00b_tre(tre: &str) -> Result<RpcCoefficients> {
+    let widths = RPC00B_HEADER_WIDTHS
+        .into_iter()
+        .chain(std::iter::repeat_n(RPC00B_COEFF_WIDTH, 80));
+
+    // Skip the SUCCESS flag
+    let mut pos = 1;
+    let mut values = Vec::with_capacity(92);
+    for width in widths {
+        let field = tre
+            .get(pos..pos + width)
+            .ok_or_else(|| RspError::Io(format!("RPC00B TRE truncated at offset {}", pos)))?;
+        let value = field.trim().parse().map_err(|_| {
+            RspError::Io(format!("Failed to parse RPC00B field at offset {}: {:?}", pos, field))
+        })?;
+        values.push(value);
+        pos += width;
+    }
+
+    RpcCoefficients::from_rpc00b(&values)
+}
+
 fn parse_coeff_array(
     metadata: &std::collections::HashMap<String, String>,
     prefix: &str,
@@ -115,6 +177,7 @@ mod tests {
         assert!(metadata.imu_orientation.is_none());
         assert!(metadata.timestamp.is_none());
         assert!(metadata.camera_id.is_none());
+        assert!(metadata.tres.is_empty());
     }
 
     #[test]
@@ -124,10 +187,6 @@ mod tests {
 
         // Create minimal RPC coefficients
         let rpc = RpcCoefficients {
-            line_num_coeff: [0.0; 20],
-            line_den_coeff: [0.0; 20],
-            samp_num_coeff: [0.0; 20],
-            samp_den_coeff: [0.0; 20],
             lat_off: 0.0,
             lat_scale: 1.0,
             lon_off: 0.0,
@@ -138,6 +197,7 @@ mod tests {
             line_scale: 1.0,
             samp_off: 0.0,
             samp_scale: 1.0,
+            ..Default::default()
         };
 
         metadata.rpc = Some(rpc);
@@ -232,6 +292,7 @@ mod tests {
             imu_orientation: None,
             timestamp: Some(12345.6),
             camera_id: Some("CAM01".to_string()),
+            tres: HashMap::new(),
         };
 
         let metadata2 = metadata1.clone();
@@ -239,4 +300,97 @@ mod tests {
         assert_eq!(metadata2.timestamp, Some(12345.6));
         assert_eq!(metadata2.camera_id, Some("CAM01".to_string()));
     }
+
+    /// RPC00B body with a linear model: line = -lat, sample = lon (normalized)
+    fn rpc00b_body() -> String {
+        let mut body = String::from("1");
+        for field in [
+            "0001.50", "0000.75", "001000", "02000", "+39.0000", "-077.0000", "+0100",
+            "001000", "02000", "+00.0500", "+000.0600", "+0500",
+        ] {
+            body.push_str(field);
+        }
+        for i in 0..80 {
+            body.push_str(match i {
+                1 => "-1.000000E+0",
+                20 | 42 | 60 => "+1.000000E+0",
+                _ => "+0.000000E+0",
+            });
+        }
+        body
+    }
+
+    #[test]
+    fn test_parse_rpc00b_tre() {
+        let body = rpc00b_body();
+        assert_eq!(body.len(), 1041);
+
+        let rpc = parse_rpc00b_tre(&body).unwrap();
+        assert_eq!(rpc.line_off, 1000.0);
+        assert_eq!(rpc.samp_off, 2000.0);
+        assert_eq!(rpc.lat_off, 39.0);
+        assert_eq!(rpc.lon_off, -77.0);
+        assert_eq!(rpc.height_off, 100.0);
+        assert_eq!(rpc.lat_scale, 0.05);
+        assert_eq!(rpc.lon_scale, 0.06);
+        assert_eq!(rpc.height_scale, 500.0);
+        assert_eq!(rpc.line_num_coeff[1], -1.0);
+        assert_eq!(rpc.line_den_coeff[0], 1.0);
+        assert_eq!(rpc.samp_num_coeff[2], 1.0);
+        assert_eq!(rpc.samp_den_coeff[0], 1.0);
+        assert_eq!((rpc.err_bias, rpc.err_rand), (Some(1.5), Some(0.75)));
+    }
+
+    #[test]
+    fn test_from_gdal_dataset_error_terms() {
+        let driver = gdal::DriverManager::get_driver_by_name("MEM").unwrap();
+        let mut dataset = driver.create("", 4, 4, 1).unwrap();
+
+        let mut items = vec![
+            ("LINE_OFF", "1000"),
+            ("SAMP_OFF", "2000"),
+            ("LAT_OFF", "39.0"),
+            ("LONG_OFF", "-77.0"),
+            ("HEIGHT_OFF", "100"),
+            ("LINE_SCALE", "1000"),
+            ("SAMP_SCALE", "2000"),
+            ("LAT_SCALE", "0.05"),
+            ("LONG_SCALE", "0.06"),
+            ("HEIGHT_SCALE", "500"),
+            ("ERR_BIAS", "5.25"),
+            ("ERR_RAND", "0.5"),
+        ];
+        let coeff_keys: Vec<String> = ["LINE_NUM", "LINE_DEN", "SAMP_NUM", "SAMP_DEN"]
+            .iter()
+            .flat_map(|block| (1..=20).map(move |i| format!("{}_COEFF_{}", block, i)))
+            .collect();
+        items.extend(coeff_keys.iter().map(|key| (key.as_str(), "0.5")));
+        for (key, value) in &items {
+            dataset.set_metadata_item(key, value, "RPC").unwrap();
+        }
+
+        let rpc = ImageMetadata::from_gdal_dataset(&dataset).rpc.unwrap();
+        assert_eq!((rpc.err_bias, rpc.err_rand), (Some(5.25), Some(0.5)));
+        assert_eq!(rpc.lat_scale, 0.05);
+        let model = rsp_core::sensor::RpcModel::new(rpc.clone());
+        assert_eq!(model.geolocation_accuracy(), Some((5.25, 0.5)));
+
+        // Unknown (-1) and absent error terms
+        dataset.set_metadata_item("ERR_BIAS", "-1.0", "RPC").unwrap();
+        let rpc = ImageMetadata::from_gdal_dataset(&dataset).rpc.unwrap();
+        assert_eq!(rpc.err_bias, None);
+        assert_eq!(rsp_core::sensor::RpcModel::new(rpc).geolocation_accuracy(), None);
+    }
+
+    #[test]
+    fn test_parse_rpc00b_tre_truncated() {
+        let body = rpc00b_body();
+        assert!(parse_rpc00b_tre(&body[..1000]).is_err());
+    }
+
+    #[test]
+    fn test_parse_rpc00b_tre_invalid_field() {
+        let body = rpc00b_body().replacen("+39.0000", "+39.00x0", 1);
+        assert!(parse_rpc00b_tre(&body).is_err());
+    }
 }
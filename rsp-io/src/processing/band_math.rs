@@ -0,0 +1,203 @@
+use ndarray::{Array2, Zip};
+use rsp_core::error::{Result, RspError};
+
+/// Evaluate an arithmetic expression over co-registered bands
+///
+/// The grammar is `+ - * /`, unary minus, parentheses, numeric literals and
+/// band references `b0`, `b1`, ... indexing into `images`, e.g. NDVI as
+/// `(b1 - b0) / (b1 + b0)`. Evaluation is per pixel in `f32`; division by
+/// zero gives NaN. Fails with `InvalidInput` for a malformed expression, a
+/// band index past the end of `images` or bands of different shapes.
+pub fn band_math(images: &[&Array2<f32>], expr: &str) -> Result<Array2<f32>> {
+    let tree = Parser::new(expr).parse()?;
+
+    let Some(first) = images.first() else {
+        return Err(RspError::InvalidInput("band_math needs at least one band".to_string()));
+    };
+    if let Some(other) = images.iter().find(|image| image.dim() != first.dim()) {
+        return Err(RspError::InvalidInput(format!(
+            "Band shapes differ: {:?} vs {:?}",
+            first.dim(),
+            other.dim()
+        )));
+    }
+
+    evaluate(&tree, images, first.dim())
+}
+
+/// Parsed expression tree
+#[derive(Debug)]
+enum Expr {
+    Constant(f32),
+    Band(usize),
+    Negate(Box<Expr>),
+    Binary(char, Box<Expr>, Box<Expr>),
+}
+
+fn evaluate(expr: &Expr, images: &[&Array2<f32>], dim: (usize, usize)) -> Result<Array2<f32>> {
+    Ok(match expr {
+        Expr::Constant(value) => Array2::from_elem(dim, *value),
+        Expr::Band(index) => {
+            let band = images.get(*index).ok_or_else(|| {
+                RspError::InvalidInput(format!(
+                    "Band b{} referenced but only {} bands given",
+                    index,
+                    images.len()
+                ))
+            })?;
+            (*band).clone()
+        }
+        Expr::Negate(inner) => -evaluate(inner, images, dim)?,
+        Expr::Binary(op, lhs, rhs) => {
+            let lhs = evaluate(lhs, images, dim)?;
+            let rhs = evaluate(rhs, images, dim)?;
+            Zip::from(&lhs).and(&rhs).map_collect(|&a, &b| match op {
+                '+' => a + b,
+                '-' => a - b,
+                '*' => a * b,
+                _ if b == 0.0 => f32::NAN,
+                _ => a / b,
+            })
+        }
+    })
+}
+
+/// Recursive-descent parser:
+///
+/// ```text
+/// expr   = term (('+' | '-') term)*
+/// term   = factor (('*' | '/') factor)*
+/// factor = '-' factor | '(' expr ')' | number | 'b' digits
+/// ```
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn parse(mut self) -> Result<Expr> {
+        let expr = self.expr()?;
+        match self.peek() {
+            None => Ok(expr),
+            Some(c) => Err(self.error(&format!("unexpected '{}'", c))),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr> {
+        let mut lhs = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.term()?));
+        }
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<Expr> {
+        let mut lhs = self.factor()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.pos += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.factor()?));
+        }
+        Ok(lhs)
+    }
+
+    fn factor(&mut self) -> Result<Expr> {
+        match self.peek() {
+            Some('-') => {
+                self.pos += 1;
+                Ok(Expr::Negate(Box::new(self.factor()?)))
+            }
+            Some('(') => {
+                self.pos += 1;
+                let inner = self.expr()?;
+                if self.peek() != Some(')') {
+                    return Err(self.error("expected ')'"));
+                }
+                self.pos += 1;
+                Ok(inner)
+            }
+            Some('b') => {
+                self.pos += 1;
+                let digits = self.take_while(|c| c.is_ascii_digit());
+                digits
+                    .parse()
+                    .map(Expr::Band)
+                    .map_err(|_| self.error("expected a band number after 'b'"))
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let number = self.take_while(|c| c.is_ascii_digit() || c == '.');
+                number
+                    .parse()
+                    .map(Expr::Constant)
+                    .map_err(|_| self.error(&format!("invalid number '{}'", number)))
+            }
+            Some(c) => Err(self.error(&format!("unexpected '{}'", c))),
+            None => Err(self.error("unexpected end of expression")),
+        }
+    }
+
+    /// Next non-whitespace character, without consuming it
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.input[self.pos..];
+        let trimmed = rest.trim_start();
+        self.pos += rest.len() - trimmed.len();
+        trimmed.chars().next()
+    }
+
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let rest = &self.input[self.pos..];
+        let len = rest.find(|c| !pred(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn error(&self, message: &str) -> RspError {
+        RspError::InvalidInput(format!(
+            "Invalid band expression {:?} at offset {}: {}",
+            self.input, self.pos, message
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_band_math_ndvi() {
+        let red = Array2::from_shape_fn((3, 4), |(r, c)| (10 * r + c) as f32);
+        let nir = Array2::from_shape_fn((3, 4), |(r, c)| (40 - 5 * r + 2 * c) as f32);
+
+        let ndvi = band_math(&[&red, &nir], "(b1 - b0) / (b1 + b0)").unwrap();
+        assert_eq!(ndvi.dim(), (3, 4));
+        for ((r, c), &value) in ndvi.indexed_iter() {
+            let (red, nir) = (red[[r, c]], nir[[r, c]]);
+            assert!((value - (nir - red) / (nir + red)).abs() < 1e-6);
+        }
+
+        // Precedence, unary minus, literals and division by zero
+        let out = band_math(&[&red, &nir], "-b0 + 2 * b1 / 4").unwrap();
+        assert_eq!(out[[1, 1]], -11.0 + 2.0 * 37.0 / 4.0);
+        let zero = Array2::zeros((3, 4));
+        let out = band_math(&[&red, &zero], "b0 / b1").unwrap();
+        assert!(out.iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_band_math_errors() {
+        let band = Array2::from_elem((2, 2), 1.0f32);
+        for expr in ["(b1 - b0", "b0 +", "b0 b1", "bx", "b0 % 2", ""] {
+            let result = band_math(&[&band, &band], expr);
+            assert!(matches!(result, Err(RspError::InvalidInput(_))), "{:?}", expr);
+        }
+
+        // Band past the end and mismatched shapes
+        assert!(band_math(&[&band], "b0 + b1").is_err());
+        let other = Array2::from_elem((3, 2), 1.0f32);
+        assert!(band_math(&[&band, &other], "b0").is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use ndarray::{Array2, Array3};
+
+/// Color scale used by [`colorize`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMap {
+    /// Black to white
+    #[default]
+    Grayscale,
+    /// Google's Turbo: dark blue through green and yellow to dark red
+    Turbo,
+    /// Matplotlib's Viridis: perceptually uniform purple to yellow
+    Viridis,
+}
+
+/// Polynomial fit of Turbo, one set of coefficients per channel (lowest order first)
+const TURBO: [[f32; 6]; 3] = [
+    [0.135_721_38, 4.615_392_6, -42.660_32, 132.131_08, -152.942_39, 59.286_38],
+    [0.091_402_61, 2.194_188_4, 4.842_966_6, -14.185_033, 4.277_298_5, 2.829_566],
+    [0.106_673_3, 12.641_946, -60.582_05, 110.362_77, -89.903_11, 27.348_25],
+];
+
+/// Polynomial fit of Viridis, one set of coefficients per channel (lowest order first)
+const VIRIDIS: [[f32; 7]; 3] = [
+    [0.277_727_33, 0.105_093_04, -0.330_861_83, -4.634_230_6, 6.228_27, 4.776_385, -5.435_456],
+    [0.005_407_345, 1.404_613_5, 0.214_847_56, -5.799_101, 14.179_933, -13.745_145, 4.645_853],
+    [0.334_099_8, 1.384_590_2, 0.095_095_16, -19.332_441, 56.690_55, -65.353_03, 26.312_435],
+];
+
+/// Map values to RGB for display (shape: [height, width, 3])
+///
+/// Values are scaled linearly from `[min, max]` onto the color map and
+/// clamped at both ends; with `max <= min` every valid value gets the low
+/// end. NaN and infinite values are black. The result can be wrapped with
+/// [`Image::from_array_u8`](crate::Image::from_array_u8) to write it out.
+pub fn colorize(values: &Array2<f32>, min: f32, max: f32, cmap: ColorMap) -> Array3<u8> {
+    let (rows, cols) = values.dim();
+    let scale = if max > min { 1.0 / (max - min) } else { 0.0 };
+
+    let mut rgb = Array3::zeros((rows, cols, 3));
+    for ((r, c), &v) in values.indexed_iter() {
+        if !v.is_finite() {
+            continue;
+        }
+
+        let t = ((v - min) * scale).clamp(0.0, 1.0);
+        let color = match cmap {
+            ColorMap::Grayscale => [t; 3],
+            ColorMap::Turbo => TURBO.map(|coeffs| polynomial(&coeffs, t)),
+            ColorMap::Viridis => VIRIDIS.map(|coeffs| polynomial(&coeffs, t)),
+        };
+        for (b, channel) in color.iter().enumerate() {
+            rgb[[r, c, b]] = (channel * 255.0).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+
+    rgb
+}
+
+/// Horner evaluation of a polynomial with coefficients lowest order first
+fn polynomial(coeffs: &[f32], t: f32) -> f32 {
+    coeffs.iter().rev().fold(0.0, |acc, c| acc * t + c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, s};
+
+    fn ramp() -> Array2<f32> {
+        Array2::from_shape_fn((1, 64), |(_, c)| 10.0 + c as f32 * 0.5)
+    }
+
+    fn luma(rgb: &Array3<u8>, c: usize) -> f32 {
+        let channel = |b: usize| rgb[[0, c, b]] as f32;
+        0.299 * channel(0) + 0.587 * channel(1) + 0.114 * channel(2)
+    }
+
+    #[test]
+    fn test_colorize_ramp_is_monotonic() {
+        let values = ramp();
+
+        let gray = colorize(&values, 10.0, 41.5, ColorMap::Grayscale);
+        assert_eq!(gray.dim(), (1, 64, 3));
+        assert_eq!([gray[[0, 0, 0]], gray[[0, 63, 0]]], [0, 255]);
+        for c in 1..64 {
+            assert!(gray[[0, c, 0]] > gray[[0, c - 1, 0]]);
+            assert!(gray[[0, c, 0]] == gray[[0, c, 1]] && gray[[0, c, 1]] == gray[[0, c, 2]]);
+        }
+
+        // Viridis brightens steadily from purple to yellow
+        let viridis = colorize(&values, 10.0, 41.5, ColorMap::Viridis);
+        for c in 1..64 {
+            assert!(luma(&viridis, c) > luma(&viridis, c - 1), "column {}", c);
+        }
+
+        // The fit stays within a few levels of the reference endpoints
+        for (c, reference) in [(0, [68, 1, 84]), (63, [253, 231, 37])] {
+            for (b, &expected) in reference.iter().enumerate() {
+                assert!(viridis[[0, c, b]].abs_diff(expected) <= 4, "column {} band {}", c, b);
+            }
+        }
+
+        // Turbo runs from blue to red, changing color at every step
+        let turbo = colorize(&values, 10.0, 41.5, ColorMap::Turbo);
+        for c in 1..64 {
+            assert_ne!(turbo.slice(s![0, c - 1, ..]), turbo.slice(s![0, c, ..]));
+        }
+        assert!(turbo[[0, 8, 2]] > turbo[[0, 8, 0]]);
+        assert!(turbo[[0, 55, 0]] > turbo[[0, 55, 2]]);
+    }
+
+    #[test]
+    fn test_colorize_invalid_and_clamped() {
+        let values = array![[f32::NAN, f32::INFINITY, -5.0, 100.0]];
+        for cmap in [ColorMap::Grayscale, ColorMap::Turbo, ColorMap::Viridis] {
+            let rgb = colorize(&values, 0.0, 1.0, cmap);
+            assert_eq!(rgb.slice(s![0, 0, ..]).to_vec(), vec![0, 0, 0]);
+            assert_eq!(rgb.slice(s![0, 1, ..]).to_vec(), vec![0, 0, 0]);
+
+            // Out-of-range values saturate at the ends of the map
+            let low = colorize(&array![[0.0]], 0.0, 1.0, cmap);
+            let high = colorize(&array![[1.0]], 0.0, 1.0, cmap);
+            assert_eq!(rgb.slice(s![0, 2, ..]), low.slice(s![0, 0, ..]));
+            assert_eq!(rgb.slice(s![0, 3, ..]), high.slice(s![0, 0, ..]));
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use ndarray::Array3;
+
+/// Downsample by an integer factor with a box (area-average) filter
+///
+/// Each output pixel is the rounded mean of a `factor` x `factor` block of
+/// the input, per band. When the size isn't divisible by `factor` the last
+/// row/column of blocks is partial and averages only the pixels it covers,
+/// so the output is `ceil(height / factor)` x `ceil(width / factor)`.
+///
+/// # Panics
+/// Panics if `factor` is zero.
+pub fn downsample_area(src: &Array3<u8>, factor: usize) -> Array3<u8> {
+    assert!(factor > 0, "downsample factor must be positive");
+
+    let (height, width, bands) = src.dim();
+    let out_height = height.div_ceil(factor);
+    let out_width = width.div_ceil(factor);
+
+    Array3::from_shape_fn((out_height, out_width, bands), |(y, x, band)| {
+        let rows = y * factor..((y + 1) * factor).min(height);
+        let cols = x * factor..((x + 1) * factor).min(width);
+        let count = (rows.len() * cols.len()) as u32;
+
+        let mut sum = 0u32;
+        for r in rows {
+            for c in cols.clone() {
+                sum += src[[r, c, band]] as u32;
+            }
+        }
+        ((sum + count / 2) / count) as u8
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkerboard_becomes_uniform_gray() {
+        // 2x2-pixel squares: a period of 4 pixels
+        let board = Array3::from_shape_fn((16, 24, 1), |(y, x, _)| {
+            if (y / 2 + x / 2) % 2 == 0 { 0 } else { 255 }
+        });
+
+        let small = downsample_area(&board, 4);
+        assert_eq!(small.dim(), (4, 6, 1));
+        assert!(small.iter().all(|&v| v == 128));
+    }
+
+    #[test]
+    fn test_partial_edge_blocks() {
+        let src = Array3::from_shape_fn((5, 7, 2), |(y, x, b)| (10 * y + x + 100 * b) as u8);
+
+        let out = downsample_area(&src, 3);
+        assert_eq!(out.dim(), (2, 3, 2));
+
+        // Full block: rows 0..3, cols 0..3 -> mean of 10y + x = 11
+        assert_eq!(out[[0, 0, 0]], 11);
+        // Corner block: rows 3..5, col 6 -> mean of 36, 46
+        assert_eq!(out[[1, 2, 0]], 41);
+        assert_eq!(out[[1, 2, 1]], 141);
+    }
+
+    #[test]
+    fn test_factor_one_is_identity() {
+        let src = Array3::from_shape_fn((3, 4, 3), |(y, x, b)| (y * 40 + x * 7 + b) as u8);
+        assert_eq!(downsample_area(&src, 1), src);
+    }
+}
@@ -0,0 +1,49 @@
+use ndarray::{Array3, Axis};
+
+/// Tight bounding box of the pixels that hold data
+///
+/// A pixel is NoData when every band equals `nodata`. Returns the box as
+/// `(x_off, y_off, width, height)`, the window layout used by
+/// [`Image::read_window_u8`](crate::Image::read_window_u8), so slicing the
+/// array to it trims NoData borders before compositing. Returns `None` when
+/// no pixel holds data.
+pub fn data_extent(array: &Array3<u8>, nodata: u8) -> Option<(usize, usize, usize, usize)> {
+    let mut rows: Option<(usize, usize)> = None;
+    let mut cols: Option<(usize, usize)> = None;
+
+    for (y, row) in array.axis_iter(Axis(0)).enumerate() {
+        for (x, pixel) in row.axis_iter(Axis(0)).enumerate() {
+            if pixel.iter().all(|&v| v == nodata) {
+                continue;
+            }
+            rows = Some(rows.map_or((y, y), |(lo, hi)| (lo.min(y), hi.max(y))));
+            cols = Some(cols.map_or((x, x), |(lo, hi)| (lo.min(x), hi.max(x))));
+        }
+    }
+
+    let ((y0, y1), (x0, x1)) = (rows?, cols?);
+    Some((x0, y0, x1 - x0 + 1, y1 - y0 + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_extent_central_region() {
+        // Data in rows 3..7 and cols 2..10, one band zero at the data edge
+        let array = Array3::from_shape_fn((10, 12, 3), |(y, x, b)| {
+            let inside = (3..7).contains(&y) && (2..10).contains(&x);
+            if inside && !(x == 2 && b == 0) { 200 } else { 0 }
+        });
+
+        assert_eq!(data_extent(&array, 0), Some((2, 3, 8, 4)));
+    }
+
+    #[test]
+    fn test_data_extent_all_nodata() {
+        let array = Array3::from_elem((5, 5, 1), 255u8);
+        assert_eq!(data_extent(&array, 255), None);
+        assert_eq!(data_extent(&array, 0), Some((0, 0, 5, 5)));
+    }
+}
@@ -0,0 +1,106 @@
+use ndarray::Array2;
+
+/// Feathering weights from a chamfer distance to the edge of the valid data
+///
+/// Each valid pixel gets its 3x3 chamfer distance (steps of 1 and sqrt 2)
+/// to the nearest invalid pixel, with everything outside the array counted
+/// as invalid, minus one so that valid pixels on the data boundary weigh 0.
+/// Distances are divided by their maximum to give weights in [0, 1] that
+/// rise towards the interior; invalid pixels are 0. A region with no
+/// interior pixels gets all-zero weights.
+pub fn distance_to_edge_weights(mask: &Array2<bool>) -> Array2<f32> {
+    let (rows, cols) = mask.dim();
+    let mut dist = mask.mapv(|valid| if valid { f32::INFINITY } else { 0.0 });
+
+    // Neighbor distance, 0 outside the array
+    let at = |dist: &Array2<f32>, r: isize, c: isize| {
+        if r < 0 || c < 0 || r >= rows as isize || c >= cols as isize {
+            0.0
+        } else {
+            dist[[r as usize, c as usize]]
+        }
+    };
+    let diagonal = std::f32::consts::SQRT_2;
+
+    // Forward pass from the top-left, backward pass from the bottom-right
+    for r in 0..rows as isize {
+        for c in 0..cols as isize {
+            let d = dist[[r as usize, c as usize]];
+            if d == 0.0 {
+                continue;
+            }
+            let best = (at(&dist, r - 1, c - 1) + diagonal)
+                .min(at(&dist, r - 1, c) + 1.0)
+                .min(at(&dist, r - 1, c + 1) + diagonal)
+                .min(at(&dist, r, c - 1) + 1.0);
+            dist[[r as usize, c as usize]] = d.min(best);
+        }
+    }
+    for r in (0..rows as isize).rev() {
+        for c in (0..cols as isize).rev() {
+            let d = dist[[r as usize, c as usize]];
+            if d == 0.0 {
+                continue;
+            }
+            let best = (at(&dist, r + 1, c + 1) + diagonal)
+                .min(at(&dist, r + 1, c) + 1.0)
+                .min(at(&dist, r + 1, c - 1) + diagonal)
+                .min(at(&dist, r, c + 1) + 1.0);
+            dist[[r as usize, c as usize]] = d.min(best);
+        }
+    }
+
+    let interior = dist.mapv(|d| (d - 1.0).max(0.0));
+    let max = interior.iter().copied().fold(0.0, f32::max);
+    if max == 0.0 {
+        return interior;
+    }
+    interior / max
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_to_edge_weights_rectangle() {
+        // Valid 9x13 rectangle inside a 13x17 invalid frame, centered on (6, 8)
+        let mask = Array2::from_shape_fn((13, 17), |(r, c)| {
+            (2..11).contains(&r) && (2..15).contains(&c)
+        });
+        let weights = distance_to_edge_weights(&mask);
+
+        for ((r, c), &w) in weights.indexed_iter() {
+            let border = r == 2 || r == 10 || c == 2 || c == 14;
+            if !mask[[r, c]] || border {
+                assert_eq!(w, 0.0, "({}, {})", r, c);
+            }
+        }
+
+        // Rising towards the center, then flat where the short side limits it
+        for c in 2..6 {
+            assert!(weights[[6, c + 1]] > weights[[6, c]]);
+        }
+        for c in 6..8 {
+            assert_eq!(weights[[6, c + 1]], weights[[6, c]]);
+        }
+        for r in 2..6 {
+            assert!(weights[[r + 1, 8]] > weights[[r, 8]]);
+        }
+        assert_eq!(weights[[6, 8]], 1.0);
+        assert!(weights.iter().all(|&w| (0.0..=1.0).contains(&w)));
+    }
+
+    #[test]
+    fn test_distance_to_edge_weights_array_edge_and_thin_region() {
+        // The array boundary counts as an edge
+        let full = Array2::from_elem((5, 5), true);
+        let weights = distance_to_edge_weights(&full);
+        assert_eq!(weights[[0, 2]], 0.0);
+        assert_eq!(weights[[2, 2]], 1.0);
+
+        // A one-pixel-wide strip has no interior
+        let strip = Array2::from_shape_fn((5, 5), |(r, _)| r == 2);
+        assert!(distance_to_edge_weights(&strip).iter().all(|&w| w == 0.0));
+    }
+}
@@ -0,0 +1,136 @@
+use ndarray::Array2;
+
+/// Fill invalid pixels with the value of the nearest valid pixel
+///
+/// Every pixel where `valid` is false takes the value of its nearest valid
+/// pixel, provided that pixel is at most `max_distance` pixels away
+/// (Euclidean). Nearest pixels are found by propagating source positions
+/// through a forward and a backward 3x3 sweep, which is exact for compact
+/// holes and a close approximation for thin, winding ones. Pixels with no
+/// valid pixel in reach are left untouched. Returns how many were filled.
+///
+/// # Panics
+/// Panics if `data` and `valid` have different shapes.
+pub fn fill_nodata_nearest(
+    data: &mut Array2<f32>,
+    valid: &Array2<bool>,
+    max_distance: usize,
+) -> usize {
+    assert_eq!(data.dim(), valid.dim(), "data and mask shapes differ");
+    let (rows, cols) = data.dim();
+
+    // Nearest valid pixel found so far for each position
+    let mut nearest: Array2<Option<(usize, usize)>> =
+        Array2::from_shape_fn((rows, cols), |(r, c)| valid[[r, c]].then_some((r, c)));
+
+    let distance2 = |(r, c): (usize, usize), (sr, sc): (usize, usize)| {
+        let (dr, dc) = (r.abs_diff(sr), c.abs_diff(sc));
+        dr * dr + dc * dc
+    };
+    // Adopt the neighbor's source at offset (dr, dc) if it is closer
+    let relax = |nearest: &mut Array2<Option<(usize, usize)>>, r: usize, c: usize, dr, dc| {
+        let (nr, nc) = (r as isize + dr, c as isize + dc);
+        if nr < 0 || nc < 0 || nr >= rows as isize || nc >= cols as isize {
+            return;
+        }
+        let Some(source) = nearest[[nr as usize, nc as usize]] else {
+            return;
+        };
+        let better = match nearest[[r, c]] {
+            Some(current) => distance2((r, c), source) < distance2((r, c), current),
+            None => true,
+        };
+        if better {
+            nearest[[r, c]] = Some(source);
+        }
+    };
+
+    // Forward pass from the top-left, backward pass from the bottom-right
+    for r in 0..rows {
+        for c in 0..cols {
+            for (dr, dc) in [(-1, -1), (-1, 0), (-1, 1), (0, -1)] {
+                relax(&mut nearest, r, c, dr, dc);
+            }
+        }
+    }
+    for r in (0..rows).rev() {
+        for c in (0..cols).rev() {
+            for (dr, dc) in [(1, 1), (1, 0), (1, -1), (0, 1)] {
+                relax(&mut nearest, r, c, dr, dc);
+            }
+        }
+    }
+
+    let mut filled = 0;
+    for ((r, c), source) in nearest.indexed_iter() {
+        if valid[[r, c]] {
+            continue;
+        }
+        if let Some(source) = *source
+            && distance2((r, c), source) <= max_distance * max_distance
+        {
+            data[[r, c]] = data[source];
+            filled += 1;
+        }
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_nodata_nearest_central_hole() {
+        let original = Array2::from_shape_fn((7, 8), |(r, c)| (r * 8 + c) as f32);
+        let valid =
+            Array2::from_shape_fn((7, 8), |(r, c)| !((2..5).contains(&r) && (2..6).contains(&c)));
+        let mut data = original.clone();
+        data.zip_mut_with(&valid, |v, &ok| if !ok { *v = f32::NAN });
+
+        let filled = fill_nodata_nearest(&mut data, &valid, 5);
+        assert_eq!(filled, 12);
+
+        // Each hole pixel holds the value of one of its nearest valid pixels
+        for ((r, c), &value) in data.indexed_iter() {
+            if valid[[r, c]] {
+                assert_eq!(value, original[[r, c]]);
+                continue;
+            }
+            let distance2 = |(sr, sc): (usize, usize)| {
+                (r.abs_diff(sr).pow(2) + c.abs_diff(sc).pow(2)) as f32
+            };
+            let closest = valid
+                .indexed_iter()
+                .filter(|(_, ok)| **ok)
+                .map(|(p, _)| distance2(p))
+                .fold(f32::INFINITY, f32::min);
+            let source = ((value as usize) / 8, (value as usize) % 8);
+            assert!(valid[source] && distance2(source) == closest, "({}, {})", r, c);
+        }
+
+        // The edge pixels of the hole copy their direct neighbors
+        assert_eq!(data[[2, 3]], original[[1, 3]]);
+        assert_eq!(data[[3, 2]], original[[3, 1]]);
+    }
+
+    #[test]
+    fn test_fill_nodata_nearest_max_distance() {
+        // Only column 0 is valid; pixels more than 2 columns away stay empty
+        let mut data =
+            Array2::from_shape_fn((3, 6), |(r, c)| if c == 0 { r as f32 } else { -1.0 });
+        let valid = Array2::from_shape_fn((3, 6), |(_, c)| c == 0);
+
+        let filled = fill_nodata_nearest(&mut data, &valid, 2);
+        assert_eq!(filled, 6);
+        for r in 0..3 {
+            assert_eq!(data[[r, 1]], r as f32);
+            assert_eq!(data[[r, 2]], r as f32);
+            assert_eq!(data[[r, 3]], -1.0);
+        }
+
+        // Nothing valid means nothing to fill from
+        let none = Array2::from_elem((3, 6), false);
+        assert_eq!(fill_nodata_nearest(&mut data, &none, 10), 0);
+    }
+}
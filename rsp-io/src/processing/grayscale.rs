@@ -0,0 +1,64 @@
+use ndarray::{Array2, Array3, Axis};
+
+/// ITU-R BT.601 luma weights for (R, G, B)
+const BT601_WEIGHTS: [f32; 3] = [0.299, 0.587, 0.114];
+
+/// Collapse a [height, width, bands] array to a single channel
+///
+/// Three-band arrays are treated as RGB and combined with `weights`
+/// (BT.601 luma when `None`). Any other band count is averaged, and
+/// `weights` is ignored. Results are rounded and saturated to u8.
+///
+/// # Panics
+/// Panics if the array has no bands.
+pub fn to_grayscale_u8(data: &Array3<u8>, weights: Option<[f32; 3]>) -> Array2<u8> {
+    let bands = data.len_of(Axis(2));
+    assert!(bands > 0, "grayscale conversion needs at least one band");
+
+    let weights = match (bands, weights) {
+        (3, weights) => weights.unwrap_or(BT601_WEIGHTS).to_vec(),
+        _ => vec![1.0 / bands as f32; bands],
+    };
+
+    data.map_axis(Axis(2), |pixel| {
+        let value: f32 = pixel.iter().zip(&weights).map(|(&v, w)| v as f32 * w).sum();
+        value.round().clamp(0.0, 255.0) as u8
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_grayscale_rgb_weights() {
+        let mut rgb = Array3::zeros((2, 2, 3));
+        let pixels = [[255, 0, 0], [0, 255, 0], [0, 0, 255], [200, 100, 50]];
+        for (i, pixel) in pixels.iter().enumerate() {
+            for (b, &v) in pixel.iter().enumerate() {
+                rgb[[i / 2, i % 2, b]] = v;
+            }
+        }
+
+        // 0.299 * 255, 0.587 * 255, 0.114 * 255, 59.8 + 58.7 + 5.7
+        let gray = to_grayscale_u8(&rgb, None);
+        assert_eq!(gray, ndarray::array![[76, 150], [29, 124]]);
+
+        // Custom weights, saturating above 255
+        let gray = to_grayscale_u8(&rgb, Some([1.0, 1.0, 1.0]));
+        assert_eq!(gray, ndarray::array![[255, 255], [255, 255]]);
+        let gray = to_grayscale_u8(&rgb, Some([0.0, 0.0, 1.0]));
+        assert_eq!(gray, ndarray::array![[0, 0], [255, 50]]);
+    }
+
+    #[test]
+    fn test_to_grayscale_other_band_counts() {
+        let single = Array3::from_shape_fn((2, 3, 1), |(r, c, _)| (r * 3 + c) as u8);
+        assert_eq!(to_grayscale_u8(&single, None), single.index_axis(Axis(2), 0));
+
+        // Four bands are averaged, ignoring the weights
+        let four = Array3::from_shape_fn((1, 2, 4), |(_, c, b)| (10 * b + c) as u8);
+        let gray = to_grayscale_u8(&four, Some([1.0, 0.0, 0.0]));
+        assert_eq!(gray, ndarray::array![[15, 16]]);
+    }
+}
@@ -0,0 +1,23 @@
+//! In-memory raster processing on [height, width, bands] arrays
+
+mod band_math;
+mod colorize;
+mod downsample;
+mod extent;
+mod feather;
+mod fill;
+mod grayscale;
+mod mosaic;
+mod pool;
+mod statistics;
+
+pub use band_math::band_math;
+pub use colorize::{colorize, ColorMap};
+pub use downsample::downsample_area;
+pub use extent::data_extent;
+pub use feather::distance_to_edge_weights;
+pub use fill::fill_nodata_nearest;
+pub use grayscale::to_grayscale_u8;
+pub use mosaic::{mosaic, BlendMode};
+pub use pool::{pool, PoolOp};
+pub use statistics::{band_statistics, band_statistics_trimmed, BandStatistics};
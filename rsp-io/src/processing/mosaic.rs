@@ -0,0 +1,171 @@
+use ndarray::{s, Array2, Array3};
+
+use super::feather::distance_to_edge_weights;
+use crate::geotransform::{invert_geotransform, pixel_to_geo};
+
+/// How [`mosaic`] combines tiles where they overlap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// The earliest tile in the list wins
+    #[default]
+    First,
+    /// The latest tile in the list wins
+    Last,
+    /// Equal-weight mean of every covering tile
+    Average,
+    /// Mean weighted by distance to each tile's data edge, hiding seams
+    FeatherByDistance,
+}
+
+/// Feather weight floor, so that pixels on every tile's edge still blend
+const MIN_FEATHER_WEIGHT: f32 = 1e-3;
+
+/// Merge georeferenced tiles into one output grid (shape: [height, width, bands])
+///
+/// Each tile is an image with its geotransform. Every output pixel center
+/// given by `out_geotransform` is mapped into each tile and takes the nearest
+/// tile pixel; `out_size` is (width, height). Tile pixels whose bands are all
+/// zero are NoData, as written by orthorectification, and never contribute.
+/// Output pixels no tile covers stay zero. Tiles with a singular
+/// geotransform are skipped.
+///
+/// # Panics
+/// Panics if the tiles have different band counts.
+pub fn mosaic(
+    tiles: &[(Array3<u8>, [f64; 6])],
+    out_geotransform: [f64; 6],
+    out_size: (usize, usize),
+    blend: BlendMode,
+) -> Array3<u8> {
+    let (width, height) = out_size;
+    let bands = tiles.first().map_or(1, |(tile, _)| tile.dim().2);
+    assert!(
+        tiles.iter().all(|(tile, _)| tile.dim().2 == bands),
+        "mosaic tiles have different band counts"
+    );
+
+    let mut sum = Array3::<f32>::zeros((height, width, bands));
+    let mut weight = Array2::<f32>::zeros((height, width));
+
+    for (tile, gt) in tiles {
+        let Some(inverse) = invert_geotransform(gt) else {
+            continue;
+        };
+        let (rows, cols, _) = tile.dim();
+        let valid = Array2::from_shape_fn((rows, cols), |(r, c)| {
+            (0..bands).any(|b| tile[[r, c, b]] != 0)
+        });
+        let feather = (blend == BlendMode::FeatherByDistance)
+            .then(|| distance_to_edge_weights(&valid));
+
+        for r in 0..height {
+            for c in 0..width {
+                let (x, y) = pixel_to_geo(&out_geotransform, c as f64 + 0.5, r as f64 + 0.5);
+                let (tc, tr) = pixel_to_geo(&inverse, x, y);
+                if !(tc >= 0.0 && tr >= 0.0 && tc < cols as f64 && tr < rows as f64) {
+                    continue;
+                }
+                let (tr, tc) = (tr as usize, tc as usize);
+                if !valid[[tr, tc]] {
+                    continue;
+                }
+
+                let w = match blend {
+                    BlendMode::First if weight[[r, c]] > 0.0 => continue,
+                    BlendMode::Last => {
+                        sum.slice_mut(s![r, c, ..]).fill(0.0);
+                        weight[[r, c]] = 0.0;
+                        1.0
+                    }
+                    BlendMode::First | BlendMode::Average => 1.0,
+                    BlendMode::FeatherByDistance => feather
+                        .as_ref()
+                        .map_or(1.0, |f| f[[tr, tc]].max(MIN_FEATHER_WEIGHT)),
+                };
+                for b in 0..bands {
+                    sum[[r, c, b]] += w * tile[[tr, tc, b]] as f32;
+                }
+                weight[[r, c]] += w;
+            }
+        }
+    }
+
+    Array3::from_shape_fn((height, width, bands), |(r, c, b)| {
+        let w = weight[[r, c]];
+        if w > 0.0 {
+            (sum[[r, c, b]] / w).round().clamp(0.0, 255.0) as u8
+        } else {
+            0
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two 10x10 tiles with 1-unit pixels, overlapping in map x 6..10
+    fn tiles() -> Vec<(Array3<u8>, [f64; 6])> {
+        let tile = |color: [u8; 3]| Array3::from_shape_fn((10, 10, 3), |(_, _, b)| color[b]);
+        vec![
+            (tile([200, 20, 60]), [0.0, 1.0, 0.0, 10.0, 0.0, -1.0]),
+            (tile([40, 120, 60]), [6.0, 1.0, 0.0, 10.0, 0.0, -1.0]),
+        ]
+    }
+
+    fn run(blend: BlendMode) -> Array3<u8> {
+        // 18 columns, so the last two lie outside both tiles
+        mosaic(&tiles(), [0.0, 1.0, 0.0, 10.0, 0.0, -1.0], (18, 10), blend)
+    }
+
+    #[test]
+    fn test_mosaic_overlap_blends() {
+        let first = run(BlendMode::First);
+        let last = run(BlendMode::Last);
+        let average = run(BlendMode::Average);
+        assert_eq!(first.dim(), (10, 18, 3));
+
+        for r in 0..10 {
+            for c in 0..18 {
+                let expected: [[u8; 3]; 3] = match c {
+                    0..6 => [[200, 20, 60]; 3],
+                    6..10 => [[200, 20, 60], [40, 120, 60], [120, 70, 60]],
+                    10..16 => [[40, 120, 60]; 3],
+                    _ => [[0, 0, 0]; 3],
+                };
+                for (out, want) in [&first, &last, &average].iter().zip(expected) {
+                    assert_eq!(out.slice(s![r, c, ..]).to_vec(), want.to_vec(), "({}, {})", r, c);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mosaic_feather_transitions_across_overlap() {
+        let feathered = run(BlendMode::FeatherByDistance);
+
+        // Outside the overlap each tile shows through unchanged
+        assert_eq!(feathered.slice(s![5, 2, ..]).to_vec(), vec![200, 20, 60]);
+        assert_eq!(feathered.slice(s![5, 13, ..]).to_vec(), vec![40, 120, 60]);
+
+        // Across the overlap red falls from the first tile's value to the second's,
+        // with a true mix in the middle where both tiles have interior weight
+        let red: Vec<u8> = (5..11).map(|c| feathered[[5, c, 0]]).collect();
+        assert_eq!((red[0], red[5]), (200, 40));
+        for pair in red.windows(2) {
+            assert!(pair[1] <= pair[0], "{:?}", red);
+        }
+        assert!(red[2] < 200 && red[2] > red[3] && red[3] > 40, "{:?}", red);
+    }
+
+    #[test]
+    fn test_mosaic_skips_nodata_pixels() {
+        let mut tiles = tiles();
+        tiles[0].0.slice_mut(s![.., 8.., ..]).fill(0);
+
+        // The second tile shows through the first tile's NoData, even with First
+        let first = mosaic(&tiles, [0.0, 1.0, 0.0, 10.0, 0.0, -1.0], (16, 10), BlendMode::First);
+        assert_eq!(first.slice(s![3, 7, ..]).to_vec(), vec![200, 20, 60]);
+        assert_eq!(first.slice(s![3, 8, ..]).to_vec(), vec![40, 120, 60]);
+    }
+}
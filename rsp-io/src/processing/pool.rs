@@ -0,0 +1,103 @@
+use ndarray::Array2;
+
+/// Reduction applied by [`pool`] over each window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolOp {
+    Mean,
+    Max,
+    Min,
+}
+
+/// Reduce sliding windows of an image to one value each
+///
+/// Output pixel `(y, x)` reduces the `window` x `window` block whose
+/// top-left corner is at `(y * stride, x * stride)`, so the output is
+/// `ceil(height / stride)` x `ceil(width / stride)`. Windows hanging over the
+/// bottom or right edge only reduce the pixels inside the image, and NaN
+/// pixels are skipped as NoData; a window with nothing valid gives NaN.
+///
+/// # Panics
+/// Panics if `window` or `stride` is zero.
+pub fn pool(src: &Array2<f32>, window: usize, stride: usize, op: PoolOp) -> Array2<f32> {
+    assert!(window > 0 && stride > 0, "pool window and stride must be positive");
+
+    let (height, width) = src.dim();
+    let out_height = height.div_ceil(stride);
+    let out_width = width.div_ceil(stride);
+
+    Array2::from_shape_fn((out_height, out_width), |(y, x)| {
+        let (r0, c0) = (y * stride, x * stride);
+        let rows = r0..(r0 + window).min(height);
+        let cols = c0..(c0 + window).min(width);
+
+        let mut count = 0usize;
+        let mut acc = match op {
+            PoolOp::Mean => 0.0,
+            PoolOp::Max => f32::NEG_INFINITY,
+            PoolOp::Min => f32::INFINITY,
+        };
+        for r in rows {
+            for c in cols.clone() {
+                let v = src[[r, c]];
+                if v.is_nan() {
+                    continue;
+                }
+                count += 1;
+                acc = match op {
+                    PoolOp::Mean => acc + v,
+                    PoolOp::Max => acc.max(v),
+                    PoolOp::Min => acc.min(v),
+                };
+            }
+        }
+
+        match (count, op) {
+            (0, _) => f32::NAN,
+            (_, PoolOp::Mean) => acc / count as f32,
+            _ => acc,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_pool_mean_constant_unchanged() {
+        let src = Array2::from_elem((7, 10), 3.25f32);
+        for (window, stride) in [(2, 2), (3, 2), (4, 4), (5, 1)] {
+            let pooled = pool(&src, window, stride, PoolOp::Mean);
+            assert_eq!(pooled.dim(), (7usize.div_ceil(stride), 10usize.div_ceil(stride)));
+            assert!(pooled.iter().all(|&v| v == 3.25), "{} {}", window, stride);
+        }
+
+        // Partial edge windows and NaN pixels average only what is valid
+        let mut src = Array2::from_shape_fn((3, 3), |(r, c)| (r * 3 + c) as f32);
+        src[[0, 1]] = f32::NAN;
+        let pooled = pool(&src, 2, 2, PoolOp::Mean);
+        assert_eq!(pooled[[0, 0]], (0.0 + 3.0 + 4.0) / 3.0);
+        assert_eq!(pooled[[0, 1]], (2.0 + 5.0) / 2.0);
+        assert_eq!(pooled[[1, 1]], 8.0);
+        assert!(pool(&Array2::from_elem((2, 2), f32::NAN), 2, 2, PoolOp::Mean)[[0, 0]].is_nan());
+    }
+
+    #[test]
+    fn test_pool_max_propagates_spike() {
+        let mut src = Array2::zeros((8, 8));
+        src[[5, 2]] = 9.0;
+
+        let pooled = pool(&src, 4, 4, PoolOp::Max);
+        assert_eq!(pooled.dim(), (2, 2));
+        assert_eq!(pooled, array![[0.0, 0.0], [9.0, 0.0]]);
+
+        // Overlapping windows each see the spike; Min ignores it
+        let overlapping = pool(&src, 3, 1, PoolOp::Max);
+        for ((r, c), &v) in overlapping.indexed_iter() {
+            let covers = (r..r + 3).contains(&5) && (c..c + 3).contains(&2);
+            assert_eq!(v, if covers { 9.0 } else { 0.0 }, "({}, {})", r, c);
+        }
+        assert!(pool(&src, 4, 4, PoolOp::Min).iter().all(|&v| v == 0.0));
+    }
+}
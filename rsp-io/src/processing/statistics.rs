@@ -0,0 +1,91 @@
+use ndarray::Array2;
+
+/// Summary statistics of a band's valid pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BandStatistics {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// Population standard deviation
+    pub std_dev: f64,
+    /// Number of pixels the statistics cover
+    pub count: usize,
+}
+
+/// Min, max, mean and standard deviation of a band
+///
+/// NaN and infinite pixels are NoData and skipped. Returns `None` if no
+/// pixel is valid.
+pub fn band_statistics(band: &Array2<f32>) -> Option<BandStatistics> {
+    band_statistics_trimmed(band, 0.0)
+}
+
+/// Band statistics with the extremes trimmed off
+///
+/// Sorts the valid pixels and drops the lowest and highest `trim_pct`
+/// percent of them before computing the statistics, so that saturated
+/// pixels or a few hot spots don't skew the mean, e.g. for auto-exposure.
+/// `min` and `max` are those of the retained pixels. With `trim_pct` 0 this
+/// is [`band_statistics`]. Returns `None` if no pixel is valid.
+///
+/// # Panics
+/// Panics if `trim_pct` is not in `[0, 50)`.
+pub fn band_statistics_trimmed(band: &Array2<f32>, trim_pct: f64) -> Option<BandStatistics> {
+    assert!((0.0..50.0).contains(&trim_pct), "trim percentage must be in [0, 50)");
+
+    let mut values: Vec<f64> = band.iter().filter(|v| v.is_finite()).map(|&v| v as f64).collect();
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable_by(f64::total_cmp);
+
+    let trim = (values.len() as f64 * trim_pct / 100.0).floor() as usize;
+    let kept = &values[trim..values.len() - trim];
+
+    let count = kept.len();
+    let mean = kept.iter().sum::<f64>() / count as f64;
+    let variance = kept.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count as f64;
+
+    Some(BandStatistics {
+        min: kept[0],
+        max: kept[count - 1],
+        mean,
+        std_dev: variance.sqrt(),
+        count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_band_statistics_plain() {
+        let band = Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, f32::NAN, 5.0]).unwrap();
+        let stats = band_statistics(&band).unwrap();
+
+        assert_eq!((stats.min, stats.max, stats.mean, stats.count), (1.0, 5.0, 3.0, 5));
+        assert!((stats.std_dev - 2f64.sqrt()).abs() < 1e-12);
+
+        assert_eq!(band_statistics(&Array2::from_elem((2, 2), f32::NAN)), None);
+    }
+
+    #[test]
+    fn test_band_statistics_trimmed_ignores_outliers() {
+        // A bulk around 100 with 2% saturated pixels on top and 2% dead ones below
+        let band = Array2::from_shape_fn((10, 50), |(r, c)| match (r * 50 + c) % 50 {
+            0 => 0.0,
+            1 => 4095.0,
+            i => 100.0 + (i % 7) as f32 - 3.0,
+        });
+
+        let plain = band_statistics(&band).unwrap();
+        let trimmed = band_statistics_trimmed(&band, 5.0).unwrap();
+
+        assert!(plain.mean > 175.0, "{}", plain.mean);
+        assert!((trimmed.mean - 100.0).abs() < 0.5, "{}", trimmed.mean);
+        assert!(trimmed.std_dev < 3.0 && plain.std_dev > 500.0);
+        assert_eq!((trimmed.min, trimmed.max), (97.0, 103.0));
+        assert_eq!(trimmed.count, 450);
+    }
+}
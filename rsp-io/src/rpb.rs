@@ -0,0 +1,234 @@
+//! RPB sidecar files (DigitalGlobe/Maxar-style RPC text format)
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use rsp_core::error::{Result, RspError};
+use rsp_core::sensor::RpcCoefficients;
+
+/// Write RPC coefficients to an `.RPB` sidecar file
+pub fn write_rpb<P: AsRef<Path>>(coeffs: &RpcCoefficients, path: P) -> Result<()> {
+    std::fs::write(path.as_ref(), format_rpb(coeffs))
+        .map_err(|e| RspError::Io(format!("Failed to write RPB file: {}", e)))
+}
+
+/// Read RPC coefficients from an `.RPB` sidecar file
+pub fn read_rpb<P: AsRef<Path>>(path: P) -> Result<RpcCoefficients> {
+    let text = std::fs::read_to_string(path.as_ref())
+        .map_err(|e| RspError::Io(format!("Failed to read RPB file: {}", e)))?;
+    parse_rpb(&text)
+}
+
+/// Render coefficients in the RPB block format
+///
+/// Coefficients are written with 17 significant digits so reading the file
+/// back reproduces them exactly.
+pub fn format_rpb(coeffs: &RpcCoefficients) -> String {
+    let mut out = String::new();
+    out.push_str("satId = \"UNKNOWN\";\n");
+    out.push_str("bandId = \"UNKNOWN\";\n");
+    out.push_str("SpecId = \"RPC00B\";\n");
+    out.push_str("BEGIN_GROUP = IMAGE\n");
+
+    // Unknown error terms are written as -1, as in provider files
+    for (key, value) in [("errBias", coeffs.err_bias), ("errRand", coeffs.err_rand)] {
+        let _ = writeln!(out, "\t{} = {};", key, value.unwrap_or(-1.0));
+    }
+
+    let scalars = [
+        ("lineOffset", coeffs.line_off),
+        ("sampOffset", coeffs.samp_off),
+        ("latOffset", coeffs.lat_off),
+        ("longOffset", coeffs.lon_off),
+        ("heightOffset", coeffs.height_off),
+        ("lineScale", coeffs.line_scale),
+        ("sampScale", coeffs.samp_scale),
+        ("latScale", coeffs.lat_scale),
+        ("longScale", coeffs.lon_scale),
+        ("heightScale", coeffs.height_scale),
+    ];
+    for (key, value) in scalars {
+        let _ = writeln!(out, "\t{} = {};", key, value);
+    }
+
+    let blocks = [
+        ("lineNumCoef", &coeffs.line_num_coeff),
+        ("lineDenCoef", &coeffs.line_den_coeff),
+        ("sampNumCoef", &coeffs.samp_num_coeff),
+        ("sampDenCoef", &coeffs.samp_den_coeff),
+    ];
+    for (key, values) in blocks {
+        let _ = writeln!(out, "\t{} = (", key);
+        let formatted: Vec<String> = values
+            .iter()
+            .map(|v| format!("\t\t\t{:+.16E}", v))
+            .collect();
+        let _ = writeln!(out, "{});", formatted.join(",\n"));
+    }
+
+    out.push_str("END_GROUP = IMAGE\n");
+    out.push_str("END;\n");
+    out
+}
+
+/// Parse RPC coefficients from RPB text
+pub fn parse_rpb(text: &str) -> Result<RpcCoefficients> {
+    // Statements are `key = value;`, values may span lines
+    let mut fields = HashMap::new();
+    for statement in text.split(';') {
+        // BEGIN_GROUP lines have no terminator and prefix the next statement,
+        // so the key is the last word before the final '='
+        if let Some((key, value)) = statement.rsplit_once('=') {
+            let key = key.split_whitespace().last().unwrap_or("");
+            fields.insert(key.to_string(), value.trim().to_string());
+        }
+    }
+
+    Ok(RpcCoefficients {
+        line_num_coeff: parse_coeff_list(&fields, "lineNumCoef")?,
+        line_den_coeff: parse_coeff_list(&fields, "lineDenCoef")?,
+        samp_num_coeff: parse_coeff_list(&fields, "sampNumCoef")?,
+        samp_den_coeff: parse_coeff_list(&fields, "sampDenCoef")?,
+
+        lat_off: parse_value(&fields, "latOffset")?,
+        lat_scale: parse_value(&fields, "latScale")?,
+        lon_off: parse_value(&fields, "longOffset")?,
+        lon_scale: parse_value(&fields, "longScale")?,
+        height_off: parse_value(&fields, "heightOffset")?,
+        height_scale: parse_value(&fields, "heightScale")?,
+        line_off: parse_value(&fields, "lineOffset")?,
+        line_scale: parse_value(&fields, "lineScale")?,
+        samp_off: parse_value(&fields, "sampOffset")?,
+        samp_scale: parse_value(&fields, "sampScale")?,
+        // Optional; missing or unreadable error terms are unknown
+        err_bias: parse_value(&fields, "errBias").ok().and_then(RpcCoefficients::error_term),
+        err_rand: parse_value(&fields, "errRand").ok().and_then(RpcCoefficients::error_term),
+    })
+}
+
+fn parse_value(fields: &HashMap<String, String>, key: &str) -> Result<f64> {
+    let value = fields
+        .get(key)
+        .ok_or_else(|| RspError::Io(format!("Missing RPB field: {}", key)))?;
+
+    value
+        .parse()
+        .map_err(|_| RspError::Io(format!("Failed to parse RPB field: {}", key)))
+}
+
+fn parse_coeff_list(fields: &HashMap<String, String>, key: &str) -> Result<[f64; 20]> {
+    let value = fields
+        .get(key)
+        .ok_or_else(|| RspError::Io(format!("Missing RPB field: {}", key)))?;
+
+    let list = value.trim_start_matches('(').trim_end_matches(')');
+    let parsed: Vec<f64> = list
+        .split(',')
+        .map(|v| v.trim().parse())
+        .collect::<std::result::Result<_, _>>()
+        .map_err(|_| RspError::Io(format!("Failed to parse RPB coefficients: {}", key)))?;
+
+    parsed.try_into().map_err(|v: Vec<f64>| {
+        RspError::Io(format!(
+            "RPB field {} has {} coefficients, expected 20",
+            key,
+            v.len()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_coeffs() -> RpcCoefficients {
+        let mut coeffs = RpcCoefficients {
+            lat_off: 39.1234,
+            lat_scale: 0.0512,
+            lon_off: -77.0456,
+            lon_scale: 0.0634,
+            height_off: 123.0,
+            height_scale: 501.0,
+            line_off: 17000.5,
+            line_scale: 17001.5,
+            samp_off: 12000.25,
+            samp_scale: 12001.75,
+            ..Default::default()
+        };
+        for i in 0..20 {
+            let x = i as f64;
+            coeffs.line_num_coeff[i] = (x + 1.0) * 1.1e-3 / 3.0;
+            coeffs.line_den_coeff[i] = -(x * 0.7).sin() * 1e-5;
+            coeffs.samp_num_coeff[i] = 1.0 / (x + 7.0);
+            coeffs.samp_den_coeff[i] = if i == 0 { 1.0 } else { x.sqrt() * -1e-7 };
+        }
+        coeffs
+    }
+
+    fn assert_same(a: &RpcCoefficients, b: &RpcCoefficients) {
+        assert_eq!(a.line_num_coeff, b.line_num_coeff);
+        assert_eq!(a.line_den_coeff, b.line_den_coeff);
+        assert_eq!(a.samp_num_coeff, b.samp_num_coeff);
+        assert_eq!(a.samp_den_coeff, b.samp_den_coeff);
+        assert_eq!(a.lat_off, b.lat_off);
+        assert_eq!(a.lat_scale, b.lat_scale);
+        assert_eq!(a.lon_off, b.lon_off);
+        assert_eq!(a.lon_scale, b.lon_scale);
+        assert_eq!(a.height_off, b.height_off);
+        assert_eq!(a.height_scale, b.height_scale);
+        assert_eq!(a.line_off, b.line_off);
+        assert_eq!(a.line_scale, b.line_scale);
+        assert_eq!(a.samp_off, b.samp_off);
+        assert_eq!(a.samp_scale, b.samp_scale);
+        assert_eq!(a.err_bias, b.err_bias);
+        assert_eq!(a.err_rand, b.err_rand);
+    }
+
+    #[test]
+    fn test_rpb_file_roundtrip() {
+        let coeffs = sample_coeffs();
+        let path = std::env::temp_dir().join(format!("rsp_io_rpb_{}.RPB", std::process::id()));
+
+        write_rpb(&coeffs, &path).unwrap();
+        let read = read_rpb(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_same(&coeffs, &read);
+    }
+
+    #[test]
+    fn test_parse_rpb_with_error_terms() {
+        let text = format_rpb(&sample_coeffs()).replace(
+            "BEGIN_GROUP = IMAGE\n",
+            "BEGIN_GROUP = IMAGE\n\terrBias =   -1.0;\n\terrRand =   -1.0;\n",
+        );
+
+        let parsed = parse_rpb(&text).unwrap();
+        assert_same(&sample_coeffs(), &parsed);
+    }
+
+    #[test]
+    fn test_rpb_error_terms_roundtrip() {
+        let mut coeffs = sample_coeffs();
+        assert!(format_rpb(&coeffs).contains("\terrBias = -1;\n"));
+
+        coeffs.err_bias = Some(4.5);
+        coeffs.err_rand = Some(0.75);
+        let parsed = parse_rpb(&format_rpb(&coeffs)).unwrap();
+        assert_same(&coeffs, &parsed);
+    }
+
+    #[test]
+    fn test_parse_rpb_missing_field() {
+        let text = format_rpb(&sample_coeffs()).replace("\tlatScale", "\tnotLatScale");
+        assert!(parse_rpb(&text).is_err());
+    }
+
+    #[test]
+    fn test_parse_rpb_short_coefficient_list() {
+        let mut fields = HashMap::new();
+        fields.insert("lineNumCoef".to_string(), "(1.0, 2.0)".to_string());
+        assert!(parse_coeff_list(&fields, "lineNumCoef").is_err());
+    }
+}